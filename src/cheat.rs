@@ -0,0 +1,286 @@
+//! The cheat entry model shared by built-in datasets, packs and importers.
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::keys::{self, Keys};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheatEntry {
+    pub category: String,
+    pub command: String,
+    pub description: String,
+    /// Longer explanation shown in the detail pane.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Translated descriptions keyed by language code (e.g. `de`, `ja`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<Difficulty>,
+    /// Mode the keys are typed in; inferred from the command when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<Mode>,
+    /// Free-form labels matched by `#tag` in the search bar.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Worked examples shown in the detail pane.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<Example>,
+    /// Animated demo played in the detail pane.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub demo: Vec<DemoStep>,
+    /// Commands of neighbouring entries, followed from the detail pane.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<String>,
+    /// Vim help tag for this entry (e.g. `motion.txt`, `:s_flags`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help_topic: Option<String>,
+    /// For user entries that share a command with a built-in one: append
+    /// the description to the built-in entry instead of replacing it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub annotate: bool,
+    /// Stable name of the entry within its pack (e.g. `yank-line`), kept
+    /// when the command is reworded so favorites follow the entry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Name of the pack the entry was loaded from, which picks its tab;
+    /// `None` for the editor profile's own entries. Not part of the pack
+    /// format.
+    #[serde(skip)]
+    pub pack: Option<String>,
+    /// Icon of the entry's category, from the `[icons]` table of its pack.
+    #[serde(skip)]
+    pub icon: Option<Icon>,
+    /// Local pack file the entry was loaded from, which edits are saved to.
+    #[serde(skip)]
+    pub file: Option<PathBuf>,
+}
+
+/// Icon drawn before a category name: a Nerd Font glyph or emoji, and
+/// plain text for terminals without either.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Icon {
+    pub glyph: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ascii: Option<String>,
+}
+
+/// A before → keystrokes → after demonstration. Buffers may span several
+/// lines; `|` marks the cursor position.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Example {
+    pub before: String,
+    pub keys: String,
+    pub after: String,
+}
+
+/// One frame of an animated demo: the buffer after typing `keys`, with `|`
+/// marking the cursor. The first frame usually has no keys.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DemoStep {
+    #[serde(default)]
+    pub keys: String,
+    pub buffer: String,
+}
+
+impl CheatEntry {
+    pub fn new(
+        category: impl Into<String>,
+        command: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            category: category.into(),
+            command: command.into(),
+            description: description.into(),
+            notes: None,
+            translations: BTreeMap::new(),
+            difficulty: None,
+            mode: None,
+            tags: Vec::new(),
+            examples: Vec::new(),
+            demo: Vec::new(),
+            related: Vec::new(),
+            help_topic: None,
+            annotate: false,
+            id: None,
+            pack: None,
+            icon: None,
+            file: None,
+        }
+    }
+
+    /// The description in `lang` (e.g. `de` or `de_DE.UTF-8`), falling back
+    /// to English when there is no translation.
+    pub fn description_in(&self, lang: Option<&str>) -> &str {
+        let Some(lang) = lang else {
+            return &self.description;
+        };
+        let primary = lang.split(['_', '-', '.']).next().unwrap_or(lang);
+        self.translations
+            .get(lang)
+            .or_else(|| self.translations.get(primary))
+            .map_or(&self.description, String::as_str)
+    }
+
+    /// Identity of the entry that favorites are kept by. Entries whose pack
+    /// names them keep theirs when their command or category changes:
+    /// `id:` and the pack and `id`. The others, like the built-in ones, are
+    /// known by where they are: `cmd:` and their category and command.
+    pub fn stable_id(&self) -> String {
+        match &self.id {
+            Some(id) => format!("id:{}/{id}", self.pack.as_deref().unwrap_or_default()),
+            None => format!("cmd:{}/{}", self.category, self.command),
+        }
+    }
+
+    /// The command parsed into structured keystrokes.
+    pub fn keys(&self) -> Keys {
+        keys::parse(&self.command)
+    }
+
+    /// Whether the entry passes the `level` filter. Entries without a
+    /// difficulty (those of packs and of most editor profiles) pass any.
+    pub fn at_level(&self, level: Option<Difficulty>) -> bool {
+        level.is_none() || self.difficulty.is_none() || self.difficulty == level
+    }
+
+    /// The entry's mode: the explicit one, or command-line for `:` commands
+    /// and normal for everything else.
+    pub fn mode(&self) -> Mode {
+        self.mode.unwrap_or(if self.command.starts_with(':') {
+            Mode::CommandLine
+        } else {
+            Mode::Normal
+        })
+    }
+
+    pub fn in_mode(mut self, mode: Mode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = Some(difficulty);
+        self
+    }
+
+    pub fn tags(mut self, tags: &[&str]) -> Self {
+        self.tags = tags.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    pub fn example(mut self, before: &str, keys: &str, after: &str) -> Self {
+        self.examples.push(Example {
+            before: before.to_string(),
+            keys: keys.to_string(),
+            after: after.to_string(),
+        });
+        self
+    }
+
+    /// Frames of the animated demo as `(keys, buffer)` pairs.
+    pub fn demo(mut self, steps: &[(&str, &str)]) -> Self {
+        self.demo = steps
+            .iter()
+            .map(|(keys, buffer)| DemoStep {
+                keys: keys.to_string(),
+                buffer: buffer.to_string(),
+            })
+            .collect();
+        self
+    }
+
+    pub fn related(mut self, commands: &[&str]) -> Self {
+        self.related = commands.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    pub fn help_topic(mut self, topic: &str) -> Self {
+        self.help_topic = Some(topic.to_string());
+        self
+    }
+
+    pub fn notes(mut self, notes: &str) -> Self {
+        self.notes = Some(notes.to_string());
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Advanced,
+}
+
+impl Difficulty {
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Beginner => "beginner",
+            Difficulty::Intermediate => "intermediate",
+            Difficulty::Advanced => "advanced",
+        }
+    }
+
+    /// Cycle for the level filter key: all → beginner → … → advanced → all.
+    pub fn cycle(level: Option<Self>) -> Option<Self> {
+        match level {
+            None => Some(Difficulty::Beginner),
+            Some(Difficulty::Beginner) => Some(Difficulty::Intermediate),
+            Some(Difficulty::Intermediate) => Some(Difficulty::Advanced),
+            Some(Difficulty::Advanced) => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Mode {
+    Normal,
+    Insert,
+    Visual,
+    CommandLine,
+    Terminal,
+}
+
+impl Mode {
+    pub fn label(self) -> &'static str {
+        match self {
+            Mode::Normal => "normal",
+            Mode::Insert => "insert",
+            Mode::Visual => "visual",
+            Mode::CommandLine => "command-line",
+            Mode::Terminal => "terminal",
+        }
+    }
+
+    /// Mode for a mapping-mode label as reported by `:map` / `nvim_get_keymap`
+    /// (e.g. `visual/select`); `None` for modes without a counterpart.
+    pub fn from_map_label(label: &str) -> Option<Self> {
+        match label {
+            "normal" | "normal/visual" | "operator-pending" => Some(Mode::Normal),
+            "insert" | "insert/cmdline" => Some(Mode::Insert),
+            "visual" | "visual/select" | "select" => Some(Mode::Visual),
+            "cmdline" => Some(Mode::CommandLine),
+            "terminal" => Some(Mode::Terminal),
+            _ => None,
+        }
+    }
+
+    /// Cycle for the mode filter key: all → normal → … → terminal → all.
+    pub fn cycle(mode: Option<Self>) -> Option<Self> {
+        let all = Self::value_variants();
+        match mode {
+            None => all.first().copied(),
+            Some(mode) => {
+                let pos = all.iter().position(|m| *m == mode).unwrap_or(0);
+                all.get(pos + 1).copied()
+            }
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Copying to the system clipboard with the OSC 52 escape sequence, which
+//! most terminals (and tmux with `set-clipboard on`) pass on, also over SSH.
+
+use std::io::{self, Write};
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+/// Put `text` on the clipboard of the terminal vimbo runs in.
+pub fn copy(text: &str) -> Result<()> {
+    let mut out = io::stdout();
+    write!(out, "\x1b]52;c;{}\x07", STANDARD.encode(text))
+        .and_then(|()| out.flush())
+        .context("failed to write to the terminal")
+}
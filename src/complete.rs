@@ -0,0 +1,96 @@
+//! Completions for the word being typed in the search bar, drawn from the
+//! loaded entries' categories, tags and commands.
+
+use crate::{query::Field, CheatEntry};
+
+/// Suggestions shown at most.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Words the search bar can complete, collected when the cheats are loaded.
+#[derive(Debug, Default)]
+pub struct Completions {
+    /// Lowercased words from category names (e.g. "registers").
+    categories: Vec<String>,
+    tags: Vec<String>,
+    /// Single-word commands (e.g. ":tabnew", "yiw").
+    commands: Vec<String>,
+}
+
+impl Completions {
+    pub fn build(cheats: &[CheatEntry]) -> Self {
+        let mut completions = Self::default();
+        for c in cheats {
+            let words = c
+                .category
+                .split(|ch: char| !ch.is_alphanumeric())
+                .filter(|w| w.len() > 2)
+                .map(str::to_lowercase);
+            push_new(&mut completions.categories, words);
+            push_new(
+                &mut completions.tags,
+                c.tags.iter().map(|t| t.to_lowercase()),
+            );
+            let commands = c
+                .command
+                .split(" / ")
+                .filter(|alt| alt.len() > 1 && !alt.contains([' ', '{']))
+                .map(str::to_string);
+            push_new(&mut completions.commands, commands);
+        }
+        completions
+    }
+
+    /// Completions for the last word of `query`, each the full word to put
+    /// in its place. Empty once the word is finished with a space.
+    pub fn suggest(&self, query: &str) -> Vec<String> {
+        if query.ends_with(char::is_whitespace) {
+            return Vec::new();
+        }
+        let Some(word) = query.split_whitespace().last() else {
+            return Vec::new();
+        };
+        let lower = word.to_lowercase();
+        let (prefix, partial, sources): (&str, &str, Vec<&[String]>) =
+            if let Some(tag) = lower.strip_prefix('#') {
+                ("#", tag, vec![&self.tags])
+            } else if let Some((scope, term)) = lower.split_once(':')
+                && let Some(field) = Field::from_prefix(scope)
+            {
+                let source: &[String] = match field {
+                    Field::Command => &self.commands,
+                    Field::Category => &self.categories,
+                    Field::Description => &[],
+                };
+                (&word[..scope.len() + 1], term, vec![source])
+            } else {
+                (
+                    "",
+                    lower.as_str(),
+                    vec![&self.categories, &self.tags, &self.commands],
+                )
+            };
+        if partial.len() < 2 {
+            return Vec::new();
+        }
+        let mut suggestions = Vec::new();
+        for candidate in sources.into_iter().flatten() {
+            let suggestion = format!("{prefix}{candidate}");
+            if candidate.to_lowercase().starts_with(partial)
+                && candidate.len() > partial.len()
+                && !suggestions.contains(&suggestion)
+            {
+                suggestions.push(suggestion);
+            }
+        }
+        suggestions.truncate(MAX_SUGGESTIONS);
+        suggestions
+    }
+}
+
+fn push_new(words: &mut Vec<String>, new: impl Iterator<Item = String>) {
+    for word in new {
+        if !words.contains(&word) {
+            words.push(word);
+        }
+    }
+}
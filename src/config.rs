@@ -0,0 +1,176 @@
+//! User configuration loaded from `config.toml` in the platform config
+//! directory (e.g. `~/.config/vimbo/config.toml`).
+
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+use log::debug;
+use serde::Deserialize;
+
+use crate::{
+    editor::Editor,
+    keymap::{Action, Binding},
+    paths,
+    theme::ThemeConfig,
+};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Bundled cheat packs to enable (e.g. `["surround", "fugitive"]`).
+    pub packs: Vec<String>,
+    /// Import mappings from the default vimrc / init.vim locations.
+    pub vimrc: bool,
+    /// Language for entry descriptions (e.g. `de`); English when unset.
+    pub lang: Option<String>,
+    /// Editor profile shown when `--editor` is not given.
+    pub editor: Option<Editor>,
+    /// Keep search history across runs.
+    pub history: bool,
+    /// Remember which entries are used most and rank them higher across runs.
+    pub usage: bool,
+    /// Start the TUI where the last session left off, as with `--resume`.
+    pub resume: bool,
+    /// Row density of the list on startup.
+    pub density: Density,
+    /// How category icons from packs are drawn.
+    pub icons: Icons,
+    /// Row numbers in front of the results on startup.
+    pub numbers: Numbers,
+    /// Milliseconds the TUI waits for a key before redrawing (the clock,
+    /// toasts and demos).
+    pub tick_rate: Option<u64>,
+    /// Template of the status line, e.g. `{shown}/{total}  {filters}{clock}`.
+    pub status: Option<String>,
+    /// Keys bound to TUI actions instead of the defaults, e.g.
+    /// `down = ["j", "<C-n>"]`.
+    pub keys: BTreeMap<Action, Binding>,
+    /// Extra search synonyms, e.g. `grab = ["yank"]`.
+    pub synonyms: BTreeMap<String, Vec<String>>,
+    /// Theme preset and style overrides per UI element, e.g.
+    /// `command = { fg = "blue" }`.
+    pub theme: ThemeConfig,
+}
+
+/// How much room each entry gets in the list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Density {
+    /// One line per entry.
+    #[default]
+    Compact,
+    /// The description wrapped below the command, and a blank line between
+    /// entries.
+    Comfortable,
+}
+
+impl Density {
+    pub fn toggle(self) -> Self {
+        match self {
+            Density::Compact => Density::Comfortable,
+            Density::Comfortable => Density::Compact,
+        }
+    }
+}
+
+/// Row numbers in front of the results, like Vim's `number` and
+/// `relativenumber`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Numbers {
+    #[default]
+    Off,
+    Absolute,
+    /// Distance from the selected row, which shows its own number.
+    Relative,
+}
+
+impl Numbers {
+    pub fn next(self) -> Self {
+        match self {
+            Numbers::Off => Numbers::Absolute,
+            Numbers::Absolute => Numbers::Relative,
+            Numbers::Relative => Numbers::Off,
+        }
+    }
+}
+
+/// How category icons are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Icons {
+    /// Nerd Font glyphs and emoji.
+    #[default]
+    Glyph,
+    /// The icons' plain-text fallbacks, for terminals without such fonts.
+    Ascii,
+    /// No icons.
+    Off,
+}
+
+impl Config {
+    /// Location of the config file, if given with `--config` or the
+    /// platform has a config directory.
+    pub fn path() -> Option<PathBuf> {
+        paths::config_file()
+    }
+
+    /// Location of the config file, or an error on platforms without a
+    /// config directory.
+    fn require_path() -> Result<PathBuf> {
+        Self::path().context("could not determine the user config directory")
+    }
+
+    /// Load the config file, falling back to defaults when it does not exist.
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        if !path.exists() && paths::config_override().is_some() {
+            bail!("no config file at {}", path.display());
+        }
+        if !path.exists() {
+            debug!("no config at {}", path.display());
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let config = toml::from_str(&text)
+            .with_context(|| format!("invalid config file {}", path.display()))?;
+        debug!("loaded config from {}", path.display());
+        Ok(config)
+    }
+}
+
+/// `vimbo config path`: where the config file is read from, whether or not
+/// it exists yet.
+pub fn print_path() -> Result<()> {
+    println!("{}", Config::require_path()?.display());
+    Ok(())
+}
+
+/// `vimbo config dirs`: where each kind of file is kept.
+pub fn print_dirs() -> Result<()> {
+    if let Some(profile) = paths::profile() {
+        println!("profile:     {profile}");
+    }
+    println!("config file: {}", Config::require_path()?.display());
+    println!("data:        {}", paths::data_dir()?.display());
+    println!("packs:       {}", paths::packs_dir()?.display());
+    Ok(())
+}
+
+/// `vimbo config show`: the config file as it is on disk.
+pub fn print_file() -> Result<()> {
+    let path = Config::require_path()?;
+    if !path.exists() {
+        bail!(
+            "no config file at {} (vimbo runs with the defaults)",
+            path.display()
+        );
+    }
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    print!("{text}");
+    Ok(())
+}
@@ -0,0 +1,7 @@
+//! Built-in cheat datasets, one module per editor profile.
+
+pub mod evil;
+pub mod helix;
+pub mod kakoune;
+pub mod nvim;
+pub mod vim;
@@ -0,0 +1,31 @@
+//! Evil (Emacs Vim emulation): Emacs-specific entries and the places where
+//! Evil behaves differently from real Vim.
+
+use crate::CheatEntry;
+
+pub fn cheats() -> Vec<CheatEntry> {
+    vec![
+        CheatEntry::new("Evil - states", "Ctrl + z", "toggle between Evil normal state and Emacs state"),
+        CheatEntry::new("Evil - states", "Esc", "return to normal state (note: Emacs keys like C-g also cancel)"),
+        CheatEntry::new("Evil - states", "M-x evil-mode", "enable / disable Evil globally"),
+
+        CheatEntry::new("Evil - Emacs bridge", "M-x {command}", "run any Emacs command from normal state"),
+        CheatEntry::new("Evil - Emacs bridge", "Ctrl + x Ctrl + f", "find file (Emacs binding, works in normal state)"),
+        CheatEntry::new("Evil - Emacs bridge", "Ctrl + x b", "switch buffer (Emacs alternative to :b)"),
+        CheatEntry::new("Evil - Emacs bridge", "Ctrl + h k {key}", "describe what a key does (Emacs help)"),
+
+        CheatEntry::new("Evil - differences", "u / Ctrl + r", "undo / redo; needs evil-undo-system (undo-redo, undo-fu or undo-tree) for redo"),
+        CheatEntry::new("Evil - differences", ":%s/old/new/g", "substitute; uses Emacs regex syntax, not Vim's (no \\v very-magic)"),
+        CheatEntry::new("Evil - differences", "/pattern", "search; Emacs regexps by default (see evil-search-module)"),
+        CheatEntry::new("Evil - differences", "Ctrl + u", "scroll up; only if evil-want-C-u-scroll is set (else universal-argument)"),
+        CheatEntry::new("Evil - differences", "Ctrl + i", "jump forward; conflicts with TAB in terminal Emacs (evil-want-C-i-jump)"),
+        CheatEntry::new("Evil - differences", ":ls / :buffers", "list buffers; shows the Emacs buffer list incl. special buffers"),
+        CheatEntry::new("Evil - differences", ":set {option}", "only a small subset of Vim options exists; use Emacs variables instead"),
+        CheatEntry::new("Evil - differences", "q{reg} ... q", "record macro; stored as an Emacs keyboard macro in the register"),
+        CheatEntry::new("Evil - differences", "\"+y / \"+p", "system clipboard; depends on Emacs select-enable-clipboard"),
+
+        CheatEntry::new("Evil - packages", "gc{motion}", "toggle comments (evil-commentary / evil-nerd-commenter)"),
+        CheatEntry::new("Evil - packages", "ys{motion}{char}", "surround (evil-surround, mirrors vim-surround)"),
+        CheatEntry::new("Evil - packages", "gx{motion} / cx{motion}", "exchange two regions (evil-exchange)"),
+    ]
+}
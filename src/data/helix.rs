@@ -0,0 +1,55 @@
+//! Helix: selection-first modal editing.
+
+use crate::CheatEntry;
+
+pub fn cheats() -> Vec<CheatEntry> {
+    vec![
+        CheatEntry::new("Helix - basics", ":q / :q!", "quit / quit discarding changes"),
+        CheatEntry::new("Helix - basics", ":w / :wq", "write / write and quit"),
+        CheatEntry::new("Helix - basics", ":o {file}", "open file"),
+        CheatEntry::new("Helix - basics", ":help / :tutor", "open the command list / the interactive tutor"),
+
+        CheatEntry::new("Helix - modes", "i / a", "insert before / after selection"),
+        CheatEntry::new("Helix - modes", "v", "enter select (extend) mode"),
+        CheatEntry::new("Helix - modes", "Esc", "return to normal mode"),
+        CheatEntry::new("Helix - modes", "Space", "open space mode (pickers, LSP, clipboard)"),
+
+        CheatEntry::new("Helix - movement", "h j k l", "move cursor left / down / up / right"),
+        CheatEntry::new("Helix - movement", "w / b / e", "select to next word start / previous start / end"),
+        CheatEntry::new("Helix - movement", "f{char} / t{char}", "select to / till next char"),
+        CheatEntry::new("Helix - movement", "gg / ge", "go to first / last line of file"),
+        CheatEntry::new("Helix - movement", "gh / gl / gs", "go to line start / end / first non-blank"),
+        CheatEntry::new("Helix - movement", "Ctrl + u / Ctrl + d", "scroll half-page up / down"),
+
+        CheatEntry::new("Helix - selection", "x", "select current line (repeat to extend)"),
+        CheatEntry::new("Helix - selection", "%", "select entire file"),
+        CheatEntry::new("Helix - selection", "s", "select regex matches inside selections"),
+        CheatEntry::new("Helix - selection", ";", "collapse selection to cursor"),
+        CheatEntry::new("Helix - selection", "mi{char} / ma{char}", "select inside / around a text object"),
+        CheatEntry::new("Helix - selection", "mm", "go to matching bracket"),
+
+        CheatEntry::new("Helix - editing", "d / c", "delete / change selection"),
+        CheatEntry::new("Helix - editing", "y / p / P", "yank selection / paste after / before"),
+        CheatEntry::new("Helix - editing", "u / U", "undo / redo"),
+        CheatEntry::new("Helix - editing", "r{char}", "replace selection with char"),
+        CheatEntry::new("Helix - editing", "> / <", "indent / dedent selection"),
+        CheatEntry::new("Helix - editing", "Ctrl + c", "toggle comments"),
+
+        CheatEntry::new("Helix - multiple cursors", "C", "copy selection onto the next line (add cursor)"),
+        CheatEntry::new("Helix - multiple cursors", ",", "keep only the primary selection"),
+        CheatEntry::new("Helix - multiple cursors", "Alt + s", "split selection on newlines"),
+
+        CheatEntry::new("Helix - search", "/ / ?", "search forward / backward"),
+        CheatEntry::new("Helix - search", "n / N", "select next / previous search match"),
+        CheatEntry::new("Helix - search", "*", "use selection as search pattern"),
+
+        CheatEntry::new("Helix - space mode", "Space f", "file picker"),
+        CheatEntry::new("Helix - space mode", "Space b", "buffer picker"),
+        CheatEntry::new("Helix - space mode", "Space /", "global search in workspace"),
+        CheatEntry::new("Helix - space mode", "Space k / Space r / Space a", "hover docs / rename symbol / code action"),
+        CheatEntry::new("Helix - space mode", "Space y / Space p", "yank to / paste from system clipboard"),
+
+        CheatEntry::new("Helix - goto", "gd / gr / gi", "go to definition / references / implementation"),
+        CheatEntry::new("Helix - goto", "Ctrl + o / Ctrl + i", "jump backward / forward in jumplist"),
+    ]
+}
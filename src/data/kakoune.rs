@@ -0,0 +1,49 @@
+//! Kakoune: object-verb editing with multiple selections.
+
+use crate::CheatEntry;
+
+pub fn cheats() -> Vec<CheatEntry> {
+    vec![
+        CheatEntry::new("Kakoune - basics", ":q / :q!", "quit / quit discarding changes"),
+        CheatEntry::new("Kakoune - basics", ":w / :wq", "write / write and quit"),
+        CheatEntry::new("Kakoune - basics", ":e {file}", "edit / open file"),
+        CheatEntry::new("Kakoune - basics", ":doc {topic}", "open documentation (e.g. :doc keys)"),
+
+        CheatEntry::new("Kakoune - modes", "i / a", "insert before / after selection"),
+        CheatEntry::new("Kakoune - modes", "o / O", "open new line below / above"),
+        CheatEntry::new("Kakoune - modes", "Esc", "return to normal mode"),
+
+        CheatEntry::new("Kakoune - movement", "h j k l", "move cursor left / down / up / right"),
+        CheatEntry::new("Kakoune - movement", "H J K L", "extend selection left / down / up / right"),
+        CheatEntry::new("Kakoune - movement", "w / b / e", "select to next word start / previous start / end"),
+        CheatEntry::new("Kakoune - movement", "f{char} / t{char}", "select to / till next char"),
+        CheatEntry::new("Kakoune - movement", "gg / ge", "go to first / last line of file"),
+        CheatEntry::new("Kakoune - movement", "gh / gl / gi", "go to line start / end / first non-blank"),
+        CheatEntry::new("Kakoune - movement", "m / M", "select / extend to matching bracket"),
+
+        CheatEntry::new("Kakoune - selection", "x", "select current line (repeat to extend)"),
+        CheatEntry::new("Kakoune - selection", "%", "select entire buffer"),
+        CheatEntry::new("Kakoune - selection", "s", "select regex matches inside selections"),
+        CheatEntry::new("Kakoune - selection", "S", "split selections on a regex"),
+        CheatEntry::new("Kakoune - selection", "Alt + i / Alt + a", "select inside / around a text object"),
+        CheatEntry::new("Kakoune - selection", ";", "reduce selections to their cursor"),
+        CheatEntry::new("Kakoune - selection", "Space / Alt + Space", "keep only main / remove main selection"),
+        CheatEntry::new("Kakoune - selection", "C", "copy selection onto the next line"),
+
+        CheatEntry::new("Kakoune - editing", "d / c", "delete / change selection"),
+        CheatEntry::new("Kakoune - editing", "y / p / P", "yank selection / paste after / before"),
+        CheatEntry::new("Kakoune - editing", "R", "replace selection with yanked text"),
+        CheatEntry::new("Kakoune - editing", "u / U", "undo / redo"),
+        CheatEntry::new("Kakoune - editing", "r{char}", "replace each selected char with char"),
+        CheatEntry::new("Kakoune - editing", "> / <", "indent / dedent selection"),
+        CheatEntry::new("Kakoune - editing", "| {command}", "pipe selections through a shell command"),
+
+        CheatEntry::new("Kakoune - search", "/ / Alt + /", "search forward / backward"),
+        CheatEntry::new("Kakoune - search", "n / N", "select next match / add next match to selections"),
+        CheatEntry::new("Kakoune - search", "*", "use selection as search pattern"),
+
+        CheatEntry::new("Kakoune - goto", "gd", "go to definition (with LSP / ctags)"),
+        CheatEntry::new("Kakoune - goto", "Ctrl + o / Ctrl + i", "jump backward / forward in jumplist"),
+        CheatEntry::new("Kakoune - goto", "ga", "go to last buffer"),
+    ]
+}
@@ -0,0 +1,38 @@
+//! Neovim-only commands and defaults, layered on top of the Vim dataset.
+
+use crate::{CheatEntry, Mode};
+
+pub fn cheats() -> Vec<CheatEntry> {
+    vec![
+        CheatEntry::new("Neovim - terminal", ":terminal / :term", "open a terminal buffer in the current window"),
+        CheatEntry::new("Neovim - terminal", ":split | terminal", "open a terminal in a horizontal split"),
+        CheatEntry::new("Neovim - terminal", "i / a (terminal buffer)", "enter terminal mode to type into the shell"),
+        CheatEntry::new("Neovim - terminal", "Ctrl + \\ Ctrl + n", "leave terminal mode back to normal mode").in_mode(Mode::Terminal),
+
+        CheatEntry::new("Neovim - health", ":checkhealth", "run health checks for Neovim and plugins"),
+        CheatEntry::new("Neovim - health", ":checkhealth {plugin}", "run health checks for a single plugin (e.g. vim.lsp)"),
+
+        CheatEntry::new("Neovim - Lua", ":lua {code}", "run a Lua chunk (e.g. :lua print(vim.version()))"),
+        CheatEntry::new("Neovim - Lua", ":lua =expr", "evaluate and pretty-print a Lua expression"),
+        CheatEntry::new("Neovim - Lua", "vim.keymap.set(mode, lhs, rhs, opts)", "define a key mapping from Lua"),
+        CheatEntry::new("Neovim - Lua", "vim.keymap.del(mode, lhs)", "remove a key mapping from Lua"),
+        CheatEntry::new("Neovim - Lua", "vim.opt.{option} = value", "set an option from Lua (e.g. vim.opt.number = true)"),
+        CheatEntry::new("Neovim - Lua", ":source % / :luafile %", "re-run the current Vimscript / Lua file"),
+
+        CheatEntry::new("Neovim - LSP", "K", "hover documentation for symbol under cursor"),
+        CheatEntry::new("Neovim - LSP", "grn", "rename symbol under cursor"),
+        CheatEntry::new("Neovim - LSP", "gra", "show code actions"),
+        CheatEntry::new("Neovim - LSP", "grr", "list references to symbol"),
+        CheatEntry::new("Neovim - LSP", "gri", "go to implementation"),
+        CheatEntry::new("Neovim - LSP", "gO", "list document symbols"),
+        CheatEntry::new("Neovim - LSP", "Ctrl + s (insert)", "show signature help").in_mode(Mode::Insert),
+        CheatEntry::new("Neovim - LSP", "Ctrl + ]", "go to definition (via tagfunc)"),
+
+        CheatEntry::new("Neovim - diagnostics", "[d / ]d", "jump to previous / next diagnostic"),
+        CheatEntry::new("Neovim - diagnostics", "Ctrl + w, then d", "show diagnostics under cursor in a float"),
+
+        CheatEntry::new("Neovim - misc", ":Inspect", "show highlight groups / treesitter captures under cursor"),
+        CheatEntry::new("Neovim - misc", ":InspectTree", "open the treesitter syntax tree for the buffer"),
+        CheatEntry::new("Neovim - misc", "gx", "open file or URL under cursor with the system handler"),
+    ]
+}
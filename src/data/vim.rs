@@ -0,0 +1,98 @@
+//! The core Vim cheatsheet, shared by every Vim-flavoured editor profile.
+
+use crate::{CheatEntry, Difficulty::*, Mode};
+
+pub fn cheats() -> Vec<CheatEntry> {
+    vec![
+        // Basics
+        CheatEntry::new("Basics", ":q", "quit (fails if there are unsaved changes)").tags(&["ex-command"]).difficulty(Beginner).help_topic(":q"),
+        CheatEntry::new("Basics", ":q!", "quit discarding changes").tags(&["ex-command"]).difficulty(Beginner).help_topic(":q!"),
+        CheatEntry::new("Basics", ":w", "write (save) current buffer").tags(&["ex-command"]).difficulty(Beginner).help_topic(":w"),
+        CheatEntry::new("Basics", ":wq / :x / ZZ", "save and quit").tags(&["ex-command"]).difficulty(Beginner).help_topic(":wq"),
+        CheatEntry::new("Basics", ":e {file}", "edit / open file").tags(&["ex-command"]).difficulty(Intermediate).help_topic(":edit"),
+        CheatEntry::new("Basics", ":help {topic}", "open Vim help (e.g. :help motion)").tags(&["ex-command"]).difficulty(Beginner).help_topic(":help"),
+
+        CheatEntry::new("Modes", "i", "enter insert mode before cursor").difficulty(Beginner).help_topic("i"),
+        CheatEntry::new("Modes", "a", "enter insert mode after cursor").difficulty(Beginner).help_topic("a"),
+        CheatEntry::new("Modes", "v", "enter visual mode").difficulty(Beginner).help_topic("v"),
+        CheatEntry::new("Modes", "V", "enter visual line mode").difficulty(Intermediate).help_topic("V"),
+        CheatEntry::new("Modes", "Ctrl + v", "enter visual block (blockwise) mode").difficulty(Intermediate).help_topic("CTRL-V"),
+        CheatEntry::new("Modes", "Esc", "return to normal mode").difficulty(Beginner).help_topic("i_<Esc>").in_mode(Mode::Insert).notes("Ctrl + [ sends the same key and is easier to reach on most keyboards."),
+
+        CheatEntry::new("Navigation - line", "h j k l", "move cursor left / down / up / right").tags(&["motion"]).difficulty(Beginner).help_topic("hjkl"),
+        CheatEntry::new("Navigation - line", "0 / $", "move cursor to start / end of line").tags(&["motion"]).difficulty(Intermediate).help_topic("0"),
+        CheatEntry::new("Navigation - line", "^", "move cursor to first non-blank in line").tags(&["motion"]).difficulty(Intermediate).help_topic("^"),
+        CheatEntry::new("Navigation - scrolling", "Ctrl + u / Ctrl + d", "move view half-page up / down").difficulty(Intermediate).help_topic("CTRL-D"),
+        CheatEntry::new("Navigation - scrolling", "Ctrl + b / Ctrl + f", "move view page up / down").difficulty(Intermediate).help_topic("CTRL-F"),
+        CheatEntry::new("Navigation - file", "gg / G", "move cursor to first / last line of file").tags(&["motion"]).difficulty(Beginner).help_topic("gg"),
+        CheatEntry::new("Navigation - file", "{n}G", "move cursor to line {n}").tags(&["motion", "count"]).difficulty(Intermediate).help_topic("G"),
+        CheatEntry::new("Navigation - screen", "H / M / L", "move cursor to top / middle / bottom of screen").tags(&["motion"]).difficulty(Intermediate).help_topic("H"),
+        CheatEntry::new("Navigation - screen", "zz / zt / zb", "move view to center / top / bottom current line").difficulty(Intermediate).help_topic("zz"),
+        CheatEntry::new("Navigation - paragraphs", "{ / }", "move cursor to previous / next paragraph or block").tags(&["motion"]).difficulty(Intermediate).help_topic("}"),
+        CheatEntry::new("Navigation - sentences", "( / )", "move cursor to previous / next sentence").tags(&["motion"]).difficulty(Intermediate).help_topic(")"),
+        CheatEntry::new("Navigation - matching", "%", "move cursor to matching bracket/brace/paren").tags(&["motion"]).difficulty(Intermediate).help_topic("%"),
+        CheatEntry::new("Navigation - word", "w / b / e", "move cursor to next / previous / end of word").tags(&["motion"]).difficulty(Beginner).help_topic("word-motions").demo(&[("", "|one two three"), ("w", "one |two three"), ("w", "one two |three"), ("b", "one |two three"), ("e", "one tw|o three")]),
+        CheatEntry::new("Navigation - word", "W / B / E", "move cursor WORD-wise next / previous / end").tags(&["motion"]).difficulty(Intermediate).help_topic("WORD"),
+        CheatEntry::new("Navigation - find", "f{char} / F{char}", "move cursor to char right / left").tags(&["motion"]).difficulty(Intermediate).help_topic("f"),
+        CheatEntry::new("Navigation - find", "t{char} / T{char}", "move cursor till before char right / left").tags(&["motion"]).difficulty(Intermediate).help_topic("t"),
+        CheatEntry::new("Navigation - find", "; / ,", "move cursor by repeating / reversing last f/F/t/T").tags(&["motion"]).difficulty(Intermediate).help_topic(";"),
+
+        CheatEntry::new("Editing", "x", "delete character under cursor").difficulty(Beginner).example("h|ello", "x", "h|llo").related(&["r{char}", "dd"]).help_topic("x").demo(&[("", "h|ello"), ("x", "h|llo")]),
+        CheatEntry::new("Editing", "dd", "delete (cut) current line").tags(&["operator"]).difficulty(Beginner).example("one\n|two\nthree", "dd", "one\n|three").related(&["yy / Y", "p / P", "\"_dd", "D", "u / Ctrl + r"]).help_topic("dd").notes("The deleted line goes into the unnamed register, so p pastes it back below the cursor. A count deletes several lines (3dd).").demo(&[("", "one\n|two\nthree"), ("dd", "one\n|three")]),
+        CheatEntry::new("Editing", "D", "delete from cursor to end of line").tags(&["operator"]).difficulty(Intermediate).example("say |hello world", "D", "say| ").related(&["dd", "cw / c$"]).help_topic("D").notes("Same as d$. With a count, also deletes the following lines (2D deletes to the end of the next line).").demo(&[("", "say |hello world"), ("D", "say| ")]),
+        CheatEntry::new("Editing", "cc", "change (replace) entire line").tags(&["operator"]).difficulty(Intermediate).example("  |old line", "ccnew<Esc>", "  ne|w").related(&["dd", "cw / c$"]).help_topic("cc").demo(&[("", "  |old line"), ("cc", "  |"), ("n", "  n|"), ("e", "  ne|"), ("w", "  new|"), ("<Esc>", "  ne|w")]),
+        CheatEntry::new("Editing", "cw / c$", "change to end of word / line").tags(&["operator"]).difficulty(Intermediate).example("a |quick fox", "cwslow<Esc>", "a slo|w fox").related(&["cc", "D"]).help_topic("cw").demo(&[("", "a |quick fox"), ("cw", "a | fox"), ("s", "a s| fox"), ("l", "a sl| fox"), ("o", "a slo| fox"), ("w", "a slow| fox"), ("<Esc>", "a slo|w fox")]),
+        CheatEntry::new("Editing", "d{motion}", "delete text covered by a motion (e.g. dw, d})").tags(&["operator"]).difficulty(Intermediate).related(&["dd", "D", "iw / aw"]).help_topic("d"),
+        CheatEntry::new("Editing", "c{motion}", "change text covered by a motion (e.g. ciw, ct,)").tags(&["operator"]).difficulty(Intermediate).related(&["cc", "cw / c$", "iw / aw"]).help_topic("c"),
+        CheatEntry::new("Editing", "r{char}", "replace a single character").difficulty(Intermediate).example("|cat", "rb", "|bat").help_topic("r"),
+        CheatEntry::new("Editing", "J", "join current line with next").difficulty(Intermediate).example("|foo\nbar", "J", "foo| bar").help_topic("J").demo(&[("", "|foo\nbar"), ("J", "foo| bar")]),
+        CheatEntry::new("Yank (copy)", "y{motion}", "yank text covered by a motion (e.g. yw, y$)").tags(&["operator"]).difficulty(Intermediate).related(&["yy / Y", "yiw / yaw", "y0 / y$"]).help_topic("y"),
+        CheatEntry::new("Yank (copy)", "yy / Y", "yank (copy) current line").tags(&["operator"]).difficulty(Beginner).related(&["dd", "p / P", "\"+y / \"+p / \"*y"]).help_topic("yy"),
+        CheatEntry::new("Yank (copy)", "yiw / yaw", "yank inner word / a word incl. space").tags(&["text-objects"]).difficulty(Intermediate).example("a |word here", "yiw$p", "a word herewor|d").related(&["y{motion}", "p / P"]).help_topic("iw"),
+        CheatEntry::new("Yank (copy)", "y0 / y$", "yank from cursor to start / end of line").tags(&["operator"]).difficulty(Intermediate).help_topic("y"),
+        CheatEntry::new("Text objects", "iw / aw", "inner word / a word incl. space, after an operator or in visual mode").tags(&["text-objects"]).difficulty(Intermediate).related(&["yiw / yaw", "c{motion}"]).help_topic("iw"),
+        CheatEntry::new("Text objects", "ip / ap", "inner paragraph / a paragraph incl. blank lines").tags(&["text-objects"]).difficulty(Intermediate).help_topic("ip"),
+        CheatEntry::new("Text objects", "i\" / a\"", "inside / around double quotes").tags(&["text-objects"]).difficulty(Intermediate).help_topic("i\""),
+        CheatEntry::new("Text objects", "i( / a(", "inside / around parentheses (also ib / ab)").tags(&["text-objects"]).difficulty(Intermediate).help_topic("i("),
+        CheatEntry::new("Paste", "p / P", "paste after / before cursor or line").difficulty(Beginner).example("|one\ntwo", "yyp", "one\n|one\ntwo").related(&["yy / Y", "dd", "gp / gP", "\"{reg}y / \"{reg}p"]).help_topic("p").demo(&[("", "|one\ntwo"), ("yy", "|one\ntwo"), ("p", "one\n|one\ntwo"), ("P", "one\n|one\none\ntwo")]),
+        CheatEntry::new("Paste", "gp / gP", "paste and move cursor to end of paste").difficulty(Advanced).related(&["p / P"]).help_topic("gp"),
+        CheatEntry::new("Indentation", ">> / <<", "indent / dedent current line").tags(&["operator"]).difficulty(Intermediate).example("|if x:", ">>", "    |if x:").help_topic(">>"),
+        CheatEntry::new("Indentation", "=", "auto-indent motion or selection").tags(&["operator"]).difficulty(Intermediate).help_topic("="),
+
+        CheatEntry::new("Visual mode", "v / V / Ctrl + v + motion", "select characters / lines / block").difficulty(Intermediate).help_topic("visual-mode"),
+        CheatEntry::new("Visual mode", "y / d / c", "yank / delete / change selection").tags(&["operator"]).difficulty(Intermediate).help_topic("v_y").in_mode(Mode::Visual),
+        CheatEntry::new("Visual mode", "> / <", "indent / dedent selection").difficulty(Intermediate).help_topic("v_>").in_mode(Mode::Visual),
+
+        CheatEntry::new("Search", "/pattern", "search forward for pattern").difficulty(Beginner).related(&["n / N", "?pattern", ":%s/old/new/g"]).help_topic("/"),
+        CheatEntry::new("Search", "n / N", "next / previous search match").difficulty(Beginner).related(&["/pattern", "?pattern"]).help_topic("n"),
+        CheatEntry::new("Search", "?pattern", "search backward for pattern").difficulty(Intermediate).related(&["/pattern", "n / N"]).help_topic("?"),
+        CheatEntry::new("Search & replace", ":%s/old/new/g", "replace all 'old' with 'new' in file").tags(&["ex-command"]).difficulty(Intermediate).example("cat cat\ncat", ":%s/cat/dog/g<CR>", "dog dog\n|dog").related(&[":%s/old/new/gc", "/pattern"]).help_topic(":s").notes("% means every line; without it only the current line is changed. Drop the g flag to replace only the first match on each line."),
+        CheatEntry::new("Search & replace", ":%s/old/new/gc", "replace with confirmation").tags(&["ex-command"]).difficulty(Intermediate).related(&[":%s/old/new/g"]).help_topic(":s_flags"),
+
+        CheatEntry::new("Buffers", ":w / :q / :wq", "write, quit, write & quit").tags(&["ex-command"]).difficulty(Intermediate).help_topic("write-quit"),
+        CheatEntry::new("Buffers", ":ls / :buffers", "list buffers").tags(&["ex-command"]).difficulty(Intermediate).help_topic(":ls"),
+        CheatEntry::new("Buffers", ":b {n}", "go to buffer {n}").tags(&["ex-command", "count"]).difficulty(Intermediate).help_topic(":b"),
+        CheatEntry::new("Buffers", ":bn / :bp", "next / previous buffer").tags(&["ex-command"]).difficulty(Intermediate).related(&[":ls / :buffers", ":b {n}"]).help_topic(":bnext"),
+
+        CheatEntry::new("Windows", ":split / :vsplit", "horizontal / vertical split").tags(&["ex-command"]).difficulty(Intermediate).related(&["Ctrl + w, then h/j/k/l", "Ctrl + w, then c / o"]).help_topic(":split"),
+        CheatEntry::new("Windows", "Ctrl + w, then h/j/k/l", "move to window left/down/up/right").difficulty(Intermediate).help_topic("CTRL-W_h"),
+        CheatEntry::new("Windows", "Ctrl + w, then c / o", "close current / keep only current").difficulty(Intermediate).help_topic("CTRL-W_o"),
+
+        CheatEntry::new("Tabs", ":tabnew {file}", "open file in a new tab").tags(&["ex-command"]).difficulty(Intermediate).help_topic(":tabnew"),
+        CheatEntry::new("Tabs", "gt / gT", "next / previous tab").difficulty(Intermediate).related(&[":tabnew {file}", ":tabclose"]).help_topic("gt"),
+        CheatEntry::new("Tabs", ":tabclose", "close current tab").tags(&["ex-command"]).difficulty(Intermediate).help_topic(":tabclose"),
+
+        CheatEntry::new("Registers", "\"{reg}y / \"{reg}p", "yank / paste using register {reg}").difficulty(Advanced).help_topic("quote"),
+        CheatEntry::new("Registers", "\"+y / \"+p / \"*y", "use system clipboards (+ or * register)").tags(&["clipboard"]).difficulty(Advanced).help_topic("quoteplus"),
+        CheatEntry::new("Registers", "\"_dd", "delete line into the black-hole register (keeps the last yank)").difficulty(Advanced).related(&["dd", "\"{reg}y / \"{reg}p"]).help_topic("quote_"),
+
+        CheatEntry::new("Marks", "m{a-z}", "set mark {a-z} on a line").difficulty(Intermediate).help_topic("m"),
+        CheatEntry::new("Marks", "'{a-z} / `{a-z}", "jump to mark line / exact position").difficulty(Intermediate).help_topic("'"),
+
+        CheatEntry::new("Macros", "q{reg} ... q", "record macro into register {reg}").difficulty(Advanced).related(&["@{reg} / @@"]).help_topic("q").notes("Macros are stored in ordinary registers, so :reg shows them and \"{reg}p pastes one for editing."),
+        CheatEntry::new("Macros", "@{reg} / @@", "play macro / repeat last macro").difficulty(Advanced).related(&["q{reg} ... q", "."]).help_topic("@"),
+
+        CheatEntry::new("Repeat", ".", "repeat last change").difficulty(Intermediate).example("|a b c", "x.", "|b c").related(&["u / Ctrl + r", "@{reg} / @@"]).help_topic(".").notes("Repeats the last change, not motions or searches; combine it with n to apply a change at each search match.").demo(&[("", "|a b c"), ("x", "| b c"), (".", "|b c")]),
+        CheatEntry::new("Undo/Redo", "u / Ctrl + r", "undo / redo last change").difficulty(Beginner).example("|hello", "xu", "|hello").related(&["."]).help_topic("undo").demo(&[("", "|hello"), ("x", "|ello"), ("u", "|hello"), ("Ctrl + r", "|ello")]),
+    ]
+}
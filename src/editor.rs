@@ -0,0 +1,101 @@
+//! Editor profiles: which dataset the cheatsheet is built from.
+
+use std::{env, path::Path};
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::{CheatEntry, data};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Editor {
+    #[default]
+    Vim,
+    /// Vim plus Neovim-specific commands and defaults
+    Nvim,
+    Helix,
+    Kakoune,
+    /// Evil-mode for Emacs: Vim emulation plus notes on where it differs
+    Evil,
+}
+
+impl Editor {
+    /// Short name shown in the status bar.
+    pub fn label(self) -> &'static str {
+        match self {
+            Editor::Vim => "vim",
+            Editor::Nvim => "nvim",
+            Editor::Helix => "helix",
+            Editor::Kakoune => "kakoune",
+            Editor::Evil => "evil",
+        }
+    }
+
+    /// Title of the cheatsheet list.
+    pub fn title(self) -> &'static str {
+        match self {
+            Editor::Vim => " Vim Cheatsheet ",
+            Editor::Nvim => " Neovim Cheatsheet ",
+            Editor::Helix => " Helix Cheatsheet ",
+            Editor::Kakoune => " Kakoune Cheatsheet ",
+            Editor::Evil => " Evil (Emacs) Cheatsheet ",
+        }
+    }
+
+    /// Program used to open `:help` pages: Neovim for the nvim profile,
+    /// Vim for everything else.
+    pub fn help_program(self) -> &'static str {
+        match self {
+            Editor::Nvim => "nvim",
+            _ => "vim",
+        }
+    }
+
+    /// Command line (program and arguments) that opens `:help` pages:
+    /// `$VISUAL` or `$EDITOR` when it is Vim or Neovim, so their flags and
+    /// config are kept, else the profile's [`Editor::help_program`].
+    pub fn help_command(self) -> Vec<String> {
+        for var in ["VISUAL", "EDITOR"] {
+            let Ok(value) = env::var(var) else {
+                continue;
+            };
+            let words: Vec<String> = value.split_whitespace().map(str::to_string).collect();
+            let is_vim = words.first().is_some_and(|program| {
+                Path::new(program)
+                    .file_name()
+                    .is_some_and(|name| name == "vim" || name == "nvim")
+            });
+            if is_vim {
+                return words;
+            }
+        }
+        vec![self.help_program().to_string()]
+    }
+
+    /// The profile after this one, wrapping around (for the TUI switcher).
+    pub fn next(self) -> Self {
+        let all = Self::value_variants();
+        let pos = all.iter().position(|e| *e == self).unwrap_or(0);
+        all[(pos + 1) % all.len()]
+    }
+
+    /// Built-in entries for this profile.
+    pub fn cheats(self) -> Vec<CheatEntry> {
+        match self {
+            Editor::Vim => data::vim::cheats(),
+            Editor::Nvim => {
+                let mut cheats = data::vim::cheats();
+                cheats.extend(data::nvim::cheats());
+                cheats
+            }
+            Editor::Helix => data::helix::cheats(),
+            Editor::Kakoune => data::kakoune::cheats(),
+            Editor::Evil => {
+                let mut cheats = data::vim::cheats();
+                cheats.extend(data::evil::cheats());
+                cheats
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Entries exported for use outside vimbo: marked entries as Markdown
+//! tables, one per category, in the layout `--markdown` imports, and the
+//! filtered database with `vimbo export` as a Markdown document, a
+//! standalone HTML page, a man page, a PDF poster or an SVG or PNG poster
+//! in the colors of a theme.
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+
+use crate::{paths, CheatEntry};
+
+pub mod html;
+pub mod man;
+pub mod pdf;
+pub mod svg;
+
+/// Formats `vimbo export` writes.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Format {
+    /// A styled, searchable single-file page, also fit for printing
+    Html,
+    /// A section per category with a table of commands, for wikis and READMEs
+    #[value(alias = "markdown")]
+    Md,
+    /// A roff man page, to install as `vimcheats(7)`
+    Man,
+    /// A printable poster with the categories laid out in columns
+    Pdf,
+    /// A poster with a box per category in the theme's colors, e.g. for a wallpaper
+    Svg,
+    /// The SVG poster as an image
+    Png,
+}
+
+/// Write `entries` in `format` to `output`, or to stdout without one.
+/// `title` heads the document; `layout` arranges PDF pages and `poster`
+/// the SVG and PNG ones.
+pub fn run(
+    format: Format,
+    entries: &[&CheatEntry],
+    title: &str,
+    lang: Option<&str>,
+    layout: pdf::Layout,
+    poster: svg::Layout,
+    output: Option<&Path>,
+) -> Result<()> {
+    let bytes = match format {
+        Format::Html => html::page(entries, title, lang).into_bytes(),
+        Format::Md => format!("# {title}\n\n{}", markdown(entries, lang)).into_bytes(),
+        Format::Man => man::page(entries, title, lang).into_bytes(),
+        Format::Pdf => pdf::poster(entries, title, lang, layout),
+        Format::Svg => svg::poster(entries, title, lang, poster).into_bytes(),
+        Format::Png => svg::png(&svg::poster(entries, title, lang, poster))?,
+    };
+    match output {
+        Some(path) => {
+            fs::write(path, bytes).with_context(|| format!("failed to write {}", path.display()))
+        }
+        None => match io::stdout().lock().write_all(&bytes) {
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+            result => result.context("failed to write to stdout"),
+        },
+    }
+}
+
+/// Write `entries` to `export.md` in the data directory, replacing an
+/// earlier export. Returns the file's path.
+pub fn export(entries: &[&CheatEntry], lang: Option<&str>) -> Result<PathBuf> {
+    let dir = paths::data_dir()?;
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let path = dir.join("export.md");
+    fs::write(&path, markdown(entries, lang))
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// `entries` as a heading per category, in the order they first appear,
+/// each followed by a table of its commands.
+pub fn markdown(entries: &[&CheatEntry], lang: Option<&str>) -> String {
+    let mut out = String::new();
+    for category in categories(entries) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "## {category}\n\n| Command | Description |\n|---|---|\n"
+        ));
+        for c in entries.iter().filter(|c| c.category == category) {
+            out.push_str(&format!(
+                "| {} | {} |\n",
+                cell(&code(&c.command)),
+                cell(c.description_in(lang))
+            ));
+        }
+    }
+    out
+}
+
+/// Categories of `entries`, in the order they first appear.
+fn categories<'a>(entries: &[&'a CheatEntry]) -> Vec<&'a str> {
+    let mut categories: Vec<&str> = Vec::new();
+    for c in entries {
+        if !categories.contains(&c.category.as_str()) {
+            categories.push(&c.category);
+        }
+    }
+    categories
+}
+
+/// `text` as an inline code span. Commands with backticks (`` `{a-z} ``)
+/// are fenced by a longer run of them, padded with spaces, as CommonMark
+/// allows.
+fn code(text: &str) -> String {
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    if longest == 0 {
+        return format!("`{text}`");
+    }
+    let fence = "`".repeat(longest + 1);
+    format!("{fence} {text} {fence}")
+}
+
+/// `text` with the pipes that would end its table cell escaped.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import;
+
+    #[test]
+    fn markdown_round_trips_through_the_importer() {
+        let entries = [
+            CheatEntry::new("Marks", "'{a-z} / `{a-z}", "jump to mark line / exact position"),
+            CheatEntry::new("Marks", "``", "jump back to where you jumped from"),
+            CheatEntry::new("Marks", "x`", "ends in a backtick"),
+            CheatEntry::new("Search", ":s/a\\|b/c/", "replace a or b, with \\| alternation"),
+            CheatEntry::new("Search", "|", "go to column | count"),
+            CheatEntry::new("Search", "a | b", "pipes | in both cells"),
+        ];
+        let refs: Vec<&CheatEntry> = entries.iter().collect();
+        let imported = import::markdown::parse(&markdown(&refs, None), "Imported");
+        let triples = |entries: &[CheatEntry]| -> Vec<(String, String, String)> {
+            entries
+                .iter()
+                .map(|e| (e.category.clone(), e.command.clone(), e.description.clone()))
+                .collect()
+        };
+        assert_eq!(triples(&imported), triples(&entries));
+    }
+}
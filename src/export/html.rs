@@ -0,0 +1,92 @@
+//! A standalone HTML cheatsheet: one page with its styles and a search
+//! box inline, a table per category, laid out in columns on screen and
+//! in print.
+
+use crate::{cheat::Difficulty, CheatEntry};
+
+const STYLE: &str = "
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }
+h1 { font-size: 1.6rem; margin: 0 0 1rem; }
+#search { width: 100%; max-width: 30rem; padding: .4rem .6rem; font-size: 1rem; margin-bottom: 1.5rem; }
+main { columns: 26rem; column-gap: 2rem; }
+section { break-inside: avoid; margin-bottom: 1.5rem; }
+h2 { font-size: 1.1rem; border-bottom: 2px solid #4a7ab8; margin: 0 0 .4rem; }
+table { border-collapse: collapse; width: 100%; }
+td { padding: .2rem .4rem; vertical-align: top; border-bottom: 1px solid #eee; }
+td:first-child { white-space: nowrap; width: 1%; }
+code { font-family: ui-monospace, monospace; background: #f2f2f2; padding: 0 .25rem; border-radius: 3px; }
+.tag { color: #888; font-size: .8rem; margin-left: .4rem; }
+.hidden { display: none; }
+@media print { #search { display: none; } body { margin: 0; font-size: 9pt; } }
+";
+
+/// Hides the rows (and then empty sections) not containing every word
+/// typed into the search box.
+const SCRIPT: &str = "
+const search = document.getElementById('search');
+search.addEventListener('input', () => {
+  const words = search.value.toLowerCase().split(/\\s+/).filter(Boolean);
+  for (const section of document.querySelectorAll('section')) {
+    let shown = 0;
+    for (const row of section.querySelectorAll('tr')) {
+      const text = (section.dataset.category + ' ' + row.textContent).toLowerCase();
+      const match = words.every(word => text.includes(word));
+      row.classList.toggle('hidden', !match);
+      shown += match;
+    }
+    section.classList.toggle('hidden', shown === 0);
+  }
+});
+";
+
+/// `entries` as a page titled `title`, with a section per category in the
+/// order they first appear.
+pub fn page(entries: &[&CheatEntry], title: &str, lang: Option<&str>) -> String {
+    let title = escape(title);
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n<input id=\"search\" type=\"search\" placeholder=\"Search\" autofocus>\n\
+         <main>\n"
+    );
+    for category in super::categories(entries) {
+        let name = escape(category);
+        out.push_str(&format!(
+            "<section data-category=\"{name}\">\n<h2>{name}</h2>\n<table>\n"
+        ));
+        for c in entries.iter().filter(|c| c.category == category) {
+            let tags: String = c
+                .difficulty
+                .map(Difficulty::label)
+                .into_iter()
+                .chain(c.tags.iter().map(String::as_str))
+                .map(|tag| format!("<span class=\"tag\">#{}</span>", escape(tag)))
+                .collect();
+            out.push_str(&format!(
+                "<tr><td><code>{}</code></td><td>{}{tags}</td></tr>\n",
+                escape(&c.command),
+                escape(c.description_in(lang))
+            ));
+        }
+        out.push_str("</table>\n</section>\n");
+    }
+    out.push_str(&format!(
+        "</main>\n<script>{SCRIPT}</script>\n</body>\n</html>\n"
+    ));
+    out
+}
+
+/// `text` with the characters HTML gives a meaning escaped.
+pub(super) fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,67 @@
+//! A man page in roff, to install as `vimcheats(7)` where no TUI can run:
+//! a section per category with the commands as tagged paragraphs.
+
+use crate::CheatEntry;
+
+/// The name the page is installed and opened under.
+const NAME: &str = "vimcheats";
+
+/// `entries` as a man page titled `title`, with a section per category in
+/// the order they first appear.
+pub fn page(entries: &[&CheatEntry], title: &str, lang: Option<&str>) -> String {
+    let mut out = format!(
+        ".TH {} 7 \"\" \"vimbo {}\" \"{}\"\n.SH NAME\n{NAME} \\- {}\n",
+        NAME.to_uppercase(),
+        env!("CARGO_PKG_VERSION"),
+        quoted(title),
+        escape(title)
+    );
+    for category in super::categories(entries) {
+        out.push_str(&format!(".SH \"{}\"\n", quoted(&category.to_uppercase())));
+        for c in entries.iter().filter(|c| c.category == category) {
+            out.push_str(&format!(
+                ".TP\n\\fB{}\\fR\n{}\n",
+                escape(&c.command),
+                line(c.description_in(lang))
+            ));
+            if let Some(notes) = &c.notes {
+                out.push_str(&format!(".IP\n{}\n", line(notes)));
+            }
+            if let Some(topic) = &c.help_topic {
+                out.push_str(&format!(".IP\nSee \\fI:help {}\\fR.\n", escape(topic)));
+            }
+        }
+    }
+    out
+}
+
+/// `text` as a text line: escaped, joined into one line, and kept from
+/// being read as a request when it starts with `.` or `'`.
+fn line(text: &str) -> String {
+    let text = escape(&text.split_whitespace().collect::<Vec<_>>().join(" "));
+    if text.starts_with(['.', '\'']) {
+        format!("\\&{text}")
+    } else {
+        text
+    }
+}
+
+/// `text` with the characters roff gives a meaning escaped; dashes are
+/// kept as typed so commands can be copied from the page.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\e"),
+            '-' => out.push_str("\\-"),
+            '\n' => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// `text` escaped to go inside a quoted macro argument.
+fn quoted(text: &str) -> String {
+    escape(text).replace('"', "\"\"")
+}
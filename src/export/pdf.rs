@@ -0,0 +1,348 @@
+//! A printable cheat poster as PDF: the entries laid out in columns, a
+//! colored heading per category, like the classic one-page cheat sheets.
+//! Uses the standard Helvetica and Courier fonts every PDF reader ships,
+//! so nothing is embedded; characters they cannot show print as `?`.
+
+use clap::ValueEnum;
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+
+use crate::CheatEntry;
+
+/// Paper sizes, in points (1/72 inch), portrait.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum PageSize {
+    #[default]
+    A4,
+    A3,
+    Letter,
+    Legal,
+}
+
+impl PageSize {
+    fn points(self) -> (f32, f32) {
+        match self {
+            PageSize::A4 => (595.0, 842.0),
+            PageSize::A3 => (842.0, 1191.0),
+            PageSize::Letter => (612.0, 792.0),
+            PageSize::Legal => (612.0, 1008.0),
+        }
+    }
+}
+
+/// How the poster is laid out on its pages.
+#[derive(Clone, Copy, Debug)]
+pub struct Layout {
+    pub page_size: PageSize,
+    pub landscape: bool,
+    pub columns: usize,
+}
+
+const MARGIN: f32 = 36.0;
+/// Space between columns.
+const GUTTER: f32 = 14.0;
+const TITLE_SIZE: f32 = 16.0;
+const HEADING_SIZE: f32 = 9.0;
+/// Height of a category's heading bar, with the space below it.
+const HEADING_HEIGHT: f32 = 14.0;
+const TEXT_SIZE: f32 = 7.0;
+/// Distance between the baselines of an entry's lines.
+const LEADING: f32 = 8.5;
+/// Space below each entry.
+const ENTRY_GAP: f32 = 2.0;
+/// Share of a column taken by the commands.
+const COMMAND_SHARE: f32 = 0.38;
+/// Heading bars, in RGB.
+const ACCENT: [f32; 3] = [0.29, 0.48, 0.72];
+
+const HELVETICA: Name = Name(b"F1");
+const HELVETICA_BOLD: Name = Name(b"F2");
+const COURIER_BOLD: Name = Name(b"F3");
+
+/// `entries` as a PDF poster titled `title`, a section per category in
+/// the order they first appear.
+pub fn poster(entries: &[&CheatEntry], title: &str, lang: Option<&str>, layout: Layout) -> Vec<u8> {
+    let mut poster = Poster::new(layout);
+    poster.title(title);
+    for category in super::categories(entries) {
+        poster.heading(category, false);
+        for c in entries.iter().filter(|c| c.category == category) {
+            poster.entry(category, &c.command, c.description_in(lang));
+        }
+    }
+    document(poster.into_pages(), poster_size(layout), title)
+}
+
+/// Width and height of the pages.
+fn poster_size(layout: Layout) -> (f32, f32) {
+    let (width, height) = layout.page_size.points();
+    if layout.landscape {
+        (height, width)
+    } else {
+        (width, height)
+    }
+}
+
+/// Pages being filled column by column, top to bottom.
+struct Poster {
+    height: f32,
+    columns: usize,
+    column_width: f32,
+    /// Finished pages.
+    pages: Vec<Content>,
+    /// The page being filled.
+    content: Content,
+    column: usize,
+    /// Top of the room left in the column.
+    y: f32,
+}
+
+impl Poster {
+    fn new(layout: Layout) -> Self {
+        let (width, height) = poster_size(layout);
+        let columns = layout.columns.max(1);
+        let room = width - 2.0 * MARGIN - GUTTER * (columns - 1) as f32;
+        Self {
+            height,
+            columns,
+            column_width: room / columns as f32,
+            pages: Vec::new(),
+            content: Content::new(),
+            column: 0,
+            y: height - MARGIN,
+        }
+    }
+
+    /// The document title, across the top of the first page.
+    fn title(&mut self, title: &str) {
+        let baseline = self.height - MARGIN - TITLE_SIZE;
+        self.content.set_fill_rgb(0.0, 0.0, 0.0);
+        self.text(HELVETICA_BOLD, TITLE_SIZE, MARGIN, baseline, title);
+        self.y = baseline - TITLE_SIZE;
+    }
+
+    /// Left edge of the current column.
+    fn x(&self) -> f32 {
+        MARGIN + self.column as f32 * (self.column_width + GUTTER)
+    }
+
+    /// Move on to the next column, or the next page after the last one,
+    /// unless `needed` points fit in this one.
+    fn make_room(&mut self, needed: f32) -> bool {
+        if self.y - needed >= MARGIN {
+            return false;
+        }
+        self.column += 1;
+        if self.column == self.columns {
+            self.pages
+                .push(std::mem::replace(&mut self.content, Content::new()));
+            self.column = 0;
+        }
+        self.y = self.height - MARGIN;
+        true
+    }
+
+    /// The heading bar of `category`, kept together with its first entry;
+    /// `continued` when the category goes on in a new column.
+    fn heading(&mut self, category: &str, continued: bool) {
+        if !continued {
+            self.y -= ENTRY_GAP * 2.0;
+            self.make_room(HEADING_HEIGHT + LEADING + ENTRY_GAP);
+        }
+        let (x, top) = (self.x(), self.y);
+        let [r, g, b] = ACCENT;
+        self.content
+            .set_fill_rgb(r, g, b)
+            .rect(
+                x,
+                top - HEADING_HEIGHT + 2.0,
+                self.column_width,
+                HEADING_HEIGHT - 2.0,
+            )
+            .fill_nonzero();
+        self.content.set_fill_rgb(1.0, 1.0, 1.0);
+        let label = if continued {
+            format!("{category} (cont.)")
+        } else {
+            category.to_string()
+        };
+        let baseline = top - HEADING_HEIGHT + 5.0;
+        self.text(HELVETICA_BOLD, HEADING_SIZE, x + 3.0, baseline, &label);
+        self.y -= HEADING_HEIGHT + 1.0;
+    }
+
+    /// An entry of `category`: the command on the left, wrapped in its
+    /// share of the column, and the description wrapped beside it.
+    fn entry(&mut self, category: &str, command: &str, description: &str) {
+        let command_width = self.column_width * COMMAND_SHARE;
+        let description_width = self.column_width - command_width - 4.0;
+        let commands = wrap(command, command_width - 2.0, courier_width);
+        let descriptions = wrap(description, description_width, helvetica_width);
+        let lines = commands.len().max(descriptions.len());
+        let height = lines as f32 * LEADING + ENTRY_GAP;
+        if self.make_room(height) {
+            self.heading(category, true);
+        }
+        let (x, top) = (self.x(), self.y);
+        self.content.set_fill_rgb(0.0, 0.0, 0.0);
+        for (i, line) in commands.iter().enumerate() {
+            let baseline = top - TEXT_SIZE - i as f32 * LEADING;
+            self.text(COURIER_BOLD, TEXT_SIZE, x + 2.0, baseline, line);
+        }
+        for (i, line) in descriptions.iter().enumerate() {
+            let baseline = top - TEXT_SIZE - i as f32 * LEADING;
+            let left = x + command_width + 4.0;
+            self.text(HELVETICA, TEXT_SIZE, left, baseline, line);
+        }
+        self.y -= height;
+        // A hairline between entries.
+        self.content
+            .set_stroke_gray(0.85)
+            .set_line_width(0.3)
+            .move_to(x, self.y + ENTRY_GAP / 2.0)
+            .line_to(x + self.column_width, self.y + ENTRY_GAP / 2.0)
+            .stroke();
+    }
+
+    /// Write `line` with its baseline starting at `x`, `y`.
+    fn text(&mut self, font: Name, size: f32, x: f32, y: f32, line: &str) {
+        self.content
+            .begin_text()
+            .set_font(font, size)
+            .next_line(x, y)
+            .show(Str(&encode(line)))
+            .end_text();
+    }
+
+    /// All pages, the one being filled last.
+    fn into_pages(mut self) -> Vec<Content> {
+        self.pages.push(self.content);
+        self.pages
+    }
+}
+
+/// The PDF file with `pages` of `size`.
+fn document(pages: Vec<Content>, (width, height): (f32, f32), title: &str) -> Vec<u8> {
+    let catalog = Ref::new(1);
+    let tree = Ref::new(2);
+    let info = Ref::new(3);
+    let fonts = [
+        (HELVETICA, Ref::new(4), Name(b"Helvetica")),
+        (HELVETICA_BOLD, Ref::new(5), Name(b"Helvetica-Bold")),
+        (COURIER_BOLD, Ref::new(6), Name(b"Courier-Bold")),
+    ];
+    // Each page is followed by its content stream.
+    let page_ids: Vec<Ref> = (0..pages.len())
+        .map(|i| Ref::new(7 + 2 * i as i32))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog).pages(tree);
+    pdf.pages(tree)
+        .kids(page_ids.iter().copied())
+        .count(pages.len() as i32);
+    pdf.document_info(info)
+        .title(TextStr(title))
+        .creator(TextStr("vimbo"));
+    for (_, id, base) in fonts {
+        pdf.type1_font(id)
+            .base_font(base)
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+    }
+    for (content, id) in pages.into_iter().zip(page_ids) {
+        let content_id = Ref::new(id.get() + 1);
+        let mut page = pdf.page(id);
+        page.media_box(Rect::new(0.0, 0.0, width, height))
+            .parent(tree)
+            .contents(content_id);
+        let mut resources = page.resources();
+        let mut font_names = resources.fonts();
+        for (name, font, _) in fonts {
+            font_names.pair(name, font);
+        }
+        font_names.finish();
+        resources.finish();
+        page.finish();
+        pdf.stream(content_id, &content.finish());
+    }
+    pdf.finish()
+}
+
+/// Split `text` at spaces into lines at most `max` points wide by
+/// `measure`; longer words are broken where they reach the edge.
+pub(super) fn wrap(text: &str, max: f32, measure: fn(&str) -> f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let joined = if line.is_empty() {
+            word.to_string()
+        } else {
+            format!("{line} {word}")
+        };
+        if measure(&joined) <= max {
+            line = joined;
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        for c in word.chars() {
+            if !line.is_empty() && measure(&format!("{line}{c}")) > max {
+                lines.push(std::mem::take(&mut line));
+            }
+            line.push(c);
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Width of `text` in Courier at the entry size: every glyph is 600 units.
+fn courier_width(text: &str) -> f32 {
+    encode(text).len() as f32 * 0.6 * TEXT_SIZE
+}
+
+/// Width of `text` in Helvetica at the entry size, from the font's metrics
+/// for ASCII.
+fn helvetica_width(text: &str) -> f32 {
+    #[rustfmt::skip]
+    const WIDTHS: [u16; 95] = [
+        278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+        556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+        1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+        667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+        333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+        556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+    ];
+    let units: u32 = encode(text)
+        .iter()
+        .map(|&b| match b {
+            b' '..=b'~' => u32::from(WIDTHS[usize::from(b - b' ')]),
+            _ => 556,
+        })
+        .sum();
+    units as f32 / 1000.0 * TEXT_SIZE
+}
+
+/// `text` in the fonts' WinAnsi encoding, which matches Latin-1 for most
+/// characters.
+fn encode(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            ' '..='~' | '\u{a0}'..='\u{ff}' => bytes.push(c as u8),
+            '‘' => bytes.push(0x91),
+            '’' => bytes.push(0x92),
+            '“' => bytes.push(0x93),
+            '”' => bytes.push(0x94),
+            '•' => bytes.push(0x95),
+            '–' => bytes.push(0x96),
+            '—' => bytes.push(0x97),
+            '…' => bytes.push(0x85),
+            '→' => bytes.extend_from_slice(b"->"),
+            _ => bytes.push(b'?'),
+        }
+    }
+    bytes
+}
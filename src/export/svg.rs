@@ -0,0 +1,289 @@
+//! A cheat poster as SVG, e.g. for a desktop wallpaper: the title across
+//! the top and a box per category, packed into columns, in the colors of a
+//! TUI theme. The PNG is the same poster drawn with the system's fonts.
+
+use anyhow::{bail, Context, Result};
+use ratatui::style::{Color, Modifier, Style};
+use resvg::{tiny_skia, usvg};
+
+use crate::{
+    theme::{Preset, Theme},
+    CheatEntry,
+};
+
+/// How the poster is laid out and colored.
+#[derive(Clone, Copy, Debug)]
+pub struct Layout<'a> {
+    pub theme: &'a Theme,
+    /// Width in pixels; the height fits the entries, but is at least 16:9.
+    pub width: u32,
+    pub columns: usize,
+}
+
+const MARGIN: f32 = 48.0;
+/// Space between columns, and between boxes in a column.
+const GUTTER: f32 = 24.0;
+const TITLE_SIZE: f32 = 36.0;
+const HEADING_SIZE: f32 = 20.0;
+/// Height of a category's heading, with the rule below it.
+const HEADING_HEIGHT: f32 = 34.0;
+const TEXT_SIZE: f32 = 15.0;
+/// Distance between the baselines of an entry's lines.
+const LEADING: f32 = 20.0;
+/// Space below each entry.
+const ENTRY_GAP: f32 = 4.0;
+/// Space inside a box's border.
+const PADDING: f32 = 14.0;
+/// Share of a box taken by the commands.
+const COMMAND_SHARE: f32 = 0.36;
+
+const SANS: &str = "DejaVu Sans, Helvetica, Arial, sans-serif";
+const MONO: &str = "DejaVu Sans Mono, Menlo, Consolas, monospace";
+
+/// `entries` as an SVG poster titled `title`, a box per category in the
+/// order they first appear, each put in the shortest column so far.
+pub fn poster(entries: &[&CheatEntry], title: &str, lang: Option<&str>, layout: Layout) -> String {
+    let theme = layout.theme;
+    let (background, text) = colors(theme.preset);
+    let width = layout.width as f32;
+    let columns = layout.columns.max(1);
+    let column_width = (width - 2.0 * MARGIN - GUTTER * (columns - 1) as f32) / columns as f32;
+    let top = MARGIN + TITLE_SIZE * 2.0;
+    let mut heights = vec![top; columns];
+
+    let mut body = String::new();
+    for category in super::categories(entries) {
+        let lines: Vec<_> = entries
+            .iter()
+            .filter(|c| c.category == category)
+            .map(|c| entry_lines(&c.command, c.description_in(lang), column_width))
+            .collect();
+        let height = 2.0 * PADDING
+            + HEADING_HEIGHT
+            + lines
+                .iter()
+                .map(|(commands, descriptions)| {
+                    commands.len().max(descriptions.len()) as f32 * LEADING + ENTRY_GAP
+                })
+                .sum::<f32>();
+        let column = (0..columns)
+            .min_by(|&a, &b| heights[a].total_cmp(&heights[b]))
+            .unwrap_or(0);
+        let x = MARGIN + column as f32 * (column_width + GUTTER);
+        let mut y = heights[column];
+        heights[column] += height + GUTTER;
+
+        body.push_str(&format!(
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{column_width}\" height=\"{height}\" rx=\"8\" \
+             fill=\"{text}\" fill-opacity=\"0.05\" stroke=\"{}\" stroke-width=\"1.5\"/>\n",
+            color(theme.pane_title, text)
+        ));
+        let left = x + PADDING;
+        let baseline = y + PADDING + HEADING_SIZE;
+        body.push_str(&text_element(
+            left,
+            baseline,
+            SANS,
+            HEADING_SIZE,
+            theme.group,
+            text,
+            category,
+        ));
+        y += PADDING + HEADING_HEIGHT;
+        body.push_str(&format!(
+            "<line x1=\"{left}\" y1=\"{0}\" x2=\"{1}\" y2=\"{0}\" stroke=\"{2}\" stroke-opacity=\"0.5\"/>\n",
+            y - 6.0,
+            x + column_width - PADDING,
+            color(theme.muted, text)
+        ));
+        let command_width = (column_width - 2.0 * PADDING) * COMMAND_SHARE;
+        for (commands, descriptions) in lines {
+            for (i, line) in commands.iter().enumerate() {
+                let baseline = y + TEXT_SIZE + i as f32 * LEADING;
+                body.push_str(&text_element(
+                    left,
+                    baseline,
+                    MONO,
+                    TEXT_SIZE,
+                    theme.command,
+                    text,
+                    line,
+                ));
+            }
+            for (i, line) in descriptions.iter().enumerate() {
+                let baseline = y + TEXT_SIZE + i as f32 * LEADING;
+                body.push_str(&text_element(
+                    left + command_width + 8.0,
+                    baseline,
+                    SANS,
+                    TEXT_SIZE,
+                    theme.description,
+                    text,
+                    line,
+                ));
+            }
+            y += commands.len().max(descriptions.len()) as f32 * LEADING + ENTRY_GAP;
+        }
+    }
+
+    let content = heights.into_iter().fold(0.0, f32::max) - GUTTER + MARGIN;
+    let height = content.max(width * 9.0 / 16.0).ceil();
+    let title = text_element(
+        MARGIN,
+        MARGIN + TITLE_SIZE,
+        SANS,
+        TITLE_SIZE,
+        theme.title,
+        text,
+        title,
+    );
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"{background}\"/>\n{title}{body}</svg>\n"
+    )
+}
+
+/// The SVG `poster` rasterized, drawing its text with the system's fonts.
+pub fn png(poster: &str) -> Result<Vec<u8>> {
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+    if options.fontdb.is_empty() {
+        bail!("no fonts found to draw the poster's text with");
+    }
+    let tree = usvg::Tree::from_str(poster, &options).context("failed to read the SVG poster")?;
+    let size = tree.size().to_int_size();
+    let Some(mut pixmap) = tiny_skia::Pixmap::new(size.width(), size.height()) else {
+        bail!("the poster is too large to draw");
+    };
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    pixmap.encode_png().context("failed to encode the PNG")
+}
+
+/// The lines of an entry in a box `width` wide: the command wrapped in its
+/// share on the left, the description wrapped beside it.
+fn entry_lines(command: &str, description: &str, width: f32) -> (Vec<String>, Vec<String>) {
+    let room = width - 2.0 * PADDING;
+    let command_width = room * COMMAND_SHARE;
+    (
+        super::pdf::wrap(command, command_width, mono_width),
+        super::pdf::wrap(description, room - command_width - 8.0, sans_width),
+    )
+}
+
+/// Width of `text` in the monospace font, whose glyphs are about 0.6 em.
+fn mono_width(text: &str) -> f32 {
+    text.chars().count() as f32 * 0.6 * TEXT_SIZE
+}
+
+/// Rough width of `text` in the sans-serif font, at an average of 0.55 em
+/// per character; the fonts' metrics are not known until it is drawn.
+fn sans_width(text: &str) -> f32 {
+    text.chars().count() as f32 * 0.55 * TEXT_SIZE
+}
+
+/// A `<text>` element with its baseline starting at `x`, `y`, in the color
+/// and weight of `style`; `fallback` colors it where the style has none.
+fn text_element(
+    x: f32,
+    y: f32,
+    family: &str,
+    size: f32,
+    style: Style,
+    fallback: &str,
+    line: &str,
+) -> String {
+    let mut attributes = String::new();
+    if style.add_modifier.contains(Modifier::BOLD) {
+        attributes.push_str(" font-weight=\"bold\"");
+    }
+    if style.add_modifier.contains(Modifier::ITALIC) {
+        attributes.push_str(" font-style=\"italic\"");
+    }
+    if style.add_modifier.contains(Modifier::UNDERLINED) {
+        attributes.push_str(" text-decoration=\"underline\"");
+    }
+    format!(
+        "<text x=\"{x}\" y=\"{y}\" font-family=\"{family}\" font-size=\"{size}\" fill=\"{}\"{attributes} \
+         xml:space=\"preserve\">{}</text>\n",
+        color(style, fallback),
+        super::html::escape(line)
+    )
+}
+
+/// Background of the poster and the text color where the theme leaves it
+/// to the terminal: dark, except for the light and monochrome presets.
+fn colors(preset: Preset) -> (&'static str, &'static str) {
+    match preset {
+        Preset::Light | Preset::Monochrome => ("#ffffff", "#222222"),
+        Preset::Solarized => ("#002b36", "#839496"),
+        Preset::Gruvbox => ("#282828", "#ebdbb2"),
+        Preset::Default | Preset::Colorblind => ("#1c1c1c", "#d7d7d7"),
+    }
+}
+
+/// The foreground of `style` as `#rrggbb`, or `fallback` without one. The
+/// terminal's named colors take xterm's default values.
+fn color(style: Style, fallback: &str) -> String {
+    let (r, g, b) = match style.fg {
+        None | Some(Color::Reset) => return fallback.to_string(),
+        Some(Color::Rgb(r, g, b)) => (r, g, b),
+        Some(Color::Indexed(i)) => indexed(i),
+        Some(named) => {
+            let i = match named {
+                Color::Black => 0,
+                Color::Red => 1,
+                Color::Green => 2,
+                Color::Yellow => 3,
+                Color::Blue => 4,
+                Color::Magenta => 5,
+                Color::Cyan => 6,
+                Color::Gray => 7,
+                Color::DarkGray => 8,
+                Color::LightRed => 9,
+                Color::LightGreen => 10,
+                Color::LightYellow => 11,
+                Color::LightBlue => 12,
+                Color::LightMagenta => 13,
+                Color::LightCyan => 14,
+                _ => 15,
+            };
+            indexed(i)
+        }
+    };
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Color `i` of xterm's 256-color palette.
+fn indexed(i: u8) -> (u8, u8, u8) {
+    const ANSI: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    match i {
+        0..=15 => ANSI[usize::from(i)],
+        16..=231 => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let n = i - 16;
+            (level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        _ => {
+            let gray = 8 + (i - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
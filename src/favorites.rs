@@ -0,0 +1,228 @@
+//! Entries starred from the TUI, saved to `favorites` in the data directory
+//! under a header line, as one `id<TAB>category<TAB>command<TAB>description`
+//! line per entry, keyed by [`CheatEntry::stable_id`]. Files from before
+//! the ids have no header and `category<TAB>command` first; their stars
+//! move to the ids of their entries once those are loaded. Where the entry
+//! was and what it said find it again when its id no longer matches.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use log::{debug, warn};
+
+use crate::{paths, store, CheatEntry};
+
+/// First line of a favorites file with ids.
+const HEADER: &str = "# id\tcategory\tcommand\tdescription";
+
+/// Where a favorite's entry was when it was starred.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Star {
+    category: String,
+    command: String,
+    description: String,
+}
+
+impl Star {
+    fn of(entry: &CheatEntry) -> Self {
+        Self {
+            category: entry.category.clone(),
+            command: entry.command.clone(),
+            description: entry.description.clone(),
+        }
+    }
+}
+
+/// Stars by the stable id of their entry.
+type Starred = BTreeMap<String, Star>;
+
+#[derive(Clone, Debug, Default)]
+pub struct Favorites {
+    starred: Starred,
+    /// Stars added (`Some`) or removed (`None`) since the last save, by id.
+    /// Saving applies them to what other instances saved meanwhile.
+    changed: BTreeMap<String, Option<Star>>,
+    /// Everything this instance starred or unstarred, which is all undoing
+    /// may take back.
+    touched: BTreeSet<String>,
+    /// Where to save, when persisting.
+    path: Option<PathBuf>,
+}
+
+impl Favorites {
+    /// Favorites that live only for this session.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Favorites loaded from and saved to the data directory.
+    pub fn persistent() -> Result<Self> {
+        let path = paths::data_dir()?.join("favorites");
+        Ok(Self {
+            starred: read(&path),
+            changed: BTreeMap::new(),
+            touched: BTreeSet::new(),
+            path: Some(path),
+        })
+    }
+
+    pub fn contains(&self, entry: &CheatEntry) -> bool {
+        self.starred.contains_key(&entry.stable_id())
+    }
+
+    /// Star `entry`, or unstar it if it already is. Returns whether it is
+    /// now a favorite.
+    pub fn toggle(&mut self, entry: &CheatEntry) -> bool {
+        let starred = !self.contains(entry);
+        let star = starred.then(|| Star::of(entry));
+        self.put(entry.stable_id(), star);
+        if let Err(err) = self.save() {
+            warn!("failed to save favorites: {err:#}");
+        }
+        starred
+    }
+
+    /// Star all of `entries`, keeping those that already are.
+    pub fn star(&mut self, entries: &[&CheatEntry]) {
+        for entry in entries {
+            self.put(entry.stable_id(), Some(Star::of(entry)));
+        }
+        if let Err(err) = self.save() {
+            warn!("failed to save favorites: {err:#}");
+        }
+    }
+
+    /// Move the favorites whose id no entry of `entries` has to the entry
+    /// they became: the one still in the same category with the same
+    /// command (say, because its pack gave it an id since), or else the
+    /// only one there with the same description, as after a built-in entry
+    /// was reworded. Entries with an id keep their stars whatever their
+    /// command and category, as the id does not change. Favorites that
+    /// match nothing are kept, in case their entry comes back (as with
+    /// another editor profile).
+    pub fn migrate(&mut self, entries: &[CheatEntry]) {
+        let ids: HashSet<String> = entries.iter().map(CheatEntry::stable_id).collect();
+        let mut moved = Vec::new();
+        for (id, star) in &self.starred {
+            if ids.contains(id) {
+                continue;
+            }
+            let in_category: Vec<&CheatEntry> = entries
+                .iter()
+                .filter(|e| e.category == star.category)
+                .collect();
+            let by_command = in_category.iter().find(|e| e.command == star.command);
+            let by_description = || {
+                let mut same = in_category
+                    .iter()
+                    .filter(|e| !star.description.is_empty() && e.description == star.description);
+                same.next().filter(|_| same.next().is_none())
+            };
+            if let Some(entry) = by_command.or_else(by_description)
+                && !self.starred.contains_key(&entry.stable_id())
+            {
+                moved.push((id.clone(), *entry));
+            }
+        }
+        if moved.is_empty() {
+            return;
+        }
+        for (id, entry) in moved {
+            debug!("favorite '{id}' is now '{}'", entry.stable_id());
+            self.put(id, None);
+            self.put(entry.stable_id(), Some(Star::of(entry)));
+        }
+        if let Err(err) = self.save() {
+            warn!("failed to save favorites: {err:#}");
+        }
+    }
+
+    /// Go back to `before`, a copy from earlier, and save. Only what this
+    /// instance changed since is undone, so stars from others stay.
+    pub fn restore(&mut self, before: Self) {
+        for id in self.touched.clone() {
+            let star = before.starred.get(&id);
+            if self.starred.get(&id) != star {
+                self.put(id, star.cloned());
+            }
+        }
+        if let Err(err) = self.save() {
+            warn!("failed to save favorites: {err:#}");
+        }
+    }
+
+    /// Star the entry with `id`, or unstar it with `None`, to be saved.
+    fn put(&mut self, id: String, star: Option<Star>) {
+        set(&mut self.starred, &id, star.clone());
+        self.touched.insert(id.clone());
+        self.changed.insert(id, star);
+    }
+
+    /// Apply the changes since the last save to the saved favorites, and
+    /// take up what other instances starred meanwhile.
+    fn save(&mut self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let starred = store::locked(path, || {
+            let mut starred = read(path);
+            for (id, star) in &self.changed {
+                set(&mut starred, id, star.clone());
+            }
+            let mut text = format!("{HEADER}\n");
+            for (id, star) in &starred {
+                text.push_str(&format!(
+                    "{id}\t{}\t{}\t{}\n",
+                    star.category, star.command, star.description
+                ));
+            }
+            store::write(path, &text)?;
+            Ok(starred)
+        })?;
+        self.starred = starred;
+        self.changed.clear();
+        Ok(())
+    }
+}
+
+/// The favorites saved at `path`; none if it cannot be read. Stars from
+/// before the ids get the id their entry would have without one, which
+/// [`Favorites::migrate`] corrects where the entry has one.
+fn read(path: &Path) -> Starred {
+    let mut starred = Starred::new();
+    let Ok(text) = fs::read_to_string(path) else {
+        return starred;
+    };
+    let mut lines = text.lines().peekable();
+    let with_ids = lines.next_if(|line| line.starts_with('#')).is_some();
+    for line in lines {
+        let mut fields = line.split('\t');
+        let id = if with_ids { fields.next() } else { None };
+        let (Some(category), Some(command)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        let star = Star {
+            category: category.to_string(),
+            command: command.to_string(),
+            description: fields.next().unwrap_or_default().to_string(),
+        };
+        let id = match id {
+            Some(id) => id.to_string(),
+            None => CheatEntry::new(category, command, "").stable_id(),
+        };
+        starred.insert(id, star);
+    }
+    starred
+}
+
+/// Star the entry with `id`, or unstar it with `None`.
+fn set(starred: &mut Starred, id: &str, star: Option<Star>) {
+    match star {
+        Some(star) => starred.insert(id.to_string(), star),
+        None => starred.remove(id),
+    };
+}
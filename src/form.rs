@@ -0,0 +1,141 @@
+//! The entry form: a small overlay for an entry's category, command and
+//! description. Editing (`e`) saves entries of local packs back to their
+//! file and copies any other entry to the personal pack, where it
+//! overrides the original; adding (`a`) creates an entry there.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+
+use crate::{packs, CheatEntry};
+
+/// Labels of the form's fields, in order.
+pub const LABELS: [&str; 3] = ["Category", "Command", "Description"];
+
+/// Where the form saves to.
+#[derive(Debug)]
+pub enum Target {
+    /// The entry of the pack file at `path` with this category and command.
+    Edit {
+        path: PathBuf,
+        category: String,
+        command: String,
+    },
+    /// A new entry of the personal pack, based on this one.
+    Copy(Box<CheatEntry>),
+    /// A new entry of the personal pack.
+    New,
+}
+
+#[derive(Debug)]
+pub struct Form {
+    /// What is typed into each of `LABELS`.
+    pub values: [String; 3],
+    /// Index of the field being typed into.
+    pub field: usize,
+    pub target: Target,
+    /// Existing categories, picked from with ←/→ in the category field.
+    categories: Vec<String>,
+}
+
+impl Form {
+    /// A form for changing `entry`, in place if it comes from a local pack.
+    pub fn edit(entry: &CheatEntry) -> Self {
+        let target = match &entry.file {
+            Some(path) => Target::Edit {
+                path: path.clone(),
+                category: entry.category.clone(),
+                command: entry.command.clone(),
+            },
+            None => Target::Copy(Box::new(entry.clone())),
+        };
+        Self {
+            values: [
+                entry.category.clone(),
+                entry.command.clone(),
+                entry.description.clone(),
+            ],
+            field: 0,
+            target,
+            categories: Vec::new(),
+        }
+    }
+
+    /// A form for a new entry in `category`, which ←/→ switch among
+    /// `categories`. Starts in the command field.
+    pub fn add(category: &str, categories: Vec<String>) -> Self {
+        Self {
+            values: [category.to_string(), String::new(), String::new()],
+            field: 1,
+            target: Target::New,
+            categories,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self.target {
+            Target::Edit { .. } => " Edit entry (Tab next field, Enter to save, Esc to cancel) ",
+            Target::Copy(_) => {
+                " Copy to your personal pack and edit (Enter to save, Esc to cancel) "
+            }
+            Target::New => " New entry (Tab next field, ←/→ pick category, Enter to save) ",
+        }
+    }
+
+    /// The field being typed into.
+    pub fn input(&mut self) -> &mut String {
+        &mut self.values[self.field]
+    }
+
+    /// Move to the next field, or the previous one, wrapping around.
+    pub fn step(&mut self, forward: bool) {
+        let count = LABELS.len();
+        self.field = (self.field + if forward { 1 } else { count - 1 }) % count;
+    }
+
+    /// In the category field, replace the category with the next (or
+    /// previous) existing one. Returns whether it did.
+    pub fn pick_category(&mut self, forward: bool) -> bool {
+        if self.field != 0 || self.categories.is_empty() {
+            return false;
+        }
+        let count = self.categories.len();
+        let next = match self.categories.iter().position(|c| *c == self.values[0]) {
+            Some(i) if forward => (i + 1) % count,
+            Some(i) => (i + count - 1) % count,
+            None if forward => 0,
+            None => count - 1,
+        };
+        self.values[0] = self.categories[next].clone();
+        true
+    }
+
+    /// Write the entry to its pack file. Returns the saved entry, tagged
+    /// with its pack and file like loaded ones.
+    pub fn save(&self) -> Result<CheatEntry> {
+        let [category, command, description] = self.values.clone().map(|v| v.trim().to_string());
+        for (label, value) in LABELS.iter().zip([&category, &command, &description]) {
+            if value.is_empty() {
+                bail!("{label} must not be empty");
+            }
+        }
+        let apply = |entry: &mut CheatEntry| {
+            entry.category = category.clone();
+            entry.command = command.clone();
+            entry.description = description.clone();
+        };
+        match &self.target {
+            Target::Edit {
+                path,
+                category,
+                command,
+            } => packs::update_entry(path, category, command, apply),
+            Target::Copy(original) => {
+                let mut entry = CheatEntry::clone(original);
+                apply(&mut entry);
+                packs::add_personal(entry)
+            }
+            Target::New => packs::add_personal(CheatEntry::new(category, command, description)),
+        }
+    }
+}
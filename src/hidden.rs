@@ -0,0 +1,167 @@
+//! Entries hidden from the list, saved to `hidden` in the data directory
+//! as one `category<TAB>command` line per entry, and listed for unhiding
+//! on the hidden entries screen.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use log::warn;
+
+use crate::{paths, store, CheatEntry};
+
+/// Hidden commands by category.
+type HiddenMap = BTreeMap<String, BTreeSet<String>>;
+
+#[derive(Clone, Debug, Default)]
+pub struct Hidden {
+    hidden: HiddenMap,
+    /// Entries hidden (`true`) or shown again (`false`) since the last
+    /// save, by category and command. Saving applies them to what other
+    /// instances saved meanwhile.
+    changed: BTreeMap<(String, String), bool>,
+    /// Everything this instance hid or showed, which is all undoing may
+    /// take back.
+    touched: BTreeSet<(String, String)>,
+    /// Where to save, when persisting.
+    path: Option<PathBuf>,
+}
+
+impl Hidden {
+    /// Hidden entries that live only for this session.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Hidden entries loaded from and saved to the data directory.
+    pub fn persistent() -> Result<Self> {
+        let path = paths::data_dir()?.join("hidden");
+        Ok(Self {
+            hidden: read(&path),
+            changed: BTreeMap::new(),
+            touched: BTreeSet::new(),
+            path: Some(path),
+        })
+    }
+
+    pub fn contains(&self, entry: &CheatEntry) -> bool {
+        contains(&self.hidden, &entry.category, &entry.command)
+    }
+
+    /// Hide `entries`, or show them again, and save.
+    pub fn set(&mut self, entries: &[&CheatEntry], hide: bool) {
+        for entry in entries {
+            self.put(&entry.category, &entry.command, hide);
+        }
+        if let Err(err) = self.save() {
+            warn!("failed to save hidden entries: {err:#}");
+        }
+    }
+
+    /// Category and command of every hidden entry, by category.
+    pub fn entries(&self) -> Vec<(&str, &str)> {
+        self.hidden
+            .iter()
+            .flat_map(|(category, commands)| {
+                commands
+                    .iter()
+                    .map(move |command| (category.as_str(), command.as_str()))
+            })
+            .collect()
+    }
+
+    /// Show the entry with `category` and `command` again, and save. Works
+    /// for entries that are not loaded, as with another editor profile.
+    pub fn unhide(&mut self, category: &str, command: &str) {
+        self.put(category, command, false);
+        if let Err(err) = self.save() {
+            warn!("failed to save hidden entries: {err:#}");
+        }
+    }
+
+    /// Go back to `before`, a copy from earlier, and save. Only what this
+    /// instance changed since is undone, so others' hidden entries stay.
+    pub fn restore(&mut self, before: Self) {
+        for (category, command) in self.touched.clone() {
+            let hide = contains(&before.hidden, &category, &command);
+            if contains(&self.hidden, &category, &command) != hide {
+                self.put(&category, &command, hide);
+            }
+        }
+        if let Err(err) = self.save() {
+            warn!("failed to save hidden entries: {err:#}");
+        }
+    }
+
+    /// Hide `command` in `category`, or show it again, to be saved.
+    fn put(&mut self, category: &str, command: &str, hide: bool) {
+        set(&mut self.hidden, category, command, hide);
+        let key = (category.to_string(), command.to_string());
+        self.touched.insert(key.clone());
+        self.changed.insert(key, hide);
+    }
+
+    /// Apply the changes since the last save to the saved entries, and
+    /// take up what other instances hid or showed meanwhile.
+    fn save(&mut self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let hidden = store::locked(path, || {
+            let mut hidden = read(path);
+            for ((category, command), &hide) in &self.changed {
+                set(&mut hidden, category, command, hide);
+            }
+            let text: String = keys(&hidden)
+                .map(|(category, command)| format!("{category}\t{command}\n"))
+                .collect();
+            store::write(path, &text)?;
+            Ok(hidden)
+        })?;
+        self.hidden = hidden;
+        self.changed.clear();
+        Ok(())
+    }
+}
+
+/// The hidden entries saved at `path`; none if it cannot be read.
+fn read(path: &Path) -> HiddenMap {
+    let mut hidden = HiddenMap::new();
+    if let Ok(text) = fs::read_to_string(path) {
+        for (category, command) in text.lines().filter_map(|line| line.split_once('\t')) {
+            set(&mut hidden, category, command, true);
+        }
+    }
+    hidden
+}
+
+fn contains(hidden: &HiddenMap, category: &str, command: &str) -> bool {
+    hidden
+        .get(category)
+        .is_some_and(|commands| commands.contains(command))
+}
+
+/// Hide `command` in `category`, or show it again.
+fn set(hidden: &mut HiddenMap, category: &str, command: &str, hide: bool) {
+    let commands = hidden.entry(category.to_string()).or_default();
+    if hide {
+        commands.insert(command.to_string());
+    } else {
+        commands.remove(command);
+    }
+    if commands.is_empty() {
+        hidden.remove(category);
+    }
+}
+
+/// Category and command of every hidden entry.
+fn keys(hidden: &HiddenMap) -> impl Iterator<Item = (String, String)> + '_ {
+    hidden.iter().flat_map(|(category, commands)| {
+        commands
+            .iter()
+            .map(move |command| (category.clone(), command.clone()))
+    })
+}
@@ -0,0 +1,135 @@
+//! Search history, recalled with the arrow keys while the search bar is
+//! focused. Optionally persisted to `history` in the data directory, one
+//! query per line.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use log::warn;
+
+use crate::{paths, store};
+
+/// Queries kept, oldest dropped first.
+const MAX_ENTRIES: usize = 200;
+
+#[derive(Debug, Default)]
+pub struct History {
+    entries: Vec<String>,
+    /// Queries submitted since the last save, oldest first. Saving puts
+    /// them after what other instances saved meanwhile.
+    added: Vec<String>,
+    /// Index into `entries` while recalling; `None` when editing the draft.
+    cursor: Option<usize>,
+    /// What was typed before recalling started, restored past the newest entry.
+    draft: String,
+    /// Where to save, when persisting.
+    path: Option<PathBuf>,
+}
+
+impl History {
+    /// A history that lives only for this session.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// A history loaded from and saved to the data directory.
+    pub fn persistent() -> Result<Self> {
+        let path = paths::data_dir()?.join("history");
+        Ok(Self {
+            entries: read(&path),
+            path: Some(path),
+            ..Self::default()
+        })
+    }
+
+    /// Record a submitted query as the newest entry.
+    pub fn push(&mut self, query: &str) {
+        self.cursor = None;
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        }
+        self.entries.retain(|q| q != query);
+        self.entries.push(query.to_string());
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.added.retain(|q| q != query);
+        self.added.push(query.to_string());
+        if let Err(err) = self.save() {
+            warn!("failed to save search history: {err:#}");
+        }
+    }
+
+    /// Step back to an older query; `current` is kept as the draft when
+    /// recalling starts. `None` when there is nothing older.
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let index = match self.cursor {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(0) => return None,
+            Some(i) => i - 1,
+        };
+        self.cursor = Some(index);
+        Some(&self.entries[index])
+    }
+
+    /// Step forward to a newer query, or back to the draft past the newest
+    /// one. `None` when not recalling.
+    pub fn newer(&mut self) -> Option<&str> {
+        let index = self.cursor?;
+        if index + 1 < self.entries.len() {
+            self.cursor = Some(index + 1);
+            Some(&self.entries[index + 1])
+        } else {
+            self.cursor = None;
+            Some(&self.draft)
+        }
+    }
+
+    /// Stop recalling, e.g. because the recalled query was edited.
+    pub fn reset(&mut self) {
+        self.cursor = None;
+    }
+
+    /// Position shown in the search bar while recalling, as (n, total).
+    pub fn position(&self) -> Option<(usize, usize)> {
+        self.cursor.map(|i| (i + 1, self.entries.len()))
+    }
+
+    /// Put the queries submitted since the last save after the saved ones,
+    /// and take up what other instances saved meanwhile.
+    fn save(&mut self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let entries = store::locked(path, || {
+            let mut entries: Vec<String> = read(path)
+                .into_iter()
+                .filter(|q| !self.added.contains(q))
+                .collect();
+            entries.extend(self.added.iter().cloned());
+            let excess = entries.len().saturating_sub(MAX_ENTRIES);
+            entries.drain(..excess);
+            store::write(path, &(entries.join("\n") + "\n"))?;
+            Ok(entries)
+        })?;
+        self.entries = entries;
+        self.added.clear();
+        Ok(())
+    }
+}
+
+/// The queries saved at `path`; none if it cannot be read.
+fn read(path: &Path) -> Vec<String> {
+    match fs::read_to_string(path) {
+        Ok(text) => text.lines().map(str::to_string).collect(),
+        Err(_) => Vec::new(),
+    }
+}
@@ -0,0 +1,79 @@
+//! Importers that turn third-party cheatsheet formats into [`CheatEntry`]
+//! records.
+//!
+//! [`CheatEntry`]: crate::CheatEntry
+
+use std::{fs, path::Path};
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+
+use crate::packs::PackFile;
+
+pub mod cheatsh;
+pub mod markdown;
+pub mod nvim;
+pub mod tldr;
+pub mod vimrc;
+
+/// Online sources that `vimbo packs import` can convert into a local pack.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Source {
+    /// https://cheat.sh
+    #[value(name = "cheat.sh")]
+    CheatSh,
+    /// https://tldr.sh pages; entries are categorised by page name
+    Tldr,
+}
+
+/// Fetch each topic from `source` (or read it from `from`), convert it and
+/// store it as a local pack that is loaded on every start.
+pub fn run(source: Source, topics: &[String], from: Option<&Path>) -> Result<()> {
+    if from.is_some() && topics.len() > 1 {
+        bail!("--from can only be used with a single topic");
+    }
+    for topic in topics {
+        import_topic(source, topic, from)?;
+    }
+    Ok(())
+}
+
+fn import_topic(source: Source, topic: &str, from: Option<&Path>) -> Result<()> {
+    let read_from = |path: &Path| {
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+    };
+    let (name, url, entries) = match source {
+        Source::CheatSh => {
+            let text = match from {
+                Some(path) => read_from(path)?,
+                None => cheatsh::fetch(topic)?,
+            };
+            (
+                format!("cheat.sh-{topic}"),
+                cheatsh::url(topic),
+                cheatsh::parse(&text, topic),
+            )
+        }
+        Source::Tldr => {
+            let (url, text) = match from {
+                Some(path) => (path.display().to_string(), read_from(path)?),
+                None => tldr::fetch(topic)?,
+            };
+            (format!("tldr-{topic}"), url, tldr::parse(&text, topic))
+        }
+    };
+
+    let pack = PackFile {
+        name: name.replace(['/', '\\'], "-"),
+        source: Some(url),
+        icons: Default::default(),
+        entries,
+    };
+    let path = pack.save()?;
+    println!(
+        "Imported {} entries into {}",
+        pack.entries.len(),
+        path.display()
+    );
+    Ok(())
+}
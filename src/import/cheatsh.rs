@@ -0,0 +1,82 @@
+//! cheat.sh importer.
+//!
+//! cheat.sh serves plain text when asked with `?T`: runs of `#` comment
+//! lines describe the command lines that follow them.
+
+use anyhow::{Context, Result};
+
+use crate::CheatEntry;
+
+pub fn url(topic: &str) -> String {
+    format!("https://cheat.sh/{topic}")
+}
+
+/// Download the cheat sheet for `topic` (e.g. `vim`, `tmux`).
+pub fn fetch(topic: &str) -> Result<String> {
+    // `?T` asks for plain text without ANSI colouring.
+    let url = format!("{}?T", url(topic));
+    ureq::get(&url)
+        .header("User-Agent", "curl/8 (vimbo)")
+        .call()
+        .with_context(|| format!("failed to fetch {url}"))?
+        .body_mut()
+        .read_to_string()
+        .with_context(|| format!("failed to read response from {url}"))
+}
+
+pub fn parse(text: &str, topic: &str) -> Vec<CheatEntry> {
+    let category = format!("cheat.sh - {topic}");
+    let mut entries = Vec::new();
+    let mut comment: Vec<String> = Vec::new();
+    let mut after_command = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            comment.clear();
+            after_command = false;
+            continue;
+        }
+        if let Some(c) = trimmed.strip_prefix('#') {
+            // A comment after commands starts a new description block.
+            if after_command {
+                comment.clear();
+                after_command = false;
+            }
+            let c = c.trim();
+            if !c.is_empty() {
+                comment.push(c.trim_end_matches(':').to_string());
+            }
+            continue;
+        }
+        if is_section_header(trimmed) {
+            continue;
+        }
+
+        // `cmd   # what it does` style lines carry their own description.
+        let (command, inline) = match trimmed.split_once(" #") {
+            Some((cmd, desc)) => (cmd.trim(), Some(desc.trim())),
+            None => (trimmed, None),
+        };
+        let description = match inline {
+            Some(d) if !d.is_empty() => d.to_string(),
+            _ => comment.join(" "),
+        };
+        if !command.is_empty() && !description.is_empty() {
+            entries.push(CheatEntry::new(category.clone(), command, description));
+        }
+        after_command = true;
+    }
+
+    entries
+}
+
+/// cheat.sh separates its upstream sources with lines like `tldr:vim`.
+fn is_section_header(line: &str) -> bool {
+    !line.contains(' ')
+        && line.split_once(':').is_some_and(|(src, topic)| {
+            !src.is_empty()
+                && !topic.is_empty()
+                && src.chars().all(|c| c.is_ascii_alphanumeric() || c == '.')
+        })
+}
@@ -0,0 +1,184 @@
+//! Markdown table importer.
+//!
+//! Understands the common GitHub cheatsheet layout:
+//!
+//! ```markdown
+//! ## Motions
+//!
+//! | Command | Description          |
+//! |---------|----------------------|
+//! | `w`     | next word            |
+//! ```
+//!
+//! Every data row becomes an entry whose category is the nearest heading
+//! above the table (or the file name if there is none).
+
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use log::debug;
+
+use crate::CheatEntry;
+
+/// Read `path` and import every Markdown table row as a cheat entry.
+pub fn import_file(path: &Path) -> Result<Vec<CheatEntry>> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let fallback = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Imported".to_string());
+    let entries = parse(&text, &fallback);
+    debug!("imported {} entries from {}", entries.len(), path.display());
+    Ok(entries)
+}
+
+/// Parse `| command | description |` tables out of Markdown text.
+///
+/// `fallback_category` is used for tables that appear before any heading.
+pub fn parse(text: &str, fallback_category: &str) -> Vec<CheatEntry> {
+    let mut entries = Vec::new();
+    let mut category = fallback_category.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    let mut i = 0;
+    let mut in_fence = false;
+
+    while i < lines.len() {
+        let line = lines[i].trim();
+        if line.starts_with("```") || line.starts_with("~~~") {
+            in_fence = !in_fence;
+            i += 1;
+            continue;
+        }
+        if in_fence {
+            i += 1;
+            continue;
+        }
+        if let Some(heading) = heading_text(line) {
+            category = heading;
+            i += 1;
+            continue;
+        }
+
+        // A table starts with a header row directly followed by a separator.
+        let is_table = line.starts_with('|')
+            && lines
+                .get(i + 1)
+                .is_some_and(|next| is_separator_row(next.trim()));
+        if !is_table {
+            i += 1;
+            continue;
+        }
+
+        let header = split_row(line);
+        let (cmd_col, desc_col) = pick_columns(&header);
+        i += 2;
+        while i < lines.len() && lines[i].trim().starts_with('|') {
+            let cells = split_row(lines[i].trim());
+            let command = cells.get(cmd_col).map(|c| clean_command(c));
+            let description = cells.get(desc_col).map(|d| clean_text(d));
+            if let (Some(command), Some(description)) = (command, description)
+                && !command.is_empty()
+            {
+                entries.push(CheatEntry::new(category.clone(), command, description));
+            }
+            i += 1;
+        }
+    }
+
+    entries
+}
+
+/// Return the text of an ATX heading (`## Foo`), if `line` is one.
+fn heading_text(line: &str) -> Option<String> {
+    let rest = line.trim_start_matches('#');
+    let level = line.len() - rest.len();
+    if level == 0 || level > 6 || !(rest.is_empty() || rest.starts_with(' ')) {
+        return None;
+    }
+    let text = clean_text(rest.trim().trim_end_matches('#').trim());
+    (!text.is_empty()).then_some(text)
+}
+
+fn is_separator_row(line: &str) -> bool {
+    line.starts_with('|')
+        && line.contains('-')
+        && line
+            .chars()
+            .all(|c| matches!(c, '|' | '-' | ':' | ' ' | '\t'))
+}
+
+/// Split a table row into trimmed cells, honouring `\|` escapes.
+fn split_row(line: &str) -> Vec<String> {
+    let inner = line.strip_prefix('|').unwrap_or(line);
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+/// Work out which columns hold the command and the description.
+///
+/// Header names are matched loosely; otherwise the first two columns are
+/// assumed to be command and description.
+fn pick_columns(header: &[String]) -> (usize, usize) {
+    let find = |names: &[&str]| {
+        header.iter().position(|h| {
+            let h = h.to_lowercase();
+            names.iter().any(|n| h.contains(n))
+        })
+    };
+    let cmd = find(&["command", "key", "shortcut", "mapping", "cmd"]).unwrap_or(0);
+    let desc = find(&["description", "action", "meaning", "what", "desc"])
+        .filter(|&d| d != cmd)
+        .unwrap_or(if cmd == 0 { 1 } else { 0 });
+    (cmd, desc)
+}
+
+/// Commands are usually wrapped in backticks or `<kbd>` tags. A cell that
+/// is one code span keeps the backticks inside it (`` `{a-z} ``).
+fn clean_command(cell: &str) -> String {
+    let cell = cell.replace("<kbd>", "").replace("</kbd>", "");
+    match code_span(cell.trim()) {
+        Some(code) => code.to_string(),
+        None => clean_text(&cell),
+    }
+}
+
+/// The contents of `text` if it is a single inline code span: fenced by
+/// equal runs of backticks, with one padding space on both sides removed.
+fn code_span(text: &str) -> Option<&str> {
+    let fence = text.len() - text.trim_start_matches('`').len();
+    if fence == 0 || text.len() < 2 * fence + 1 {
+        return None;
+    }
+    let inner = text[fence..].strip_suffix(&text[..fence])?;
+    if inner.starts_with('`') || inner.ends_with('`') || inner.contains(&text[..fence]) {
+        return None;
+    }
+    let inner = match inner.strip_prefix(' ').and_then(|i| i.strip_suffix(' ')) {
+        Some(padded) if !padded.trim().is_empty() => padded,
+        _ => inner,
+    };
+    Some(inner)
+}
+
+/// Strip inline Markdown decoration that would otherwise clutter the list.
+fn clean_text(cell: &str) -> String {
+    let text = cell.replace("<br>", " ").replace("<br/>", " ");
+    let text = text.replace('`', "").replace("**", "");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
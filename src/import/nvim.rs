@@ -0,0 +1,151 @@
+//! Live key mappings from a running Neovim over msgpack-rpc.
+//!
+//! Connects to `$NVIM` (set inside `:terminal` buffers) or an explicit
+//! address given to `nvim --listen`: a Unix socket path or `host:port`.
+
+use std::{
+    env,
+    io::{BufReader, BufWriter, Read, Write},
+    net::TcpStream,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use log::debug;
+use rmpv::Value;
+
+use crate::{CheatEntry, Mode};
+
+const CATEGORY: &str = "Live mappings (nvim)";
+
+/// Modes queried with `nvim_get_keymap`, with the label shown in entries.
+const MODES: &[(&str, &str)] = &[
+    ("n", "normal"),
+    ("x", "visual"),
+    ("s", "select"),
+    ("o", "operator-pending"),
+    ("i", "insert"),
+    ("c", "cmdline"),
+    ("t", "terminal"),
+];
+
+/// Fetch every global mapping from the Neovim listening on `address`
+/// (or `$NVIM` when `None`).
+pub fn fetch_mappings(address: Option<&str>) -> Result<Vec<CheatEntry>> {
+    let address = match address {
+        Some(a) => a.to_string(),
+        None => env::var("NVIM").context("no Neovim address given and $NVIM is not set")?,
+    };
+    debug!("connecting to nvim at {address}");
+    let mut rpc = Rpc::connect(&address)?;
+
+    let mut entries = Vec::new();
+    for &(mode, label) in MODES {
+        let keymaps = rpc.call("nvim_get_keymap", vec![Value::from(mode)])?;
+        let Value::Array(keymaps) = keymaps else {
+            bail!("unexpected nvim_get_keymap result for mode {mode}");
+        };
+        entries.extend(keymaps.iter().filter_map(|map| keymap_entry(map, label)));
+    }
+    debug!("fetched {} live mappings", entries.len());
+    Ok(entries)
+}
+
+fn keymap_entry(map: &Value, mode: &str) -> Option<CheatEntry> {
+    let field = |name: &str| {
+        map.as_map()?
+            .iter()
+            .find(|(k, _)| k.as_str() == Some(name))
+            .map(|(_, v)| v)
+    };
+    let lhs = field("lhs")?.as_str()?;
+    // <Plug> mappings are plugin plumbing, not something you type.
+    if lhs.starts_with("<Plug>") {
+        return None;
+    }
+    let rhs = field("rhs")
+        .and_then(Value::as_str)
+        .filter(|r| !r.is_empty())
+        .or_else(|| field("callback").map(|_| "<Lua function>"))
+        .unwrap_or("<Nop>");
+    let description = match field("desc").and_then(Value::as_str) {
+        Some(desc) => format!("{desc} ({mode}: {rhs})"),
+        None => format!("{mode}: {rhs}"),
+    };
+    let mut entry = CheatEntry::new(CATEGORY, lhs, description);
+    entry.mode = Mode::from_map_label(mode);
+    Some(entry)
+}
+
+/// A minimal synchronous msgpack-rpc client: one request at a time.
+struct Rpc {
+    reader: BufReader<Box<dyn Read>>,
+    writer: BufWriter<Box<dyn Write>>,
+    next_id: u32,
+}
+
+impl Rpc {
+    fn connect(address: &str) -> Result<Self> {
+        let (reader, writer): (Box<dyn Read>, Box<dyn Write>) = if is_tcp_address(address) {
+            let stream = TcpStream::connect(address)
+                .with_context(|| format!("failed to connect to nvim at {address}"))?;
+            (Box::new(stream.try_clone()?), Box::new(stream))
+        } else {
+            connect_socket(address)?
+        };
+        Ok(Self {
+            reader: BufReader::new(reader),
+            writer: BufWriter::new(writer),
+            next_id: 0,
+        })
+    }
+
+    fn call(&mut self, method: &str, params: Vec<Value>) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let request = Value::Array(vec![
+            Value::from(0),
+            Value::from(id),
+            Value::from(method),
+            Value::Array(params),
+        ]);
+        rmpv::encode::write_value(&mut self.writer, &request)?;
+        self.writer.flush()?;
+
+        // Skip notifications and anything that isn't our response.
+        loop {
+            let message = rmpv::decode::read_value(&mut self.reader)
+                .context("failed to read nvim response")?;
+            let Value::Array(parts) = message else {
+                continue;
+            };
+            if parts.len() != 4
+                || parts[0].as_u64() != Some(1)
+                || parts[1].as_u64() != Some(id.into())
+            {
+                continue;
+            }
+            if !parts[2].is_nil() {
+                return Err(anyhow!("{method} failed: {}", parts[2]));
+            }
+            return Ok(parts[3].clone());
+        }
+    }
+}
+
+fn is_tcp_address(address: &str) -> bool {
+    address
+        .rsplit_once(':')
+        .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok())
+}
+
+#[cfg(unix)]
+fn connect_socket(path: &str) -> Result<(Box<dyn Read>, Box<dyn Write>)> {
+    let stream = std::os::unix::net::UnixStream::connect(path)
+        .with_context(|| format!("failed to connect to nvim socket {path}"))?;
+    Ok((Box::new(stream.try_clone()?), Box::new(stream)))
+}
+
+#[cfg(not(unix))]
+fn connect_socket(path: &str) -> Result<(Box<dyn Read>, Box<dyn Write>)> {
+    bail!("named pipe {path} is not supported; start nvim with --listen host:port")
+}
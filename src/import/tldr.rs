@@ -0,0 +1,53 @@
+//! tldr-pages importer.
+//!
+//! A tldr page is Markdown where each example is a `- description:` line
+//! followed by a `` `command` `` line. Placeholders are written `{{like
+//! this}}` and are converted to vimbo's `{like this}` notation.
+
+use anyhow::{bail, Context, Result};
+
+use crate::CheatEntry;
+
+const PLATFORMS: &[&str] = &["common", "linux", "osx", "windows"];
+
+pub fn url(platform: &str, page: &str) -> String {
+    format!("https://raw.githubusercontent.com/tldr-pages/tldr/main/pages/{platform}/{page}.md")
+}
+
+/// Download `page`, trying the platform directories in order.
+pub fn fetch(page: &str) -> Result<(String, String)> {
+    for platform in PLATFORMS {
+        let url = url(platform, page);
+        match ureq::get(&url).call() {
+            Ok(mut response) => {
+                let text = response
+                    .body_mut()
+                    .read_to_string()
+                    .with_context(|| format!("failed to read response from {url}"))?;
+                return Ok((url, text));
+            }
+            Err(ureq::Error::StatusCode(404)) => continue,
+            Err(err) => return Err(err).with_context(|| format!("failed to fetch {url}")),
+        }
+    }
+    bail!("no tldr page named '{page}'")
+}
+
+pub fn parse(text: &str, page: &str) -> Vec<CheatEntry> {
+    let mut entries = Vec::new();
+    let mut description: Option<String> = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(d) = line.strip_prefix("- ") {
+            description = Some(d.trim().trim_end_matches(':').to_string());
+        } else if let Some(cmd) = line.strip_prefix('`').and_then(|l| l.strip_suffix('`'))
+            && let Some(d) = description.take()
+        {
+            let command = cmd.replace("{{", "{").replace("}}", "}");
+            entries.push(CheatEntry::new(page, command, d));
+        }
+    }
+
+    entries
+}
@@ -0,0 +1,188 @@
+//! Personal key mappings from the user's vimrc / init.vim.
+//!
+//! Only `:map`-family commands are understood; everything else in the file
+//! is ignored. A comment line directly above a mapping is used as its
+//! description.
+
+use std::{env, fs, path::Path, path::PathBuf};
+
+use anyhow::{Context, Result};
+use log::debug;
+
+use crate::{CheatEntry, Mode};
+
+const CATEGORY: &str = "My mappings";
+
+/// Map arguments that may precede the left-hand side.
+const MAP_ARGS: &[&str] = &[
+    "<buffer>", "<nowait>", "<silent>", "<special>", "<script>", "<expr>", "<unique>",
+];
+
+/// The usual vimrc locations for Vim and Neovim that exist on this machine.
+pub fn default_paths() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    let nvim_config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config"))
+        .join("nvim")
+        .join("init.vim");
+    [home.join(".vimrc"), home.join(".vim").join("vimrc"), nvim_config]
+        .into_iter()
+        .filter(|p| p.is_file())
+        .collect()
+}
+
+/// Read `path` and turn every mapping into a "My mappings" entry.
+pub fn import_file(path: &Path) -> Result<Vec<CheatEntry>> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let entries = parse(&text);
+    debug!("imported {} mappings from {}", entries.len(), path.display());
+    Ok(entries)
+}
+
+/// Import the given vimrc files, dropping mappings repeated across them
+/// (e.g. when init.vim sources ~/.vimrc).
+pub fn import_files(paths: &[PathBuf]) -> Result<Vec<CheatEntry>> {
+    let mut entries: Vec<CheatEntry> = Vec::new();
+    for path in paths {
+        for entry in import_file(path)? {
+            let duplicate = entries
+                .iter()
+                .any(|e| e.command == entry.command && e.description == entry.description);
+            if !duplicate {
+                entries.push(entry);
+            }
+        }
+    }
+    Ok(entries)
+}
+
+pub fn parse(text: &str) -> Vec<CheatEntry> {
+    let mut entries = Vec::new();
+    let mut comment: Option<String> = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(c) = line.strip_prefix('"') {
+            let c = c.trim();
+            comment = (!c.is_empty()).then(|| c.to_string());
+            continue;
+        }
+        if let Some((mode, lhs, rhs)) = parse_mapping(line) {
+            let description = match comment.take() {
+                Some(c) => format!("{c} ({mode}: {rhs})"),
+                None => format!("{mode}: {rhs}"),
+            };
+            let mut entry = CheatEntry::new(CATEGORY, lhs, description);
+            entry.mode = Mode::from_map_label(mode);
+            entries.push(entry);
+        } else {
+            comment = None;
+        }
+    }
+
+    entries
+}
+
+/// Split a `:map`-family line into (mode description, lhs, rhs).
+fn parse_mapping(line: &str) -> Option<(&'static str, String, String)> {
+    let line = line.trim_start_matches(':');
+    let (cmd, mut rest) = line.split_once(char::is_whitespace)?;
+    let mode = map_mode(cmd)?;
+
+    loop {
+        rest = rest.trim_start();
+        let Some(arg) = MAP_ARGS
+            .iter()
+            .find(|a| rest.get(..a.len()).is_some_and(|p| p.eq_ignore_ascii_case(a)))
+        else {
+            break;
+        };
+        rest = &rest[arg.len()..];
+    }
+
+    let (lhs, rhs) = rest.split_once(char::is_whitespace)?;
+    let rhs = rhs.trim();
+    if lhs.is_empty() || rhs.is_empty() {
+        return None;
+    }
+    Some((mode, lhs.to_string(), rhs.to_string()))
+}
+
+/// Map a `:map` command name (including the usual abbreviations such as
+/// `nno`) to the mode it defines a mapping for.
+fn map_mode(cmd: &str) -> Option<&'static str> {
+    let bang = cmd.ends_with('!');
+    let cmd = cmd.trim_end_matches('!');
+    if is_abbrev(cmd, "map", 3) || is_abbrev(cmd, "noremap", 2) {
+        return Some(if bang { "insert/cmdline" } else { "normal/visual" });
+    }
+
+    let mut chars = cmd.chars();
+    let prefix = chars.next()?;
+    let body = chars.as_str();
+    // Shortest accepted abbreviation of "map" / "noremap" after the prefix;
+    // shorter forms are other commands (e.g. `:tn` is `:tnext`).
+    let (map_min, noremap_min, mode) = match prefix {
+        'n' => (1, 1, "normal"),
+        'v' => (1, 1, "visual/select"),
+        'x' => (1, 1, "visual"),
+        'l' => (1, 1, "lang-arg"),
+        'o' => (1, 2, "operator-pending"),
+        'i' => (1, 2, "insert"),
+        'c' => (1, 2, "cmdline"),
+        't' => (2, 2, "terminal"),
+        's' => (3, 3, "select"),
+        _ => return None,
+    };
+    (is_abbrev(body, "map", map_min) || is_abbrev(body, "noremap", noremap_min)).then_some(mode)
+}
+
+/// Whether `word` is `full` or an abbreviation of it at least `min` long.
+fn is_abbrev(word: &str, full: &str, min: usize) -> bool {
+    word.len() >= min && full.starts_with(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_commands_and_their_abbreviations() {
+        for (cmd, mode) in [
+            ("map", "normal/visual"),
+            ("no", "normal/visual"),
+            ("map!", "insert/cmdline"),
+            ("nn", "normal"),
+            ("nnoremap", "normal"),
+            ("nm", "normal"),
+            ("xn", "visual"),
+            ("vmap", "visual/select"),
+            ("smap", "select"),
+            ("ino", "insert"),
+            ("tma", "terminal"),
+        ] {
+            assert_eq!(map_mode(cmd), Some(mode), "{cmd}");
+        }
+    }
+
+    #[test]
+    fn other_commands_are_not_mappings() {
+        // :cnext, :tnext, :vsplit, :sm (:smagic) and :ma (:mark).
+        for cmd in ["cn", "tn", "vs", "sm", "ma", "set", "nnoremapx"] {
+            assert_eq!(map_mode(cmd), None, "{cmd}");
+        }
+        assert!(parse(":cn foo bar\n:tn foo bar\n:vs foo bar\n").is_empty());
+    }
+
+    #[test]
+    fn mappings_keep_their_comment() {
+        let entries = parse("\" save quickly\nnnoremap <silent> <leader>w :w<CR>\n");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command, "<leader>w");
+        assert_eq!(entries[0].description, "save quickly (normal: :w<CR>)");
+    }
+}
@@ -0,0 +1,160 @@
+//! Search index: the fields each entry is searched by, lowercased once when
+//! the cheats are loaded instead of on every key press.
+
+use crate::{
+    cheat::{CheatEntry, Difficulty},
+    matcher::{self, Pattern, Text},
+    query::{Field, Query},
+    notes::Notes,
+    synonyms::Synonyms,
+};
+
+/// Prepared fields of one entry.
+#[derive(Debug)]
+pub struct Fields {
+    pub command: Text,
+    /// The description in the chosen language.
+    pub description: Text,
+    /// The English description, which stays searchable with a translation
+    /// active.
+    pub english: Text,
+    pub category: Text,
+    /// Your note on the entry, empty without one.
+    pub note: Text,
+    /// Tags plus the difficulty and mode labels, lowercased.
+    pub tags: Vec<String>,
+}
+
+impl Fields {
+    /// How well the entry matches the query's terms, or `None` if it doesn't.
+    /// Every term must match: free-text terms anywhere, scoring against the
+    /// entry's best field with command matches weighted above description
+    /// matches and those above category ones; `cmd:`-style terms in their
+    /// own field. Shorter descriptions win ties.
+    pub fn relevance(&self, terms: &Terms) -> Option<i32> {
+        let mut score = 0;
+        for (field, pattern) in &terms.scoped {
+            let (values, weight) = match field {
+                Field::Command => ([Some(&self.command), None], 3),
+                Field::Description => ([Some(&self.description), Some(&self.english)], 2),
+                Field::Category => ([Some(&self.category), None], 1),
+            };
+            let best = values
+                .into_iter()
+                .flatten()
+                .filter_map(|value| matcher::relevance_in(pattern, value))
+                .max()
+                .filter(|&s| s > 0)?;
+            score += best * weight;
+        }
+        let fields = [
+            (&self.command, 3),
+            (&self.description, 2),
+            (&self.english, 2),
+            (&self.note, 2),
+            (&self.category, 1),
+        ];
+        for alternatives in &terms.words {
+            score += term_relevance(&fields, alternatives)?;
+        }
+        Some(score - self.description.as_str().len() as i32 / 8)
+    }
+
+    /// Whether the entry contains any of the query's excluded words.
+    pub fn excluded_by(&self, query: &Query) -> bool {
+        query.excludes(&[
+            self.category.as_str(),
+            self.command.as_str(),
+            self.description.as_str(),
+            self.english.as_str(),
+            self.note.as_str(),
+        ])
+    }
+}
+
+/// Best weighted score of a term, or one of its synonyms, in any of
+/// `fields`. Synonym matches rank below literal ones.
+fn term_relevance(fields: &[(&Text, i32)], alternatives: &[Pattern]) -> Option<i32> {
+    alternatives
+        .iter()
+        .enumerate()
+        .filter_map(|(n, pattern)| {
+            let best = fields
+                .iter()
+                .filter_map(|(field, weight)| {
+                    matcher::relevance_in(pattern, field)
+                        .filter(|&s| s > 0)
+                        .map(|s| s * weight)
+                })
+                .max()?;
+            Some(if n == 0 { best } else { best * 3 / 4 })
+        })
+        .max()
+}
+
+/// A query's terms, prepared once per search rather than once per entry.
+#[derive(Debug)]
+pub struct Terms {
+    /// Each free-text word followed by its synonyms.
+    words: Vec<Vec<Pattern>>,
+    scoped: Vec<(Field, Pattern)>,
+}
+
+impl Terms {
+    pub fn new(query: &Query, synonyms: &Synonyms) -> Self {
+        Self {
+            words: query
+                .text
+                .split_whitespace()
+                .map(|word| synonyms.alternatives(word).map(Pattern::new).collect())
+                .collect(),
+            scoped: query
+                .scoped
+                .iter()
+                .map(|(field, term)| (*field, Pattern::new(term)))
+                .collect(),
+        }
+    }
+}
+
+/// Prepared fields for every entry, in the same order as the cheats.
+#[derive(Debug, Default)]
+pub struct Index(Vec<Fields>);
+
+impl Index {
+    pub fn build(cheats: &[CheatEntry], lang: Option<&str>, notes: &Notes) -> Self {
+        Self(
+            cheats
+                .iter()
+                .map(|c| {
+                    let difficulty = c.difficulty.map(Difficulty::label);
+                    Fields {
+                        command: Text::new(&c.command),
+                        description: Text::new(c.description_in(lang)),
+                        english: Text::new(&c.description),
+                        category: Text::new(&c.category),
+                        note: Text::new(notes.get(c).unwrap_or_default()),
+                        tags: c
+                            .tags
+                            .iter()
+                            .map(String::as_str)
+                            .chain(difficulty)
+                            .chain([c.mode().label()])
+                            .map(str::to_lowercase)
+                            .collect(),
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Search the entry at `index` by `note` from now on.
+    pub fn set_note(&mut self, index: usize, note: &str) {
+        self.0[index].note = Text::new(note);
+    }
+
+    /// Fields of the entry at `index` in the cheats.
+    pub fn get(&self, index: usize) -> &Fields {
+        &self.0[index]
+    }
+}
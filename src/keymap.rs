@@ -0,0 +1,226 @@
+//! Keys of the list, sidebar and help overlay, remappable per action from
+//! the `[keys]` table of the config file (e.g. `down = ["j", "<C-n>"]`).
+//! Global Ctrl keys and the two-key commands starting with `g` and `z`
+//! are fixed.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context, Result};
+use crossterm::event::{KeyCode, KeyModifiers};
+use serde::Deserialize;
+
+use crate::keys::{self, Key, Notation};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    Quit,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Bottom,
+    NextMatch,
+    PrevMatch,
+    /// Clear the query and focus the search bar.
+    Search,
+    EditSearch,
+    /// Open or close the details (or a group).
+    Details,
+    /// Close the details.
+    Close,
+    /// Previous related link, or focus the sidebar.
+    Left,
+    /// Next related link, or back to the list from the sidebar.
+    Right,
+    Help,
+    /// Open the entry's `:help` topic in Vim.
+    VimHelp,
+    Favorite,
+    FavoritesOnly,
+    /// Show only recently opened or copied entries.
+    Recent,
+    /// Hide the entry (or the marked ones) from the list, or unhide it.
+    Hide,
+    /// List hidden entries too.
+    ShowHidden,
+    /// Revert the latest change to favorites, hidden entries, notes or packs.
+    Undo,
+    /// Mark or unmark the entry for batch actions.
+    Mark,
+    /// Copy the marked (or selected) commands to the clipboard.
+    Copy,
+    /// Edit the entry, or a copy of it in the personal pack.
+    Edit,
+    /// Write a note on the entry.
+    Note,
+    /// Add an entry to the personal pack.
+    Add,
+    /// Show the selected entry alone, large and centered.
+    Zen,
+    Table,
+    Sort,
+    ReverseSort,
+    Density,
+}
+
+impl Action {
+    /// The action's key in the `[keys]` table (e.g. `page-up`).
+    fn name(self) -> String {
+        let mut name = String::new();
+        for c in format!("{self:?}").chars() {
+            if c.is_uppercase() && !name.is_empty() {
+                name.push('-');
+            }
+            name.push(c.to_ascii_lowercase());
+        }
+        name
+    }
+}
+
+/// Keys bound to an action in the config file: one key or a list.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Binding {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Binding {
+    fn keys(&self) -> &[String] {
+        match self {
+            Binding::One(key) => std::slice::from_ref(key),
+            Binding::Many(keys) => keys,
+        }
+    }
+}
+
+const DEFAULTS: &[(Action, &[&str])] = &[
+    (Action::Quit, &["q"]),
+    (Action::Up, &["k", "<Up>"]),
+    (Action::Down, &["j", "<Down>"]),
+    (Action::PageUp, &["<PageUp>"]),
+    (Action::PageDown, &["<PageDown>"]),
+    (Action::Bottom, &["G"]),
+    (Action::NextMatch, &["n"]),
+    (Action::PrevMatch, &["N"]),
+    (Action::Search, &["/"]),
+    (Action::EditSearch, &["i"]),
+    (Action::Details, &["<Enter>"]),
+    (Action::Close, &["<Esc>"]),
+    (Action::Left, &["h", "<Left>"]),
+    (Action::Right, &["l", "<Right>"]),
+    (Action::Help, &["?"]),
+    (Action::VimHelp, &["K"]),
+    (Action::Favorite, &["s"]),
+    (Action::FavoritesOnly, &["f"]),
+    (Action::Recent, &["r"]),
+    (Action::Hide, &["x"]),
+    (Action::ShowHidden, &["H"]),
+    (Action::Undo, &["u"]),
+    (Action::Mark, &["<Space>"]),
+    (Action::Copy, &["y"]),
+    (Action::Edit, &["e"]),
+    (Action::Note, &["c"]),
+    (Action::Add, &["a"]),
+    (Action::Zen, &["Z"]),
+    (Action::Table, &["T"]),
+    (Action::Sort, &["o"]),
+    (Action::ReverseSort, &["O"]),
+    (Action::Density, &["D"]),
+];
+
+/// Keys handled before the keymap, which actions cannot be bound to.
+const RESERVED: &[&str] = &[
+    "<C-c>",
+    "<C-e>",
+    "<C-l>",
+    "<C-k>",
+    "<C-t>",
+    "<C-o>",
+    "<C-p>",
+    "<C-r>",
+    "<Tab>",
+    "<F1>",
+    "<C-Left>",
+    "<C-Right>",
+    "<C-Up>",
+    "<C-Down>",
+    "g",
+    "z",
+    "0",
+    "1",
+    "2",
+    "3",
+    "4",
+    "5",
+    "6",
+    "7",
+    "8",
+    "9",
+];
+
+#[derive(Debug)]
+pub struct Keymap {
+    bindings: Vec<(Key, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&BTreeMap::new()).expect("default key bindings are valid")
+    }
+}
+
+impl Keymap {
+    /// The default bindings, with the actions in `overrides` bound to the
+    /// given keys instead. Fails on unknown keys and on keys bound twice.
+    pub fn new(overrides: &BTreeMap<Action, Binding>) -> Result<Self> {
+        let mut bindings: Vec<(Key, Action)> = Vec::new();
+        for &(action, defaults) in DEFAULTS {
+            let texts: Vec<&str> = match overrides.get(&action) {
+                Some(binding) => binding.keys().iter().map(String::as_str).collect(),
+                None => defaults.to_vec(),
+            };
+            for text in texts {
+                let key = keys::parse_key(text)
+                    .with_context(|| format!("invalid key '{text}' for {}", action.name()))?;
+                if RESERVED
+                    .iter()
+                    .filter_map(|r| keys::parse_key(r))
+                    .any(|reserved| key.matches(&reserved))
+                {
+                    bail!("key '{text}' for {} is reserved", action.name());
+                }
+                if let Some((_, other)) = bindings.iter().find(|(k, _)| key.matches(k)) {
+                    bail!(
+                        "key '{text}' is bound to both {} and {}",
+                        other.name(),
+                        action.name()
+                    );
+                }
+                bindings.push((key, action));
+            }
+        }
+        Ok(Self { bindings })
+    }
+
+    /// The action bound to a key pressed in the terminal.
+    pub fn action(&self, code: KeyCode, modifiers: KeyModifiers) -> Option<Action> {
+        let typed = Key::from_event(code, modifiers)?;
+        self.bindings
+            .iter()
+            .find(|(key, _)| typed.matches(key))
+            .map(|&(_, action)| action)
+    }
+
+    /// Keys bound to `action`, for the help overlay (e.g. `j, Down`).
+    pub fn describe(&self, action: Action) -> String {
+        let keys: Vec<String> = self
+            .bindings
+            .iter()
+            .filter(|&&(_, a)| a == action)
+            .map(|(key, _)| key.render(Notation::Readable))
+            .collect();
+        keys.join(", ")
+    }
+}
@@ -0,0 +1,579 @@
+//! Structured keystrokes parsed from the free-text `command` notation.
+//!
+//! Entries keep writing commands the way people read them (`Ctrl + w`,
+//! `<C-w>`, `yiw / yaw`, `f{char}`); [`parse`] turns any of those spellings
+//! into [`Keys`] so they can be rendered in one consistent [`Notation`],
+//! compared, and matched against typed keys.
+
+use std::fmt::Write;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// A command: one or more alternative key sequences (`yy / Y`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keys {
+    pub alternatives: Vec<Vec<Token>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Token {
+    Key(Key),
+    /// Something the user fills in, e.g. `{char}` or `{motion}`.
+    Placeholder(String),
+    /// Text that is read, not typed (`then`, `(visual)`).
+    Note(String),
+    /// Visual gap between keys; not a keypress.
+    Gap,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Key {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub code: Code,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Code {
+    Char(char),
+    Named(Named),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Named {
+    Esc,
+    Enter,
+    Tab,
+    Space,
+    Backspace,
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Leader,
+    F(u8),
+}
+
+/// How keys are written when rendering.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Notation {
+    /// `Ctrl + w`, `Esc`: the notation the built-in cheatsheet uses.
+    Readable,
+    /// `<C-w>`, `<Esc>`: Vim's key notation, as used in mappings.
+    Vim,
+}
+
+/// Words that are read rather than typed.
+const NOTE_WORDS: &[&str] = &["then", "..."];
+
+impl Named {
+    fn from_name(name: &str) -> Option<Self> {
+        let named = match name.to_ascii_lowercase().as_str() {
+            "esc" | "escape" => Named::Esc,
+            "enter" | "cr" | "return" => Named::Enter,
+            "tab" => Named::Tab,
+            "space" => Named::Space,
+            "backspace" | "bs" => Named::Backspace,
+            "up" => Named::Up,
+            "down" => Named::Down,
+            "left" => Named::Left,
+            "right" => Named::Right,
+            "pageup" | "pgup" => Named::PageUp,
+            "pagedown" | "pgdn" => Named::PageDown,
+            "leader" => Named::Leader,
+            f if f.len() > 1 && f.starts_with('f') => Named::F(f[1..].parse().ok()?),
+            _ => return None,
+        };
+        Some(named)
+    }
+
+    fn name(self, notation: Notation) -> String {
+        let name = match self {
+            Named::Esc => "Esc",
+            Named::Enter => match notation {
+                Notation::Readable => "Enter",
+                Notation::Vim => "CR",
+            },
+            Named::Tab => "Tab",
+            Named::Space => "Space",
+            Named::Backspace => match notation {
+                Notation::Readable => "Backspace",
+                Notation::Vim => "BS",
+            },
+            Named::Up => "Up",
+            Named::Down => "Down",
+            Named::Left => "Left",
+            Named::Right => "Right",
+            Named::PageUp => "PageUp",
+            Named::PageDown => "PageDown",
+            Named::Leader => "leader",
+            Named::F(n) => return format!("F{n}"),
+        };
+        name.to_string()
+    }
+}
+
+impl Key {
+    fn plain(code: Code) -> Self {
+        Self {
+            ctrl: false,
+            alt: false,
+            shift: false,
+            code,
+        }
+    }
+
+    /// A key pressed in the terminal, as used by reverse lookup.
+    pub fn from_event(code: KeyCode, modifiers: KeyModifiers) -> Option<Self> {
+        let code = match code {
+            KeyCode::Char(' ') => Code::Named(Named::Space),
+            KeyCode::Char(c) => Code::Char(c),
+            KeyCode::Esc => Code::Named(Named::Esc),
+            KeyCode::Enter => Code::Named(Named::Enter),
+            KeyCode::Tab | KeyCode::BackTab => Code::Named(Named::Tab),
+            KeyCode::Backspace => Code::Named(Named::Backspace),
+            KeyCode::Up => Code::Named(Named::Up),
+            KeyCode::Down => Code::Named(Named::Down),
+            KeyCode::Left => Code::Named(Named::Left),
+            KeyCode::Right => Code::Named(Named::Right),
+            KeyCode::PageUp => Code::Named(Named::PageUp),
+            KeyCode::PageDown => Code::Named(Named::PageDown),
+            KeyCode::F(n) => Code::Named(Named::F(n)),
+            _ => return None,
+        };
+        Some(Self {
+            ctrl: modifiers.contains(KeyModifiers::CONTROL),
+            alt: modifiers.contains(KeyModifiers::ALT),
+            // Shift is already in the character for printable keys.
+            shift: modifiers.contains(KeyModifiers::SHIFT) && matches!(code, Code::Named(_)),
+            code,
+        })
+    }
+
+    /// Whether this typed key is the `expected` key of a command.
+    pub fn matches(&self, expected: &Key) -> bool {
+        if self.ctrl != expected.ctrl || self.alt != expected.alt {
+            return false;
+        }
+        match (&self.code, &expected.code) {
+            // Ctrl + W and Ctrl + w are the same key to a terminal.
+            (Code::Char(a), Code::Char(b)) if self.ctrl => a.eq_ignore_ascii_case(b),
+            (Code::Char(a), Code::Char(b)) => a == b,
+            // The default <leader> is backslash.
+            (Code::Char('\\'), Code::Named(Named::Leader)) => true,
+            (a, b) => a == b && self.shift == expected.shift,
+        }
+    }
+
+    fn has_modifiers(&self) -> bool {
+        self.ctrl || self.alt || self.shift
+    }
+
+    pub fn render(&self, notation: Notation) -> String {
+        let code = match &self.code {
+            Code::Char(c) => c.to_string(),
+            Code::Named(named) => named.name(notation),
+        };
+        match notation {
+            Notation::Readable
+                if self.code == Code::Named(Named::Leader) && !self.has_modifiers() =>
+            {
+                "<leader>".to_string()
+            }
+            Notation::Readable => {
+                let mut out = String::new();
+                for (on, name) in [
+                    (self.ctrl, "Ctrl"),
+                    (self.alt, "Alt"),
+                    (self.shift, "Shift"),
+                ] {
+                    if on {
+                        let _ = write!(out, "{name} + ");
+                    }
+                }
+                out + &code
+            }
+            Notation::Vim => {
+                if !self.has_modifiers() && matches!(self.code, Code::Char(_)) {
+                    return code;
+                }
+                let mut out = String::from("<");
+                for (on, prefix) in [(self.ctrl, "C-"), (self.alt, "M-"), (self.shift, "S-")] {
+                    if on {
+                        out.push_str(prefix);
+                    }
+                }
+                out + &code + ">"
+            }
+        }
+    }
+}
+
+/// How a command matched the start of some typed keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Typed {
+    /// The keys so far are the beginning of the command.
+    Prefix,
+    /// The whole command was typed, using this many keys.
+    Complete(usize),
+}
+
+impl Keys {
+    pub fn render(&self, notation: Notation) -> String {
+        self.alternatives
+            .iter()
+            .map(|seq| render_sequence(seq, notation))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    /// Match the start of `typed` against each alternative, preferring the
+    /// one that accounts for the most keys.
+    pub fn match_typed(&self, typed: &[Key]) -> Option<Typed> {
+        self.alternatives
+            .iter()
+            .filter_map(|seq| match_sequence(seq, typed))
+            .max()
+    }
+}
+
+fn match_sequence(seq: &[Token], typed: &[Key]) -> Option<Typed> {
+    let Some((token, rest)) = seq.split_first() else {
+        return Some(Typed::Complete(0));
+    };
+    let consumed = |n: usize, result: Option<Typed>| {
+        result.map(|r| match r {
+            Typed::Complete(m) => Typed::Complete(n + m),
+            Typed::Prefix => Typed::Prefix,
+        })
+    };
+    match token {
+        Token::Note(_) => match_sequence(rest, typed),
+        // A gap may stand for a typed space (`:e {file}`).
+        Token::Gap => match typed.first() {
+            Some(key) if key.code == Code::Named(Named::Space) => {
+                consumed(1, match_sequence(rest, &typed[1..]))
+                    .or_else(|| match_sequence(rest, typed))
+            }
+            _ => match_sequence(rest, typed),
+        },
+        _ if typed.is_empty() => Some(Typed::Prefix),
+        Token::Key(expected) => {
+            if !typed[0].matches(expected) {
+                return None;
+            }
+            consumed(1, match_sequence(rest, &typed[1..]))
+        }
+        Token::Placeholder(name) if name == "n" || name == "count" => {
+            let digits = typed
+                .iter()
+                .take_while(|k| matches!(k.code, Code::Char(c) if c.is_ascii_digit()))
+                .count();
+            if digits == 0 {
+                return None;
+            }
+            consumed(digits, match_sequence(rest, &typed[digits..]))
+        }
+        // A motion can be any number of keys: take what's left.
+        Token::Placeholder(name) if name == "motion" => Some(Typed::Complete(typed.len())),
+        Token::Placeholder(_) => consumed(1, match_sequence(rest, &typed[1..])),
+    }
+}
+
+fn render_sequence(seq: &[Token], notation: Notation) -> String {
+    let mut out = String::new();
+    // Whether the previous token was a key, and if so whether it rendered
+    // as a name (`Ctrl + w`, `Esc`) that needs spacing from its neighbours.
+    let mut prev_key: Option<bool> = None;
+    for token in seq {
+        let (text, is_key, is_name) = match token {
+            Token::Key(key) => {
+                let rendered = key.render(notation);
+                let is_name = notation == Notation::Readable
+                    && rendered.chars().count() > 1
+                    && !rendered.starts_with('<');
+                (rendered, true, is_name)
+            }
+            Token::Placeholder(name) => (format!("{{{name}}}"), true, false),
+            Token::Note(text) => (text.clone(), false, false),
+            Token::Gap => (" ".to_string(), false, false),
+        };
+        if is_key && prev_key.is_some_and(|prev_name| prev_name || is_name) {
+            out.push(' ');
+        }
+        out.push_str(&text);
+        prev_key = is_key.then_some(is_name);
+    }
+    out
+}
+
+/// The key `text` names, when it is exactly one key (`j`, `<C-n>`,
+/// `Ctrl + n`, `Down`).
+pub fn parse_key(text: &str) -> Option<Key> {
+    match parse(text).alternatives.as_slice() {
+        [tokens] => match tokens.as_slice() {
+            [Token::Key(key)] => Some(key.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Keys as literally typed (`ciw`, `<C-w>j`, `:e foo`), for matching
+/// against commands. Spaces are typed too; placeholders and notes are
+/// left out.
+pub fn parse_typed(text: &str) -> Vec<Key> {
+    parse_sequence(text.trim())
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Key(key) => Some(key),
+            Token::Gap => Some(Key::plain(Code::Named(Named::Space))),
+            Token::Placeholder(_) | Token::Note(_) => None,
+        })
+        .collect()
+}
+
+/// Parse a command written in any of the notations used by the datasets,
+/// packs and importers.
+pub fn parse(command: &str) -> Keys {
+    let alternatives = command
+        .split(" / ")
+        .map(str::trim)
+        .filter(|alt| !alt.is_empty())
+        .map(parse_sequence)
+        .collect();
+    Keys { alternatives }
+}
+
+fn parse_sequence(mut rest: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut word_start = true;
+    while !rest.is_empty() {
+        if rest.starts_with(char::is_whitespace) {
+            rest = rest.trim_start();
+            if !matches!(tokens.last(), None | Some(Token::Gap)) {
+                tokens.push(Token::Gap);
+            }
+            word_start = true;
+            continue;
+        }
+        let (token, len) = next_token(rest, word_start);
+        tokens.push(token);
+        rest = &rest[len..];
+        word_start = false;
+    }
+    if tokens.last() == Some(&Token::Gap) {
+        tokens.pop();
+    }
+    tokens
+}
+
+/// The token at the start of `s` and how many bytes it spans. Chords and
+/// key names are only recognised at the start of a word.
+fn next_token(s: &str, word_start: bool) -> (Token, usize) {
+    if word_start && let Some((key, len)) = modified_key(s) {
+        return (Token::Key(key), len);
+    }
+    if s.starts_with('<')
+        && let Some(end) = s.find('>')
+        && let Some(key) = vim_key(&s[1..end])
+    {
+        return (Token::Key(key), end + 1);
+    }
+    if s.starts_with('{')
+        && let Some(end) = s.find('}')
+    {
+        return (Token::Placeholder(s[1..end].to_string()), end + 1);
+    }
+    if s.starts_with('(')
+        && s[1..].starts_with(|c: char| c.is_ascii_lowercase())
+        && let Some(end) = s.find(')')
+    {
+        return (Token::Note(s[..=end].to_string()), end + 1);
+    }
+    // `,` followed by a gap separates steps ("Ctrl + w, then h").
+    if s.starts_with(", ") {
+        return (Token::Note(",".to_string()), 1);
+    }
+
+    let word_len = s.find(char::is_whitespace).unwrap_or(s.len());
+    let word = &s[..word_len];
+    if NOTE_WORDS.contains(&word) {
+        return (Token::Note(word.to_string()), word_len);
+    }
+    if word_start
+        && word.len() > 1
+        && word.chars().all(|c| c.is_ascii_alphanumeric())
+        && let Some(named) = Named::from_name(word)
+        // Only capitalised names: `tab` and `up` are also plain words.
+        && word.starts_with(char::is_uppercase)
+    {
+        return (Token::Key(Key::plain(Code::Named(named))), word_len);
+    }
+
+    let c = s.chars().next().unwrap_or_default();
+    (Token::Key(Key::plain(Code::Char(c))), c.len_utf8())
+}
+
+/// `Ctrl + w`, `Ctrl-w`, `Alt + s` or `Shift + Tab`.
+fn modified_key(s: &str) -> Option<(Key, usize)> {
+    let mut key = Key::plain(Code::Char(' '));
+    let mut rest = s;
+    loop {
+        let (modifier, after) = [
+            ("Ctrl + ", "ctrl"),
+            ("Ctrl-", "ctrl"),
+            ("Alt + ", "alt"),
+            ("Alt-", "alt"),
+            ("Shift + ", "shift"),
+            ("Shift-", "shift"),
+        ]
+        .iter()
+        .find_map(|(prefix, m)| rest.strip_prefix(prefix).map(|after| (*m, after)))?;
+        match modifier {
+            "ctrl" => key.ctrl = true,
+            "alt" => key.alt = true,
+            _ => key.shift = true,
+        }
+        rest = after;
+        if !["Ctrl", "Alt", "Shift"].iter().any(|p| rest.starts_with(p)) {
+            break;
+        }
+    }
+
+    let word_len = rest
+        .find(|c: char| c.is_whitespace() || c == ',')
+        .unwrap_or(rest.len());
+    let word = &rest[..word_len];
+    let (code, len) = match Named::from_name(word) {
+        Some(named) if word.len() > 1 => (Code::Named(named), word_len),
+        _ => {
+            let c = rest.chars().next()?;
+            (Code::Char(c), c.len_utf8())
+        }
+    };
+    // `Ctrl + arrow`: a description of a key, not a key.
+    if len == 1 && rest[len..].starts_with(char::is_alphanumeric) {
+        return None;
+    }
+    key.code = code;
+    Some((key, s.len() - rest.len() + len))
+}
+
+/// The inside of a `<...>` Vim key code, e.g. `C-w`, `Esc`, `leader`.
+fn vim_key(inner: &str) -> Option<Key> {
+    let mut key = Key::plain(Code::Char(' '));
+    let mut rest = inner;
+    while rest.len() > 2 && rest.as_bytes()[1] == b'-' {
+        match rest.as_bytes()[0].to_ascii_uppercase() {
+            b'C' => key.ctrl = true,
+            b'M' | b'A' => key.alt = true,
+            b'S' => key.shift = true,
+            _ => return None,
+        }
+        rest = &rest[2..];
+    }
+    let mut chars = rest.chars();
+    key.code = match (chars.next(), chars.next()) {
+        (Some(c), None) if key.has_modifiers() => Code::Char(c),
+        _ => Code::Named(Named::from_name(rest)?),
+    };
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(text: &str) -> Vec<Key> {
+        parse_typed(text)
+    }
+
+    #[test]
+    fn readable_commands_render_back_unchanged() {
+        for command in [
+            "yiw / yaw",
+            "Ctrl + w",
+            "Ctrl + u / Ctrl + d",
+            "f{char} / F{char}",
+            ":e {file}",
+            "{n}G",
+            "q{reg} ... q",
+            "Shift + Tab",
+            "Esc",
+        ] {
+            assert_eq!(parse(command).render(Notation::Readable), command);
+        }
+    }
+
+    #[test]
+    fn vim_commands_render_back_unchanged() {
+        for command in ["<C-w>v", "<leader>ff", "<Esc>", "<S-Tab>", "<M-j>", "gg / G"] {
+            assert_eq!(parse(command).render(Notation::Vim), command);
+        }
+    }
+
+    #[test]
+    fn notations_convert() {
+        assert_eq!(parse("<C-w>v").render(Notation::Readable), "Ctrl + w v");
+        assert_eq!(parse("<CR>").render(Notation::Readable), "Enter");
+        assert_eq!(parse("Ctrl + r").render(Notation::Vim), "<C-r>");
+        assert_eq!(parse("Backspace").render(Notation::Vim), "<BS>");
+    }
+
+    #[test]
+    fn vim_and_readable_chords_are_the_same_keys() {
+        let vim = parse("<C-w>v");
+        let readable = parse("Ctrl + w v");
+        assert_eq!(vim.render(Notation::Readable), readable.render(Notation::Readable));
+        let keys = typed("<C-w>v");
+        assert_eq!(vim.match_typed(&keys), Some(Typed::Complete(2)));
+        assert_eq!(readable.match_typed(&keys), Some(Typed::Complete(2)));
+        assert_eq!(parse_key("<C-n>"), parse_key("Ctrl + n"));
+    }
+
+    #[test]
+    fn ctrl_chords_ignore_case() {
+        let ctrl_w = Key::from_event(KeyCode::Char('W'), KeyModifiers::CONTROL).unwrap();
+        assert!(ctrl_w.matches(&parse_key("Ctrl + w").unwrap()));
+        assert!(!typed("W")[0].matches(&parse_key("w").unwrap()));
+    }
+
+    #[test]
+    fn counts_take_any_number_of_digits() {
+        for command in ["{n}G", "{count}G"] {
+            let keys = parse(command);
+            assert_eq!(keys.match_typed(&typed("12G")), Some(Typed::Complete(3)));
+            assert_eq!(keys.match_typed(&typed("5G")), Some(Typed::Complete(2)));
+            assert_eq!(keys.match_typed(&typed("12")), Some(Typed::Prefix));
+            assert_eq!(keys.match_typed(&typed("G")), None);
+        }
+    }
+
+    #[test]
+    fn motions_take_the_rest() {
+        let keys = parse("d{motion}");
+        assert_eq!(keys.match_typed(&typed("dw")), Some(Typed::Complete(2)));
+        assert_eq!(keys.match_typed(&typed("d2w")), Some(Typed::Complete(3)));
+        assert_eq!(keys.match_typed(&typed("d")), Some(Typed::Prefix));
+        assert_eq!(keys.match_typed(&typed("yw")), None);
+    }
+
+    #[test]
+    fn other_placeholders_take_one_key() {
+        let keys = parse("f{char} / F{char}");
+        assert_eq!(keys.match_typed(&typed("fx")), Some(Typed::Complete(2)));
+        assert_eq!(keys.match_typed(&typed("Fxy")), Some(Typed::Complete(2)));
+        assert_eq!(keys.match_typed(&typed("t")), None);
+    }
+
+    #[test]
+    fn gaps_match_typed_spaces_or_nothing() {
+        let keys = parse(":e {file}");
+        assert_eq!(keys.match_typed(&typed(":e x")), Some(Typed::Complete(4)));
+        assert_eq!(keys.match_typed(&typed(":e")), Some(Typed::Prefix));
+    }
+}
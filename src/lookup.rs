@@ -0,0 +1,104 @@
+//! `vimbo lookup <keys>`: what a literal key sequence does, printed like
+//! `vimbo list`. Without an entry for the exact keys, they are explained piece
+//! by piece (`d2w` as `d{motion}`, a count and `w`), as in the TUI's
+//! reverse lookup.
+
+use std::io::{self, Write};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{
+    keys::{Key, Notation},
+    output::{self, Paint},
+    CheatEntry,
+};
+
+/// Entries explaining some typed keys, by index into the cheats.
+#[derive(Debug, Default)]
+pub struct Explained {
+    /// Commands made of exactly the typed keys.
+    pub complete: Vec<usize>,
+    /// Without those, the typed keys split into successive commands: how
+    /// many keys each piece is, and the command it is, if any.
+    pub pieces: Vec<(usize, Option<usize>)>,
+    /// Commands the typed keys are the start of.
+    pub prefix: Vec<usize>,
+}
+
+/// Print how `explained` accounts for `typed`; fails when nothing does.
+pub fn print(
+    cheats: &[CheatEntry],
+    typed: &[Key],
+    explained: &Explained,
+    lang: Option<&str>,
+    paint: Paint,
+) -> Result<()> {
+    let entries =
+        |indices: &[usize]| -> Vec<&CheatEntry> { indices.iter().map(|&i| &cheats[i]).collect() };
+    let keys = render(typed);
+    let text = if !explained.complete.is_empty() {
+        output::text(&entries(&explained.complete), lang, paint)
+    } else if explained.pieces.iter().any(|(_, i)| i.is_some()) {
+        pieces(cheats, typed, &explained.pieces, lang, paint)
+    } else if !explained.prefix.is_empty() {
+        format!(
+            "No command is exactly {keys}; these start with it:\n{}",
+            output::text(&entries(&explained.prefix), lang, paint)
+        )
+    } else {
+        bail!("no command matches '{keys}'");
+    };
+    match io::stdout().lock().write_all(text.as_bytes()) {
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result.context("failed to write to stdout"),
+    }
+}
+
+/// The pieces of `typed` as aligned lines: the keys, then the command and
+/// description they stand for.
+fn pieces(
+    cheats: &[CheatEntry],
+    typed: &[Key],
+    pieces: &[(usize, Option<usize>)],
+    lang: Option<&str>,
+    paint: Paint,
+) -> String {
+    let mut rows = Vec::new();
+    let mut pos = 0;
+    for &(n, i) in pieces {
+        let keys = render(&typed[pos..pos + n]);
+        pos += n;
+        rows.push(match i {
+            Some(i) => (
+                keys,
+                cheats[i].command.as_str(),
+                cheats[i].description_in(lang),
+            ),
+            None if keys.chars().all(|c| c.is_ascii_digit()) => (keys, "", "count"),
+            None => (keys, "", "unknown"),
+        });
+    }
+    let keys_width = rows.iter().map(|(keys, ..)| keys.chars().count()).max();
+    let command_width = rows
+        .iter()
+        .map(|(_, command, _)| command.chars().count())
+        .max();
+    let (keys_width, command_width) = (keys_width.unwrap_or(0), command_width.unwrap_or(0));
+    let mut out = format!("{}, piece by piece:\n", render(typed));
+    for (keys, command, description) in rows {
+        let line = format!(
+            "  {}  {}  {}",
+            paint.paint(&format!("{keys:<keys_width$}"), |t| t.title),
+            paint.paint(&format!("{command:<command_width$}"), |t| t.command),
+            paint.paint(description, |t| t.description)
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// `keys` in Vim's notation, as they would be typed (`d2w`, `<C-w>v`).
+fn render(keys: &[Key]) -> String {
+    keys.iter().map(|key| key.render(Notation::Vim)).collect()
+}
@@ -1,670 +1,2678 @@
-use std::io;
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::{self, IsTerminal, Write},
+    path::PathBuf,
+    process,
+    time::{Duration, Instant},
+};
 
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{bail, Context, Result};
+use cheat::{CheatEntry, Difficulty, Mode};
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap_complete::{ArgValueCandidates, CompleteEnv};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    cursor::{Hide, Show},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use log::debug;
+use editor::Editor;
+use keymap::Action;
+use keys::Typed;
+use log::{debug, warn};
+use query::{Field, Query};
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    layout::{Position, Rect},
     Terminal,
 };
 
+mod cheat;
+mod clipboard;
+mod complete;
+mod config;
+mod data;
+mod editor;
+mod export;
+mod favorites;
+mod form;
+mod hidden;
+mod history;
+mod import;
+mod index;
+mod keymap;
+mod keys;
+mod lookup;
+mod matcher;
+mod merge;
+mod natural;
+mod notes;
+mod output;
+mod packs;
+mod palette;
+mod panes;
+mod paths;
+mod query;
+mod quiz;
+mod recent;
+mod registry;
+mod session;
+mod shell;
+mod status;
+mod store;
+mod synonyms;
+mod theme;
+mod tip;
+mod toast;
+mod ui;
+mod undo;
+mod usage;
+mod watch;
+
 /// Simple Vim cheatsheet and search helper.
 #[derive(Parser, Debug)]
 #[command(name = "vimbo", version, about = "Terminal Vim cheatsheet and helper")]
 struct Cli {
+    /// What to do; the TUI when left out
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Optional initial search query (e.g. 'copy', 'paste', 'delete')
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     query: Option<String>,
+
+    /// `-` reads the query from stdin instead (one per line for `list`)
+    #[arg(value_name = "-", value_parser = ["-"], conflicts_with = "query")]
+    stdin: Option<String>,
+
+    /// Same as `list` (kept for scripts written before it)
+    #[arg(long, hide = true)]
+    list: bool,
+
+    /// Same as `list --format` (kept for scripts written before it)
+    #[arg(long, value_enum, hide = true)]
+    format: Option<output::Format>,
+
+    /// Same as `list --tip` (kept for scripts written before it)
+    #[arg(long, hide = true, conflicts_with_all = ["list", "format"])]
+    tip: bool,
+
+    /// Same as `list --fresh` (kept for scripts written before it)
+    #[arg(long, hide = true, requires = "tip")]
+    fresh: bool,
+
+    /// Import extra cheats from Markdown tables in FILE (repeatable)
+    #[arg(short, long = "markdown", value_name = "FILE", global = true)]
+    markdown: Vec<PathBuf>,
+
+    /// Enable bundled packs (e.g. 'surround,fugitive,tmux'); overrides the config file
+    #[arg(short, long, value_delimiter = ',', value_name = "PACKS", global = true,
+          add = ArgValueCandidates::new(shell::bundled_packs))]
+    packs: Vec<String>,
+
+    /// Add mappings from your vimrc / init.vim as "My mappings" (optionally from PATH)
+    #[arg(long, value_name = "PATH", num_args = 0..=1, global = true)]
+    vimrc: Option<Option<PathBuf>>,
+
+    /// Add the live mappings of a running Neovim (socket path or host:port; defaults to $NVIM)
+    #[arg(long, value_name = "ADDRESS", num_args = 0..=1, global = true)]
+    live: Option<Option<String>>,
+
+    /// Editor profile whose cheats are shown [default: vim, or `editor` in the config file]
+    #[arg(short, long, value_enum, global = true)]
+    editor: Option<Editor>,
+
+    /// Only show entries of this difficulty (e.g. 'beginner' for teaching)
+    #[arg(short, long, value_enum, global = true)]
+    level: Option<Difficulty>,
+
+    /// Only show this category (fuzzy matched, e.g. 'reg' for 'Registers')
+    #[arg(short, long, value_name = "NAME", global = true,
+          add = ArgValueCandidates::new(shell::categories))]
+    category: Option<String>,
+
+    /// Show descriptions in this language where packs provide one (e.g. 'de', 'es', 'ja')
+    #[arg(long, value_name = "LANG", global = true)]
+    lang: Option<String>,
+
+    /// Read the config from FILE instead of the config directory
+    #[arg(long, value_name = "FILE", env = "VIMBO_CONFIG", global = true)]
+    config: Option<PathBuf>,
+
+    /// Keep packs, favorites and other state in DIR instead of the data directory
+    #[arg(long, value_name = "DIR", env = "VIMBO_DATA_DIR", global = true)]
+    data_dir: Option<PathBuf>,
+
+    /// Use the config, packs, favorites and other data of the named profile (e.g. 'work')
+    #[arg(long, value_name = "NAME", env = "VIMBO_PROFILE", global = true)]
+    profile: Option<String>,
+
+    /// Color the output of `list` and `lookup` in the theme's colors
+    #[arg(long, value_enum, value_name = "WHEN", default_value_t, global = true)]
+    color: output::ColorChoice,
+
+    /// Options of the TUI, also taken without `tui` since it is the default
+    #[command(flatten)]
+    tui: TuiArgs,
+}
+
+/// Options that only matter to the TUI.
+#[derive(Args, Debug, Default)]
+struct TuiArgs {
+    /// Color theme preset (use 'light' on light terminal backgrounds)
+    #[arg(long, value_enum)]
+    theme: Option<theme::Preset>,
+
+    /// No colors, borders or highlighting; selections are marked with '>' (for screen readers)
+    #[arg(long)]
+    plain: bool,
+
+    /// Print the command of the entry picked with Enter and exit (Esc exits 1), e.g. in `$(...)`
+    #[arg(long)]
+    pick: bool,
+
+    /// Start where the last session left off: its query, category and selected entry
+    #[arg(long)]
+    resume: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Browse and search the cheatsheet in the terminal (the default)
+    Tui(TuiArgs),
+    /// Print the entries matching --query, --category and --level to stdout
+    List {
+        /// Aligned text, or records for other tools
+        #[arg(long, value_enum, default_value_t)]
+        format: output::Format,
+        /// Print one random matching entry instead (e.g. from a shell startup file)
+        #[arg(long, conflicts_with = "format")]
+        tip: bool,
+        /// With --tip, favor entries not yet starred, opened or copied
+        #[arg(long, requires = "tip")]
+        fresh: bool,
+    },
+    /// Write the entries matching --query, --category and --level to a file (e.g. `export html`)
+    Export {
+        format: export::Format,
+        /// File to write; stdout when left out
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+        /// Paper size of the PDF poster
+        #[arg(long, value_enum, default_value_t)]
+        page_size: export::pdf::PageSize,
+        /// Turn the PDF poster's pages sideways
+        #[arg(long)]
+        landscape: bool,
+        /// Columns per page of the PDF poster, or of the SVG and PNG one
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u8).range(1..=8))]
+        columns: u8,
+        /// Width in pixels of the SVG and PNG poster (e.g. your screen's, for a wallpaper)
+        #[arg(long, default_value_t = 1920, value_parser = clap::value_parser!(u32).range(320..=16384))]
+        width: u32,
+        /// Color theme of the SVG and PNG poster; the TUI's when left out
+        #[arg(long, value_enum)]
+        theme: Option<theme::Preset>,
+    },
+    /// Ask for the commands of random entries on stdin and print a score, without the TUI
+    Quiz {
+        /// Number of questions
+        #[arg(long, default_value_t = 10)]
+        count: usize,
+    },
+    /// Explain a literal key sequence (e.g. `lookup ciw`), piece by piece without an exact entry
+    Lookup {
+        /// Keys as typed in Vim, e.g. 'ciw', 'd2w' or '<C-w>v'
+        keys: String,
+    },
+    /// Show the entries you look up and copy most, and your top searches (see `usage` in the config)
+    Stats {
+        /// Entries and searches listed
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+    },
+    /// List, install, update and import cheat packs
+    Packs {
+        /// What to do; listing them when left out
+        #[command(subcommand)]
+        command: Option<PacksCommand>,
+    },
+    /// Show where the config file is, or print it
+    Config {
+        /// What to show; the path when left out
+        #[command(subcommand)]
+        command: Option<ConfigCommand>,
+    },
+    /// Print the completion script for a shell (e.g. `source <(vimbo completions bash)`)
+    Completions { shell: clap_complete::Shell },
+    // The pack commands as they were spelled before `packs`.
+    #[command(hide = true)]
+    Import(ImportArgs),
+    #[command(hide = true)]
+    Install(InstallArgs),
+    #[command(hide = true)]
+    Update(UpdateArgs),
+}
+
+#[derive(Subcommand, Debug)]
+enum PacksCommand {
+    /// List bundled, installed and local packs
+    List,
+    /// Install a cheat pack from a git repository (e.g. `packs install github:user/vim-cheats`)
+    Install(InstallArgs),
+    /// Pull upstream changes for installed packs and summarise what changed
+    Update(UpdateArgs),
+    /// Convert online cheat sheets into local packs (e.g. `packs import tldr vim nvim tmux`)
+    Import(ImportArgs),
+}
+
+#[derive(Args, Debug)]
+struct InstallArgs {
+    /// github:user/repo, gitlab:user/repo, codeberg:user/repo or any git URL
+    spec: String,
 }
 
-#[derive(Clone)]
-struct CheatEntry {
-    category: &'static str,
-    command: &'static str,
-    description: &'static str,
+#[derive(Args, Debug)]
+struct UpdateArgs {
+    /// Only update this pack
+    #[arg(add = ArgValueCandidates::new(shell::installed_packs))]
+    pack: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct ImportArgs {
+    source: import::Source,
+    /// Topics or pages to import (e.g. 'vim', 'tmux')
+    #[arg(required = true)]
+    topics: Vec<String>,
+    /// Convert a previously downloaded copy instead of fetching it
+    #[arg(long, value_name = "FILE")]
+    from: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Print where the config file is read from
+    Path,
+    /// Print where the config file, data and packs are kept
+    Dirs,
+    /// Print the config file
+    Show,
+}
+
+/// Which part of the UI the arrow keys act on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Focus {
+    /// The list, navigated like Vim's normal mode.
+    List,
+    /// The search bar: ↑/↓ recall earlier queries, Enter submits.
+    Search,
+    /// The category sidebar: ↑/↓ pick the category to show.
+    Sidebar,
+}
+
+/// A line of the results list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Row {
+    /// Heading of a category group, with its number of matching entries.
+    Group { category: String, count: usize },
+    /// An entry, by index into `cheats`.
+    Entry(usize),
+}
+
+/// Column the table view is sorted by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SortColumn {
+    Category,
+    Command,
+    Description,
+}
+
+impl SortColumn {
+    /// Cycle for the sort key, from relevance order through each column.
+    fn cycle(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(Self::Category),
+            Some(Self::Category) => Some(Self::Command),
+            Some(Self::Command) => Some(Self::Description),
+            Some(Self::Description) => None,
+        }
+    }
+}
+
+/// Order of the result list outside the table view.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum ListSort {
+    /// Dataset order, or relevance while searching.
+    #[default]
+    Pack,
+    Command,
+    Category,
+    /// Most used first.
+    Usage,
+    /// Last loaded first, so entries of your own packs come before the
+    /// built-in ones.
+    Added,
+}
+
+impl ListSort {
+    fn next(self) -> Self {
+        match self {
+            Self::Pack => Self::Command,
+            Self::Command => Self::Category,
+            Self::Category => Self::Usage,
+            Self::Usage => Self::Added,
+            Self::Added => Self::Pack,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Pack => "pack order",
+            Self::Command => "command",
+            Self::Category => "category",
+            Self::Usage => "most used",
+            Self::Added => "recently added",
+        }
+    }
+}
+
+/// Entry row the mouse pointer rests on, for its tooltip.
+#[derive(Clone, Copy, Debug)]
+struct Hover {
+    /// Index into `rows`.
+    row: usize,
+    /// Screen position of the pointer.
+    column: u16,
+    line: u16,
+    /// When the pointer came to the row.
+    since: Instant,
+}
+
+/// Filters of a tab, kept while another tab is shown.
+#[derive(Debug, Default)]
+struct TabState {
+    query: String,
+    selected: usize,
+    level: Option<Difficulty>,
+    mode: Option<Mode>,
+    category: Option<String>,
 }
 
 struct App {
+    editor: Editor,
+    /// Entries from bundled packs, kept to rebuild `cheats` on editor switch.
+    bundled: Vec<CheatEntry>,
+    /// Entries from user packs and imports; these take precedence over
+    /// built-in entries with the same command.
+    user: Vec<CheatEntry>,
     cheats: Vec<CheatEntry>,
+    /// Search fields of `cheats`, prepared when they are loaded.
+    index: index::Index,
+    /// Words the search bar completes, collected from `cheats`.
+    completions: complete::Completions,
+    conflicts: Vec<merge::Conflict>,
     filtered: Vec<usize>,
+    /// `filtered` laid out as list lines, grouped by category or not.
+    rows: Vec<Row>,
     query: String,
+    /// Index into `rows`.
     selected: usize,
     show_help: bool,
+    /// Focus mode: the selected entry alone, filling the screen.
+    zen: bool,
+    /// Lines the help overlay is scrolled down by.
+    help_scroll: usize,
+    show_conflicts: bool,
+    /// Show the usage statistics in place of the results.
+    show_stats: bool,
+    /// Selected row of the hidden entries screen, while it is shown in
+    /// place of the results.
+    hidden_screen: Option<usize>,
+    show_detail: bool,
+    /// Related link picked in the detail pane, if any.
+    related: Option<usize>,
+    /// Only show entries of this difficulty.
+    level: Option<Difficulty>,
+    /// Only show entries for this mode.
+    mode: Option<Mode>,
+    /// Only show entries in this category.
+    category: Option<String>,
+    /// Only show starred entries.
+    favorites_only: bool,
+    /// Show only recently opened or copied entries, newest first.
+    recent_only: bool,
+    /// List hidden entries too, to unhide them.
+    show_hidden: bool,
+    /// Group the list under category headings.
+    grouped: bool,
+    /// Show the results as a table with aligned columns.
+    table: bool,
+    /// Lines per entry in the list.
+    density: config::Density,
+    /// How category icons are drawn.
+    icon_style: config::Icons,
+    /// Row numbers in front of the results. While shown, digits type a
+    /// count instead of picking a category.
+    numbers: config::Numbers,
+    /// Category icons from packs, by category.
+    icons: BTreeMap<String, cheat::Icon>,
+    /// Table column the results are sorted by; relevance order when unset.
+    sort: Option<SortColumn>,
+    /// Order of the list view.
+    list_sort: ListSort,
+    /// Reverse the table's or the list's sort order.
+    sort_descending: bool,
+    /// Categories whose group is collapsed to its heading.
+    collapsed: BTreeSet<String>,
+    /// Tab shown: 0 for all entries, 1 for the editor profile's, then one
+    /// per pack (see [`App::tabs`]).
+    tab: usize,
+    /// Filters of each tab; the shown tab's slot is stale.
+    tab_states: Vec<TabState>,
+    /// Language for descriptions; English when unset or untranslated.
+    lang: Option<String>,
+    focus: Focus,
+    history: history::History,
+    /// How often each entry was opened, boosting it in search results.
+    usage: usage::Usage,
+    recent: recent::Recent,
+    hidden: hidden::Hidden,
+    /// Changes to favorites, hidden entries and packs, to undo.
+    undo: undo::Undo,
+    /// Sidebar and detail pane sizes.
+    panes: panes::Panes,
+    /// Entries starred with `s`.
+    favorites: favorites::Favorites,
+    /// Your notes on entries, searched with their descriptions.
+    notes: notes::Notes,
+    /// Entries marked for batch actions, by index into `cheats`.
+    marked: BTreeSet<usize>,
+    /// Extra words each search word also looks for.
+    synonyms: synonyms::Synonyms,
+    /// Keys typed in reverse lookup mode; `None` when searching by text.
+    lookup: Option<Vec<keys::Key>>,
+    theme: theme::Theme,
+    /// Style overrides from the config file, kept on top of whichever
+    /// preset is picked.
+    theme_overrides: BTreeMap<String, theme::StyleConfig>,
+    /// Plain mode: no colors, borders or highlighting, for screen readers
+    /// and dumb terminals.
+    plain: bool,
+    /// Picker mode: Enter picks the selected (or marked) entries and Esc
+    /// gives up, both quitting.
+    pick: bool,
+    /// Commands of the entries picked in picker mode.
+    picked: Option<Vec<String>>,
+    /// How long to wait for a key before redrawing anyway.
+    tick: Duration,
+    /// Template of the status bar.
+    status_template: String,
+    /// Keys of the list, sidebar and help overlay.
+    keymap: keymap::Keymap,
+    /// Notifications shown for a moment each.
+    toasts: toast::Toasts,
+    /// Changes to the local packs, reloaded as they happen.
+    watcher: Option<watch::PackWatcher>,
+    /// First key of a two-key list command (`gg`, `gt`, `za`, ...),
+    /// waiting for the second.
+    pending: Option<char>,
+    /// Count typed before a motion (`12G`), while row numbers are shown.
+    count: Option<usize>,
+    /// The command palette, while open.
+    palette: Option<palette::Palette>,
+    /// The entry form, while open.
+    form: Option<form::Form>,
+    /// The note being written on the selected entry, while editing it.
+    note: Option<String>,
+    /// Screen areas of the last frame, for mapping mouse clicks.
+    areas: Cell<ui::Areas>,
+    /// Entry whose demo the detail pane is playing, and when it started.
+    demo: Cell<Option<(usize, Instant)>>,
+    /// Entry row under the mouse pointer.
+    hover: Option<Hover>,
 }
 
 impl App {
-    fn new(initial_query: Option<String>) -> Self {
-        let cheats = default_cheats();
+    fn new(
+        initial_query: Option<String>,
+        editor: Editor,
+        bundled: Vec<CheatEntry>,
+        user: Vec<CheatEntry>,
+        lang: Option<String>,
+    ) -> Self {
+        // Start typing right away, unless a query was given to browse.
+        let focus = if initial_query.is_some() {
+            Focus::List
+        } else {
+            Focus::Search
+        };
         let mut app = Self {
-            cheats,
+            editor,
+            bundled,
+            user,
+            cheats: Vec::new(),
+            index: Default::default(),
+            completions: Default::default(),
+            conflicts: Vec::new(),
             filtered: Vec::new(),
+            rows: Vec::new(),
             query: initial_query.unwrap_or_default(),
             selected: 0,
             show_help: false,
+            zen: false,
+            help_scroll: 0,
+            show_conflicts: false,
+            show_stats: false,
+            hidden_screen: None,
+            show_detail: false,
+            related: None,
+            level: None,
+            mode: None,
+            category: None,
+            favorites_only: false,
+            recent_only: false,
+            show_hidden: false,
+            grouped: false,
+            table: false,
+            density: config::Density::Compact,
+            icon_style: config::Icons::Glyph,
+            numbers: config::Numbers::Off,
+            icons: BTreeMap::new(),
+            sort: None,
+            list_sort: ListSort::Pack,
+            sort_descending: false,
+            collapsed: BTreeSet::new(),
+            tab: 0,
+            tab_states: Vec::new(),
+            lang,
+            focus,
+            history: history::History::in_memory(),
+            usage: usage::Usage::in_memory(),
+            recent: recent::Recent::in_memory(),
+            hidden: hidden::Hidden::in_memory(),
+            undo: Default::default(),
+            panes: panes::Panes::in_memory(),
+            favorites: favorites::Favorites::in_memory(),
+            notes: notes::Notes::in_memory(),
+            marked: BTreeSet::new(),
+            synonyms: Default::default(),
+            lookup: None,
+            theme: Default::default(),
+            theme_overrides: BTreeMap::new(),
+            plain: false,
+            pick: false,
+            picked: None,
+            tick: TICK,
+            status_template: status::DEFAULT_TEMPLATE.to_string(),
+            keymap: keymap::Keymap::default(),
+            toasts: Default::default(),
+            watcher: None,
+            pending: None,
+            count: None,
+            palette: None,
+            form: None,
+            note: None,
+            areas: Cell::default(),
+            demo: Cell::new(None),
+            hover: None,
         };
-        app.apply_filter();
+        app.load_cheats();
         app
     }
 
-    fn apply_filter(&mut self) {
-        let q = self.query.to_lowercase();
-        if q.is_empty() {
-            self.filtered = (0..self.cheats.len()).collect();
-        } else {
-            self.filtered = self
-                .cheats
+    fn load_cheats(&mut self) {
+        let mut builtin = self.editor.cheats();
+        builtin.extend(self.bundled.iter().cloned());
+        (self.cheats, self.conflicts) = merge::merge(builtin, &self.user);
+        self.index = index::Index::build(&self.cheats, self.lang.as_deref(), &self.notes);
+        self.completions = complete::Completions::build(&self.cheats);
+        self.marked.clear();
+        self.icons.clear();
+        for c in &self.cheats {
+            if let Some(icon) = &c.icon {
+                self.icons
+                    .entry(c.category.clone())
+                    .or_insert_with(|| icon.clone());
+            }
+        }
+        // Reloads and editor switches can drop the pack of the shown tab.
+        if self.tab >= self.tabs().len() {
+            self.tab = 0;
+        }
+        debug!("{} user entries override built-ins", self.conflicts.len());
+        self.favorites.migrate(&self.cheats);
+        self.apply_filter();
+    }
+
+    /// Pick up where `session` left off: its query, its category if that
+    /// still exists, its selected entry if that still matches, and its
+    /// scroll position.
+    fn restore_session(&mut self, session: session::Session) {
+        if !session.query.is_empty() {
+            self.focus = Focus::List;
+        }
+        self.query = session.query;
+        self.category = session
+            .category
+            .filter(|name| self.cheats.iter().any(|c| c.category == *name));
+        self.apply_filter();
+        if let Some(idx) = session.selected.and_then(|(category, command)| {
+            self.cheats
                 .iter()
-                .enumerate()
-                .filter_map(|(i, c)| {
-                    let haystack = format!(
-                        "{} {} {}",
-                        c.category.to_lowercase(),
-                        c.command.to_lowercase(),
-                        c.description.to_lowercase()
-                    );
-                    haystack.contains(&q).then_some(i)
-                })
+                .position(|c| c.category == category && c.command == command)
+        }) {
+            self.select_row(&Row::Entry(idx));
+        }
+        // The list keeps the selection in view from this offset on.
+        let mut areas = self.areas.get();
+        areas.list_offset = session.offset;
+        self.areas.set(areas);
+    }
+
+    /// Load the local packs again after they changed on disk, e.g. edited
+    /// in another window, keeping the selected entry selected.
+    fn reload_packs(&mut self) {
+        let fresh = match packs::load_local() {
+            Ok(fresh) => fresh,
+            Err(err) => {
+                self.toasts.push(format!("{err:#}"));
+                return;
+            }
+        };
+        // Saving from the form writes the packs too, but changes nothing
+        // the entries do not have already.
+        let fingerprint = |entries: Vec<&CheatEntry>| {
+            let mut keys: Vec<_> = entries
+                .into_iter()
+                .map(|e| (e.file.clone(), serde_json::to_string(e).ok()))
                 .collect();
+            keys.sort();
+            keys
+        };
+        let loaded = self.user.iter().filter(|e| e.file.is_some()).collect();
+        if fingerprint(loaded) == fingerprint(fresh.iter().collect()) {
+            return;
         }
-        debug!("filter updated; query='{}', shown={}", self.query, self.filtered.len());
-        if self.selected >= self.filtered.len() {
-            self.selected = self.filtered.len().saturating_sub(1);
+        debug!("local packs changed, reloading {} entries", fresh.len());
+        let key = |c: &CheatEntry| (c.category.clone(), c.command.clone());
+        let selected = self.selected_entry().map(key);
+        let marked: Vec<_> = self.marked.iter().map(|&i| key(&self.cheats[i])).collect();
+        // Local packs come first, as at startup.
+        self.user.retain(|e| e.file.is_none());
+        self.user.splice(0..0, fresh);
+        self.load_cheats();
+        let position = |(category, command): (String, String)| {
+            self.cheats
+                .iter()
+                .position(|c| c.category == category && c.command == command)
+        };
+        self.marked = marked.into_iter().filter_map(position).collect();
+        if let Some(idx) = selected.and_then(position) {
+            self.select_row(&Row::Entry(idx));
         }
+        self.toasts.push("Packs reloaded");
     }
-}
 
-fn default_cheats() -> Vec<CheatEntry> {
-    vec![
-        // Basics
-        CheatEntry {
-            category: "Basics",
-            command: ":q",
-            description: "quit (fails if there are unsaved changes)",
-        },
-        CheatEntry {
-            category: "Basics",
-            command: ":q!",
-            description: "quit discarding changes",
-        },
-        CheatEntry {
-            category: "Basics",
-            command: ":w",
-            description: "write (save) current buffer",
-        },
-        CheatEntry {
-            category: "Basics",
-            command: ":wq / :x / ZZ",
-            description: "save and quit",
-        },
-        CheatEntry {
-            category: "Basics",
-            command: ":e {file}",
-            description: "edit / open file",
-        },
-        CheatEntry {
-            category: "Basics",
-            command: ":help {topic}",
-            description: "open Vim help (e.g. :help motion)",
-        },
+    fn switch_editor(&mut self, editor: Editor) {
+        debug!("switching editor profile to {}", editor.label());
+        self.editor = editor;
+        self.selected = 0;
+        self.load_cheats();
+    }
 
-        CheatEntry {
-            category: "Modes",
-            command: "i",
-            description: "enter insert mode before cursor",
-        },
-        CheatEntry {
-            category: "Modes",
-            command: "a",
-            description: "enter insert mode after cursor",
-        },
-        CheatEntry {
-            category: "Modes",
-            command: "v",
-            description: "enter visual mode",
-        },
-        CheatEntry {
-            category: "Modes",
-            command: "V",
-            description: "enter visual line mode",
-        },
-        CheatEntry {
-            category: "Modes",
-            command: "Ctrl + v",
-            description: "enter visual block (blockwise) mode",
-        },
-        CheatEntry {
-            category: "Modes",
-            command: "Esc",
-            description: "return to normal mode",
-        },
+    /// Index into `cheats` of the selected entry, unless a group heading
+    /// is selected.
+    fn selected_index(&self) -> Option<usize> {
+        match self.rows.get(self.selected)? {
+            Row::Entry(idx) => Some(*idx),
+            Row::Group { .. } => None,
+        }
+    }
 
-        CheatEntry {
-            category: "Navigation - line",
-            command: "h j k l",
-            description: "move cursor left / down / up / right",
-        },
-        CheatEntry {
-            category: "Navigation - line",
-            command: "0 / $",
-            description: "move cursor to start / end of line",
-        },
-        CheatEntry {
-            category: "Navigation - line",
-            command: "^",
-            description: "move cursor to first non-blank in line",
-        },
-        CheatEntry {
-            category: "Navigation - scrolling",
-            command: "Ctrl + u / Ctrl + d",
-            description: "move view half-page up / down",
-        },
-        CheatEntry {
-            category: "Navigation - scrolling",
-            command: "Ctrl + b / Ctrl + f",
-            description: "move view page up / down",
-        },
-        CheatEntry {
-            category: "Navigation - file",
-            command: "gg / G",
-            description: "move cursor to first / last line of file",
-        },
-        CheatEntry {
-            category: "Navigation - file",
-            command: "{n}G",
-            description: "move cursor to line {n}",
-        },
-        CheatEntry {
-            category: "Navigation - screen",
-            command: "H / M / L",
-            description: "move cursor to top / middle / bottom of screen",
-        },
-        CheatEntry {
-            category: "Navigation - screen",
-            command: "zz / zt / zb",
-            description: "move view to center / top / bottom current line",
-        },
-        CheatEntry {
-            category: "Navigation - paragraphs",
-            command: "{ / }",
-            description: "move cursor to previous / next paragraph or block",
-        },
-        CheatEntry {
-            category: "Navigation - sentences",
-            command: "( / )",
-            description: "move cursor to previous / next sentence",
-        },
-        CheatEntry {
-            category: "Navigation - matching",
-            command: "%",
-            description: "move cursor to matching bracket/brace/paren",
-        },
-        CheatEntry {
-            category: "Navigation - word",
-            command: "w / b / e",
-            description: "move cursor to next / previous / end of word",
-        },
-        CheatEntry {
-            category: "Navigation - word",
-            command: "W / B / E",
-            description: "move cursor WORD-wise next / previous / end",
-        },
-        CheatEntry {
-            category: "Navigation - find",
-            command: "f{char} / F{char}",
-            description: "move cursor to char right / left",
-        },
-        CheatEntry {
-            category: "Navigation - find",
-            command: "t{char} / T{char}",
-            description: "move cursor till before char right / left",
-        },
-        CheatEntry {
-            category: "Navigation - find",
-            command: "; / ,",
-            description: "move cursor by repeating / reversing last f/F/t/T",
-        },
+    fn selected_entry(&self) -> Option<&CheatEntry> {
+        self.selected_index().map(|idx| &self.cheats[idx])
+    }
 
-        CheatEntry {
-            category: "Editing",
-            command: "x",
-            description: "delete character under cursor",
-        },
-        CheatEntry {
-            category: "Editing",
-            command: "dd",
-            description: "delete (cut) current line",
-        },
-        CheatEntry {
-            category: "Editing",
-            command: "D",
-            description: "delete from cursor to end of line",
-        },
-        CheatEntry {
-            category: "Editing",
-            command: "cc",
-            description: "change (replace) entire line",
-        },
-        CheatEntry {
-            category: "Editing",
-            command: "cw / c$",
-            description: "change to end of word / line",
-        },
-        CheatEntry {
-            category: "Editing",
-            command: "r{char}",
-            description: "replace a single character",
-        },
-        CheatEntry {
-            category: "Editing",
-            command: "J",
-            description: "join current line with next",
-        },
-        CheatEntry {
-            category: "Yank (copy)",
-            command: "y{motion}",
-            description: "yank text covered by a motion (e.g. yw, y$)",
-        },
-        CheatEntry {
-            category: "Yank (copy)",
-            command: "yy / Y",
-            description: "yank (copy) current line",
-        },
-        CheatEntry {
-            category: "Yank (copy)",
-            command: "yiw / yaw",
-            description: "yank inner word / a word incl. space",
-        },
-        CheatEntry {
-            category: "Yank (copy)",
-            command: "y0 / y$",
-            description: "yank from cursor to start / end of line",
-        },
-        CheatEntry {
-            category: "Paste",
-            command: "p / P",
-            description: "paste after / before cursor or line",
-        },
-        CheatEntry {
-            category: "Paste",
-            command: "gp / gP",
-            description: "paste and move cursor to end of paste",
-        },
-        CheatEntry {
-            category: "Indentation",
-            command: ">> / <<",
-            description: "indent / dedent current line",
-        },
-        CheatEntry {
-            category: "Indentation",
-            command: "=",
-            description: "auto-indent motion or selection",
-        },
+    /// Icon to draw before `category`, in the configured style.
+    fn icon(&self, category: &str) -> Option<&str> {
+        let icon = self.icons.get(category)?;
+        match self.icon_style {
+            config::Icons::Glyph => Some(&icon.glyph),
+            config::Icons::Ascii => icon.ascii.as_deref(),
+            config::Icons::Off => None,
+        }
+    }
 
-        CheatEntry {
-            category: "Visual mode",
-            command: "v / V / Ctrl + v + motion",
-            description: "select characters / lines / block",
-        },
-        CheatEntry {
-            category: "Visual mode",
-            command: "y / d / c",
-            description: "yank / delete / change selection",
-        },
-        CheatEntry {
-            category: "Visual mode",
-            command: "> / <",
-            description: "indent / dedent selection",
-        },
+    /// Completions offered for the word being typed in the search bar;
+    /// none unless it is focused, so Tab elsewhere cycles the mode filter.
+    fn suggestions(&self) -> Vec<String> {
+        if self.lookup.is_some() || self.focus != Focus::Search {
+            return Vec::new();
+        }
+        self.completions.suggest(&self.query)
+    }
 
-        CheatEntry {
-            category: "Search",
-            command: "/pattern",
-            description: "search forward for pattern",
-        },
-        CheatEntry {
-            category: "Search",
-            command: "n / N",
-            description: "next / previous search match",
-        },
-        CheatEntry {
-            category: "Search",
-            command: "?pattern",
-            description: "search backward for pattern",
-        },
-        CheatEntry {
-            category: "Search & replace",
-            command: ":%s/old/new/g",
-            description: "replace all 'old' with 'new' in file",
-        },
-        CheatEntry {
-            category: "Search & replace",
-            command: ":%s/old/new/gc",
-            description: "replace with confirmation",
-        },
+    /// Replace the word being typed with `suggestion`, ready for the next one.
+    fn accept_suggestion(&mut self, suggestion: &str) {
+        let keep = self.query.trim_end_matches(|c: char| !c.is_whitespace()).len();
+        self.query.truncate(keep);
+        self.query.push_str(suggestion);
+        self.query.push(' ');
+        self.history.reset();
+        self.apply_filter();
+    }
 
-        CheatEntry {
-            category: "Buffers",
-            command: ":w / :q / :wq",
-            description: "write, quit, write & quit",
-        },
-        CheatEntry {
-            category: "Buffers",
-            command: ":ls / :buffers",
-            description: "list buffers",
-        },
-        CheatEntry {
-            category: "Buffers",
-            command: ":b {n}",
-            description: "go to buffer {n}",
-        },
-        CheatEntry {
-            category: "Buffers",
-            command: ":bn / :bp",
-            description: "next / previous buffer",
-        },
+    /// Switch to the next theme preset, keeping the config file's overrides.
+    fn next_theme(&mut self) {
+        if self.plain {
+            self.toasts.push("Themes are off in plain mode");
+            return;
+        }
+        let preset = self.theme.preset.next();
+        match theme::Theme::new(preset, &self.theme_overrides) {
+            Ok(theme) => {
+                self.theme = theme;
+                self.toasts.push(format!("Theme: {}", preset.label()));
+            }
+            Err(err) => self.toasts.push(format!("{err:#}")),
+        }
+    }
 
-        CheatEntry {
-            category: "Windows",
-            command: ":split / :vsplit",
-            description: "horizontal / vertical split",
-        },
-        CheatEntry {
-            category: "Windows",
-            command: "Ctrl + w, then h/j/k/l",
-            description: "move to window left/down/up/right",
-        },
-        CheatEntry {
-            category: "Windows",
-            command: "Ctrl + w, then c / o",
-            description: "close current / keep only current",
-        },
+    /// Count a use of the selected entry for ranking.
+    fn record_use(&mut self) {
+        if let Some(idx) = self.selected_index() {
+            self.usage.record(&self.cheats[idx]);
+            self.recent.record(&self.cheats[idx]);
+        }
+    }
 
-        CheatEntry {
-            category: "Tabs",
-            command: ":tabnew {file}",
-            description: "open file in a new tab",
-        },
-        CheatEntry {
-            category: "Tabs",
-            command: "gt / gT",
-            description: "next / previous tab",
-        },
-        CheatEntry {
-            category: "Tabs",
-            command: ":tabclose",
-            description: "close current tab",
-        },
+    fn open_help(&mut self) {
+        self.show_help = true;
+        self.help_scroll = 0;
+    }
 
-        CheatEntry {
-            category: "Registers",
-            command: "\"{reg}y / \"{reg}p",
-            description: "yank / paste using register {reg}",
-        },
-        CheatEntry {
-            category: "Registers",
-            command: "\"+y / \"+p / \"*y",
-            description: "use system clipboards (+ or * register)",
-        },
+    /// Enter focus mode on the selected entry, or the first one below a
+    /// selected heading.
+    fn open_zen(&mut self) {
+        if self.selected_index().is_none() {
+            self.step_entry(true);
+        }
+        if self.selected_index().is_some() {
+            self.zen = true;
+        } else {
+            self.toasts.push("No entries to show");
+        }
+    }
 
-        CheatEntry {
-            category: "Marks",
-            command: "m{a-z}",
-            description: "set mark {a-z} on a line",
-        },
-        CheatEntry {
-            category: "Marks",
-            command: "'{a-z} / `{a-z}",
-            description: "jump to mark line / exact position",
-        },
+    /// Select the next (or previous) entry row, skipping headings.
+    fn step_entry(&mut self, forward: bool) {
+        let next = if forward {
+            (self.selected + 1..self.rows.len()).find(|&i| matches!(self.rows[i], Row::Entry(_)))
+        } else {
+            (0..self.selected).rev().find(|&i| matches!(self.rows[i], Row::Entry(_)))
+        };
+        if let Some(i) = next {
+            self.selected = i;
+        }
+    }
 
-        CheatEntry {
-            category: "Macros",
-            command: "q{reg} ... q",
-            description: "record macro into register {reg}",
-        },
-        CheatEntry {
-            category: "Macros",
-            command: "@{reg} / @@",
-            description: "play macro / repeat last macro",
-        },
+    /// Star or unstar the selected entry.
+    fn toggle_favorite(&mut self) {
+        let before = undo::Snapshot::Favorites(self.favorites.clone());
+        if !self.marked.is_empty() {
+            let marked: Vec<&CheatEntry> = self.marked.iter().map(|&i| &self.cheats[i]).collect();
+            self.favorites.star(&marked);
+            self.undo.record(format!("star {} entries", marked.len()), before);
+            self.toasts.push(format!("Added {} marked entries to favorites", marked.len()));
+            if self.favorites_only {
+                self.apply_filter();
+            }
+        } else if let Some(idx) = self.selected_index() {
+            let starred = self.favorites.toggle(&self.cheats[idx]);
+            let verb = if starred { "star" } else { "unstar" };
+            self.undo.record(format!("{verb} {}", self.cheats[idx].command), before);
+            self.toasts.push(if starred {
+                "Added to favorites"
+            } else {
+                "Removed from favorites"
+            });
+            if self.favorites_only {
+                self.apply_filter();
+            }
+        }
+    }
 
-        CheatEntry {
-            category: "Repeat",
-            command: ".",
-            description: "repeat last change",
-        },
-        CheatEntry {
-            category: "Undo/Redo",
-            command: "u / Ctrl + r",
-            description: "undo / redo last change",
-        },
-    ]
-}
+    /// Mark the selected entry for batch actions, or unmark it, and move
+    /// on to the next row.
+    fn toggle_mark(&mut self) {
+        if let Some(idx) = self.selected_index() {
+            if !self.marked.remove(&idx) {
+                self.marked.insert(idx);
+            }
+            self.selected = (self.selected + 1).min(self.rows.len() - 1);
+            self.toasts.push(format!("{} marked", self.marked.len()));
+        }
+    }
 
-fn main() -> Result<()> {
-    env_logger::init();
-    debug!("starting vimbo");
-    let cli = Cli::parse();
-    let mut app = App::new(cli.query);
+    /// Start writing a note on the selected entry, from its current one.
+    fn open_note(&mut self) {
+        if let Some(entry) = self.selected_entry() {
+            self.note = Some(self.notes.get(entry).unwrap_or_default().to_string());
+        }
+    }
 
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    /// Save the note being written on the selected entry; a blank one
+    /// removes it.
+    fn save_note(&mut self) {
+        let (Some(note), Some(idx)) = (self.note.take(), self.selected_index()) else {
+            return;
+        };
+        let entry = &self.cheats[idx];
+        if self.notes.get(entry).unwrap_or_default() == note.trim() {
+            return;
+        }
+        let before = undo::Snapshot::Notes(self.notes.clone());
+        self.notes.set(entry, &note);
+        let removed = self.notes.get(entry).is_none();
+        let verb = if removed { "remove note on" } else { "note on" };
+        self.undo.record(format!("{verb} {}", entry.command), before);
+        self.toasts.push(if removed { "Note removed" } else { "Note saved" });
+        self.index.set_note(idx, self.notes.get(&self.cheats[idx]).unwrap_or_default());
+        self.apply_filter();
+    }
 
-    let res = run_app(&mut terminal, &mut app);
+    /// Open the form for the selected entry.
+    fn open_form(&mut self) {
+        if let Some(entry) = self.selected_entry() {
+            self.form = Some(form::Form::edit(entry));
+        }
+    }
 
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
-    debug!("exiting vimbo");
+    /// Open the form for a new entry, in the category of the selected
+    /// entry or filter.
+    fn open_add_form(&mut self) {
+        let category = match (&self.category, self.selected_entry()) {
+            (Some(category), _) => category.clone(),
+            (None, Some(entry)) => entry.category.clone(),
+            (None, None) => String::new(),
+        };
+        let categories = self
+            .categories()
+            .into_iter()
+            .map(|(name, _)| name.to_string())
+            .collect();
+        self.form = Some(form::Form::add(&category, categories));
+    }
 
-    res
-}
+    /// Save the open form, and show the saved entry in place of the old
+    /// one. The form stays open if saving fails.
+    fn save_form(&mut self) {
+        let Some(form) = &self.form else {
+            return;
+        };
+        let path = match &form.target {
+            form::Target::Edit { path, .. } => Ok(path.clone()),
+            form::Target::Copy(_) | form::Target::New => packs::personal_path(),
+        };
+        let before = path.map(|path| undo::Snapshot::Pack {
+            text: fs::read_to_string(&path).ok(),
+            path,
+            user: self.user.clone(),
+        });
+        let entry = match form.save() {
+            Ok(entry) => entry,
+            Err(err) => {
+                self.toasts.push(format!("{err:#}"));
+                return;
+            }
+        };
+        let old = self.user.iter().position(|e| match &form.target {
+            form::Target::Edit {
+                path,
+                category,
+                command,
+            } => e.file.as_ref() == Some(path) && e.category == *category && e.command == *command,
+            form::Target::Copy(_) | form::Target::New => {
+                e.file == entry.file && merge::same_command(&e.command, &entry.command)
+            }
+        });
+        if let Some(path) = &entry.file {
+            self.toasts.push(format!("Saved to {}", path.display()));
+        }
+        let (category, command) = (entry.category.clone(), entry.command.clone());
+        if let Ok(before) = before {
+            let verb = if old.is_some() { "edit" } else { "add" };
+            self.undo.record(format!("{verb} {command}"), before);
+        }
+        match old {
+            Some(i) => self.user[i] = entry,
+            None => self.user.push(entry),
+        }
+        self.form = None;
+        self.load_cheats();
+        if let Some(idx) = self
+            .cheats
+            .iter()
+            .position(|c| c.category == category && c.command == command)
+        {
+            self.select_row(&Row::Entry(idx));
+        }
+    }
 
-fn run_app<B: ratatui::backend::Backend>(
-    terminal: &mut Terminal<B>,
-    app: &mut App,
-) -> Result<()> {
-    loop {
-        terminal.draw(|f| {
-            let size = f.area();
-            let constraints = if app.show_help {
-                vec![
-                    Constraint::Length(3), // search bar
-                    Constraint::Min(5),    // list
-                    Constraint::Length(5), // help pane
-                ]
-            } else {
-                vec![
-                    Constraint::Length(3), // search bar
-                    Constraint::Min(5),    // list
-                    Constraint::Length(1), // status
-                ]
-            };
-            let chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(constraints)
-                .split(size);
-
-            // Search input
-            let search = Paragraph::new(app.query.as_str())
-                .block(
-                    Block::default()
-                        .title(Span::styled(
-                            " Search (type to filter, Esc to quit) ",
-                            Style::default()
-                                .fg(Color::Yellow)
-                                .add_modifier(Modifier::BOLD),
-                        ))
-                        .borders(Borders::ALL),
-                )
-                .style(Style::default().fg(Color::Cyan));
-            f.render_widget(search, chunks[0]);
-
-            // Cheats list
-            let items: Vec<ListItem> = app
-                .filtered
-                .iter()
-                .map(|&idx| {
-                    let c = &app.cheats[idx];
-                    let line = Line::from(vec![
-                        Span::styled(
-                            format!("[{}] ", c.category),
-                            Style::default().fg(Color::Magenta),
-                        ),
-                        Span::styled(
-                            format!("{:<12}", c.command),
-                            Style::default()
-                                .fg(Color::Green)
-                                .add_modifier(Modifier::BOLD),
-                        ),
-                        Span::raw(" "),
-                        Span::styled(
-                            c.description,
-                            Style::default().fg(Color::White),
-                        ),
-                    ]);
-                    ListItem::new(line)
-                })
-                .collect();
+    /// Hide the marked entries, or else hide or unhide the selected one.
+    fn toggle_hidden(&mut self) {
+        let indices = self.batch_indices();
+        let Some(&first) = indices.first() else {
+            return;
+        };
+        let hide = !self.marked.is_empty() || !self.hidden.contains(&self.cheats[first]);
+        let entries: Vec<&CheatEntry> = indices.iter().map(|&i| &self.cheats[i]).collect();
+        let before = undo::Snapshot::Hidden(self.hidden.clone());
+        self.hidden.set(&entries, hide);
+        let label = match (hide, entries.len()) {
+            (true, 1) => format!("hide {}", entries[0].command),
+            (true, n) => format!("hide {n} entries"),
+            (false, _) => format!("unhide {}", entries[0].command),
+        };
+        let undo_keys = self.keymap.describe(Action::Undo);
+        self.toasts.push(match (hide, entries.len()) {
+            (true, 1) => format!("Hid {}  ({undo_keys} to undo)", entries[0].command),
+            (true, n) => format!("Hid {n} entries  ({undo_keys} to undo)"),
+            (false, _) => format!("Unhid {}", entries[0].command),
+        });
+        self.undo.record(label, before);
+        self.marked.clear();
+        self.apply_filter();
+    }
 
-            let cheats_block = List::new(items)
-                .block(
-                    Block::default()
-                        .title(Span::styled(
-                            " Vim Cheatsheet ",
-                            Style::default()
-                                .fg(Color::Cyan)
-                                .add_modifier(Modifier::BOLD),
-                        ))
-                        .borders(Borders::ALL),
-                )
-                .highlight_style(
-                    Style::default()
-                        .bg(Color::Blue)
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD),
-                )
-                .highlight_symbol(">> ");
-
-            f.render_stateful_widget(
-                cheats_block,
-                chunks[1],
-                &mut ratatui::widgets::ListState::default()
-                    .with_selected(if app.filtered.is_empty() {
-                        None
-                    } else {
-                        Some(app.selected)
-                    }),
-            );
+    /// Unhide the entry picked on the hidden entries screen.
+    fn unhide_picked(&mut self) {
+        let Some(row) = self.hidden_screen else {
+            return;
+        };
+        let Some((category, command)) = self
+            .hidden
+            .entries()
+            .get(row)
+            .map(|&(category, command)| (category.to_string(), command.to_string()))
+        else {
+            return;
+        };
+        let before = undo::Snapshot::Hidden(self.hidden.clone());
+        self.hidden.unhide(&category, &command);
+        self.undo.record(format!("unhide {command}"), before);
+        self.toasts.push(format!("Unhid {command}"));
+        self.clamp_hidden_screen();
+        self.apply_filter();
+    }
 
-            if app.show_help {
-                let help = Paragraph::new(
-                    "Keys: ↑/↓ move  •  PgUp/PgDn scroll  •  g/G top/bottom\n\
-                     Typing filters cheats  •  Backspace deletes  •  / clears query\n\
-                     ? toggle this help  •  Esc to quit",
-                )
-                .block(
-                    Block::default()
-                        .title(Span::styled(
-                            " Help ",
-                            Style::default()
-                                .fg(Color::Yellow)
-                                .add_modifier(Modifier::BOLD),
-                        ))
-                        .borders(Borders::ALL),
-                )
-                .style(Style::default().fg(Color::White));
-                f.render_widget(help, chunks[2]);
-            } else {
-                // Status bar
-                let status_text = format!(
-                    "Total: {}  Shown: {}  (? for help)",
-                    app.cheats.len(),
-                    app.filtered.len()
-                );
-                let status = Paragraph::new(status_text).style(
-                    Style::default()
-                        .fg(Color::DarkGray)
-                        .add_modifier(Modifier::ITALIC),
-                );
-                f.render_widget(status, chunks[2]);
-            }
-        })?;
-
-        if crossterm::event::poll(std::time::Duration::from_millis(200))? {
-            match event::read()? {
-                Event::Key(key) => {
-                    debug!("key: {:?}", key.code);
-                    match key.code {
-                        KeyCode::Esc => return Ok(()),
-                        KeyCode::Char('?') => {
-                            app.show_help = !app.show_help;
-                        }
-                        KeyCode::Up => {
-                            if app.selected > 0 {
-                                app.selected -= 1;
-                            }
-                        }
-                        KeyCode::Down => {
-                            if app.selected + 1 < app.filtered.len() {
-                                app.selected += 1;
-                            }
-                        }
-                        KeyCode::PageUp => {
-                            let step = 10;
-                            app.selected = app.selected.saturating_sub(step);
-                        }
-                        KeyCode::PageDown => {
-                            let step = 10;
-                            if app.selected + step < app.filtered.len() {
-                                app.selected += step;
-                            } else if !app.filtered.is_empty() {
-                                app.selected = app.filtered.len() - 1;
-                            }
-                        }
-                        KeyCode::Char('g') => {
-                            if key.modifiers.is_empty() {
-                                app.selected = 0;
-                            }
-                        }
-                        KeyCode::Char('G') => {
-                            if !app.filtered.is_empty() {
-                                app.selected = app.filtered.len() - 1;
-                            }
-                        }
-                        KeyCode::Char('/') => {
-                            app.query.clear();
-                            app.apply_filter();
+    /// Keep the hidden entries screen's selection on a listed entry.
+    fn clamp_hidden_screen(&mut self) {
+        let last = self.hidden.entries().len().saturating_sub(1);
+        if let Some(row) = &mut self.hidden_screen {
+            *row = (*row).min(last);
+        }
+    }
+
+    fn toggle_show_hidden(&mut self) {
+        self.show_hidden = !self.show_hidden;
+        self.apply_filter();
+    }
+
+    /// Sort by the next table column, or in the list, by the next order.
+    fn cycle_sort(&mut self) {
+        if self.table {
+            self.sort = SortColumn::cycle(self.sort);
+        } else {
+            self.list_sort = self.list_sort.next();
+            self.toasts.push(format!("Sorted by {}", self.list_sort.label()));
+        }
+        self.apply_filter();
+    }
+
+    /// Revert the latest change to favorites, hidden entries or packs, or
+    /// with `redo`, reapply the latest reverted one.
+    fn undo(&mut self, redo: bool) {
+        let step = if redo { self.undo.redo() } else { self.undo.undo() };
+        let Some(step) = step else {
+            self.toasts.push(if redo { "Nothing to redo" } else { "Nothing to undo" });
+            return;
+        };
+        let current = match &step.before {
+            undo::Snapshot::Favorites(_) => undo::Snapshot::Favorites(self.favorites.clone()),
+            undo::Snapshot::Hidden(_) => undo::Snapshot::Hidden(self.hidden.clone()),
+            undo::Snapshot::Notes(_) => undo::Snapshot::Notes(self.notes.clone()),
+            undo::Snapshot::Pack { path, .. } => undo::Snapshot::Pack {
+                path: path.clone(),
+                text: fs::read_to_string(path).ok(),
+                user: self.user.clone(),
+            },
+        };
+        match step.before {
+            undo::Snapshot::Favorites(before) => self.favorites.restore(before),
+            undo::Snapshot::Hidden(before) => self.hidden.restore(before),
+            undo::Snapshot::Notes(before) => {
+                self.notes.restore(before);
+                self.index = index::Index::build(&self.cheats, self.lang.as_deref(), &self.notes);
+            }
+            undo::Snapshot::Pack { path, text, user } => {
+                if let Err(err) = packs::restore_file(&path, text.as_deref()) {
+                    self.toasts.push(format!("{err:#}"));
+                    return;
+                }
+                self.user = user;
+                self.load_cheats();
+            }
+        }
+        let verb = if redo { "Redid" } else { "Undid" };
+        self.toasts.push(format!("{verb}: {}", step.label));
+        let step = undo::Step {
+            label: step.label,
+            before: current,
+        };
+        if redo {
+            self.undo.push_done(step);
+        } else {
+            self.undo.push_undone(step);
+        }
+        self.apply_filter();
+    }
+
+    /// Switch between the recent view, newest first, and all entries.
+    fn toggle_recent(&mut self) {
+        self.recent_only = !self.recent_only;
+        self.selected = 0;
+        self.apply_filter();
+    }
+
+    fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.toasts.push("Marks cleared");
+    }
+
+    /// The entries batch actions work on: the marked ones, or else the
+    /// selected one.
+    fn batch(&self) -> Vec<&CheatEntry> {
+        self.batch_indices()
+            .into_iter()
+            .map(|i| &self.cheats[i])
+            .collect()
+    }
+
+    /// Indices into `cheats` of the entries batch actions work on.
+    fn batch_indices(&self) -> Vec<usize> {
+        if self.marked.is_empty() {
+            self.selected_index().into_iter().collect()
+        } else {
+            self.marked.iter().copied().collect()
+        }
+    }
+
+    /// Copy the commands of the marked entries to the clipboard, one per
+    /// line.
+    fn copy_commands(&mut self) {
+        let entries = self.batch();
+        if entries.is_empty() {
+            return;
+        }
+        let text: Vec<&str> = entries.iter().map(|c| c.command.as_str()).collect();
+        let result = clipboard::copy(&text.join("\n"));
+        self.toasts.push(match &result {
+            Ok(()) if text.len() == 1 => format!("Copied {}", text[0]),
+            Ok(()) => format!("Copied {} commands", text.len()),
+            Err(err) => format!("{err:#}"),
+        });
+        if result.is_ok() {
+            for idx in self.batch_indices() {
+                self.usage.record_copy(&self.cheats[idx]);
+                self.recent.record(&self.cheats[idx]);
+            }
+        }
+    }
+
+    /// Pick the commands of the marked entries, or the selected one, to
+    /// print on exit.
+    fn pick_commands(&mut self) {
+        self.picked = Some(self.batch().iter().map(|c| c.command.clone()).collect());
+        for idx in self.batch_indices() {
+            self.recent.record(&self.cheats[idx]);
+        }
+    }
+
+    /// Export the marked entries to a Markdown file.
+    fn export_marked(&mut self) {
+        let entries = self.batch();
+        if entries.is_empty() {
+            return;
+        }
+        self.toasts.push(match export::export(&entries, self.lang.as_deref()) {
+            Ok(path) => format!("Exported {} entries to {}", entries.len(), path.display()),
+            Err(err) => format!("{err:#}"),
+        });
+    }
+
+    /// Move the selection to the entry the picked related link points to,
+    /// clearing the query if that entry is currently filtered out.
+    fn follow_related(&mut self) {
+        let Some(target) = self
+            .related
+            .and_then(|i| self.selected_entry()?.related.get(i).cloned())
+        else {
+            return;
+        };
+        let find = |app: &Self| {
+            app.filtered
+                .iter()
+                .copied()
+                .find(|&idx| merge::same_command(&app.cheats[idx].command, &target))
+        };
+        if find(self).is_none() {
+            self.query.clear();
+            self.level = None;
+            self.category = None;
+            self.apply_filter();
+        }
+        match find(self) {
+            Some(idx) => {
+                self.collapsed.remove(&self.cheats[idx].category);
+                self.build_rows();
+                self.select_row(&Row::Entry(idx));
+            }
+            None => debug!("related command '{target}' is not in the cheatsheet"),
+        }
+        self.related = None;
+    }
+
+    /// Words to highlight in `field` of each list item: the free-text terms
+    /// and their synonyms, plus terms scoped to that field.
+    fn highlight_terms(&self, field: Field) -> Vec<String> {
+        if self.lookup.is_some() {
+            return Vec::new();
+        }
+        let query = Query::parse(&self.query);
+        let mut terms: Vec<String> = query
+            .text
+            .split_whitespace()
+            .flat_map(|term| self.synonyms.alternatives(term))
+            .map(str::to_string)
+            .collect();
+        terms.extend(
+            query
+                .scoped
+                .into_iter()
+                .filter(|(f, _)| *f == field)
+                .map(|(_, term)| term),
+        );
+        terms
+    }
+
+    /// Entries explaining the keys typed in reverse lookup: commands made
+    /// of exactly those keys, then ones explaining successive pieces of them
+    /// (`d`, `2`, `w`), then commands the keys are the start of.
+    fn lookup_matches(&self, typed: &[keys::Key], candidates: &[usize]) -> Vec<usize> {
+        let explained = self.explain_keys(typed, candidates);
+        let pieces = explained.pieces.iter().filter_map(|&(_, i)| i);
+        let mut matches: Vec<usize> = Vec::new();
+        for i in explained.complete.into_iter().chain(pieces).chain(explained.prefix) {
+            if !matches.contains(&i) {
+                matches.push(i);
+            }
+        }
+        matches
+    }
+
+    /// How the entries among `candidates` explain `typed`.
+    fn explain_keys(&self, typed: &[keys::Key], candidates: &[usize]) -> lookup::Explained {
+        let mut complete = Vec::new();
+        let mut prefix = Vec::new();
+        for &i in candidates {
+            match self.cheats[i].keys().match_typed(typed) {
+                Some(Typed::Complete(n)) if n == typed.len() => complete.push(i),
+                Some(Typed::Prefix) => prefix.push(i),
+                _ => {}
+            }
+        }
+
+        let mut pieces = Vec::new();
+        let mut pos = 0;
+        while complete.is_empty() && pos < typed.len() {
+            let longest = candidates
+                .iter()
+                .filter_map(|&i| match self.cheats[i].keys().match_typed(&typed[pos..]) {
+                    Some(Typed::Complete(n)) if n > 0 => Some((n, i)),
+                    _ => None,
+                })
+                .min_by_key(|&(n, i)| (std::cmp::Reverse(n), i));
+            match longest {
+                Some((n, i)) => {
+                    pieces.push((n, Some(i)));
+                    pos += n;
+                }
+                // Counts and unknown keys don't stop the rest being explained.
+                None => {
+                    pieces.push((1, None));
+                    pos += 1;
+                }
+            }
+        }
+        lookup::Explained {
+            complete,
+            pieces,
+            prefix,
+        }
+    }
+
+    /// Like [`App::explain_keys`], for `vimbo lookup`: an operator taking
+    /// any motion (`c{motion}`) is told apart from the motion it is given
+    /// (`c`, `iw`), unless a command spells out both (`yiw`).
+    fn explain_typed(&self, typed: &[keys::Key], candidates: &[usize]) -> lookup::Explained {
+        let mut explained = self.explain_keys(typed, candidates);
+        let operator = |i: usize| {
+            self.cheats[i].keys().alternatives.into_iter().find_map(|mut seq| {
+                if seq.pop()? != keys::Token::Placeholder("motion".to_string()) {
+                    return None;
+                }
+                match (keys::Keys { alternatives: vec![seq] }).match_typed(typed)? {
+                    Typed::Complete(n) if n > 0 && n < typed.len() => Some(n),
+                    _ => None,
+                }
+            })
+        };
+        let operators: Vec<(usize, usize)> =
+            explained.complete.iter().filter_map(|&i| Some((operator(i)?, i))).collect();
+        explained.complete.retain(|&i| operator(i).is_none());
+        let Some(&(n, i)) = operators.first().filter(|_| explained.complete.is_empty()) else {
+            return explained;
+        };
+        let motion = self.explain_typed(&typed[n..], candidates);
+        explained.pieces = vec![(n, Some(i))];
+        match motion.complete.first() {
+            Some(&m) => explained.pieces.push((typed.len() - n, Some(m))),
+            None => explained.pieces.extend(motion.pieces),
+        }
+        explained
+    }
+
+    /// Entries reverse lookup searches: those of the current tab that pass
+    /// the filters.
+    fn lookup_candidates(&self) -> Vec<usize> {
+        let tabs = self.tabs();
+        (0..self.cheats.len())
+            .filter(|&i| {
+                let c = &self.cheats[i];
+                self.in_tab(&tabs, c)
+                    && (!self.favorites_only || self.favorites.contains(c))
+                    && (!self.recent_only || self.recent.position(c).is_some())
+                    && (self.show_hidden || !self.hidden.contains(c))
+                    && c.at_level(self.level)
+                    && self.mode.is_none_or(|m| m == c.mode())
+                    && self.category.as_ref().is_none_or(|cat| *cat == c.category)
+            })
+            .collect()
+    }
+
+    /// Categories of the current tab in the order they first appear, with
+    /// their entry counts.
+    fn categories(&self) -> Vec<(&str, usize)> {
+        let tabs = self.tabs();
+        let mut categories: Vec<(&str, usize)> = Vec::new();
+        for c in self.cheats.iter().filter(|c| self.in_tab(&tabs, c)) {
+            match categories.iter_mut().find(|(name, _)| *name == c.category) {
+                Some((_, count)) => *count += 1,
+                None => categories.push((&c.category, 1)),
+            }
+        }
+        categories
+    }
+
+    /// The category `name` stands for: the one named so, ignoring case,
+    /// or else the best fuzzy match.
+    fn find_category(&self, name: &str) -> Result<String> {
+        let categories = self.categories();
+        let exact = categories
+            .iter()
+            .find(|(category, _)| category.eq_ignore_ascii_case(name));
+        let mut best: Option<(i32, &str)> = None;
+        for &(category, _) in &categories {
+            let score = matcher::relevance(name, category).unwrap_or(0);
+            if score > 0 && best.is_none_or(|(top, _)| score > top) {
+                best = Some((score, category));
+            }
+        }
+        match exact.map(|&(category, _)| category).or(best.map(|(_, c)| c)) {
+            Some(category) => Ok(category.to_string()),
+            None => {
+                let names: Vec<&str> = categories.iter().map(|&(category, _)| category).collect();
+                bail!("no category matches '{name}' (available: {})", names.join(", "))
+            }
+        }
+    }
+
+    /// Position of the category filter in the sidebar: 0 for all
+    /// categories, then one per category.
+    fn category_position(&self) -> usize {
+        let Some(current) = &self.category else {
+            return 0;
+        };
+        self.categories()
+            .iter()
+            .position(|(name, _)| name == current)
+            .map_or(0, |i| i + 1)
+    }
+
+    /// Number key pressed in the list or sidebar: filter by the `n`th
+    /// category, or all categories for 0. Numbers past the last category
+    /// are ignored.
+    fn jump_to_category(&mut self, n: usize) {
+        if n <= self.categories().len() {
+            self.set_category_position(n);
+        }
+    }
+
+    /// Filter by the category at sidebar `position` (0 for all).
+    fn set_category_position(&mut self, position: usize) {
+        self.category = match position {
+            0 => None,
+            p => self.categories().get(p - 1).map(|(name, _)| name.to_string()),
+        };
+        self.apply_filter();
+    }
+
+    /// Tab labels: all entries, the editor profile's, then each pack's in
+    /// load order. Empty when no pack is loaded, leaving out the tab bar.
+    fn tabs(&self) -> Vec<&str> {
+        let mut tabs = vec!["All", self.editor.label()];
+        for c in &self.cheats {
+            if let Some(pack) = &c.pack
+                && !tabs[2..].contains(&pack.as_str())
+            {
+                tabs.push(pack);
+            }
+        }
+        if tabs.len() == 2 { Vec::new() } else { tabs }
+    }
+
+    /// Whether `c` is shown in the current tab, out of `tabs`.
+    fn in_tab(&self, tabs: &[&str], c: &CheatEntry) -> bool {
+        match self.tab {
+            0 => true,
+            1 => c.pack.is_none(),
+            tab => c.pack.as_deref() == tabs.get(tab).copied(),
+        }
+    }
+
+    /// Show the next or previous tab, with the filters it was left with.
+    fn switch_tab(&mut self, forward: bool) {
+        let count = self.tabs().len();
+        if count == 0 {
+            return;
+        }
+        self.tab_states.resize_with(count, TabState::default);
+        self.tab_states[self.tab] = TabState {
+            query: std::mem::take(&mut self.query),
+            selected: self.selected,
+            level: self.level.take(),
+            mode: self.mode.take(),
+            category: self.category.take(),
+        };
+        self.tab = if forward {
+            (self.tab + 1) % count
+        } else {
+            (self.tab + count - 1) % count
+        };
+        let state = std::mem::take(&mut self.tab_states[self.tab]);
+        self.query = state.query;
+        self.selected = state.selected;
+        self.level = state.level;
+        self.mode = state.mode;
+        self.category = state.category;
+        self.related = None;
+        self.history.reset();
+        self.apply_filter();
+    }
+
+    /// Switch between the flat list and one grouped by category, keeping
+    /// the selected entry selected.
+    fn toggle_grouped(&mut self) {
+        let selected = self.selected_index();
+        self.grouped = !self.grouped;
+        self.build_rows();
+        if let Some(idx) = selected {
+            self.select_row(&Row::Entry(idx));
+        }
+    }
+
+    /// Switch between the list and the table view, keeping the selected
+    /// entry selected.
+    fn toggle_table(&mut self) {
+        let selected = self.selected_index();
+        self.table = !self.table;
+        self.apply_filter();
+        if let Some(idx) = selected {
+            self.select_row(&Row::Entry(idx));
+        }
+    }
+
+    /// Collapse the selected group, or expand it when collapsed. The
+    /// selection moves to the group heading.
+    fn toggle_group(&mut self) {
+        let Some(category) = self.selected_category() else {
+            return;
+        };
+        if !self.collapsed.remove(&category) {
+            self.collapsed.insert(category.clone());
+        }
+        self.build_rows();
+        self.select_group(&category);
+    }
+
+    /// Collapse or expand every group.
+    fn collapse_all(&mut self, collapse: bool) {
+        let category = self.selected_category();
+        self.collapsed = if collapse {
+            self.cheats.iter().map(|c| c.category.clone()).collect()
+        } else {
+            BTreeSet::new()
+        };
+        self.build_rows();
+        if let Some(category) = category {
+            self.select_group(&category);
+        }
+    }
+
+    /// Category of the selected heading or entry.
+    fn selected_category(&self) -> Option<String> {
+        match self.rows.get(self.selected)? {
+            Row::Group { category, .. } => Some(category.clone()),
+            Row::Entry(idx) => Some(self.cheats[*idx].category.clone()),
+        }
+    }
+
+    fn select_group(&mut self, category: &str) {
+        let heading = self
+            .rows
+            .iter()
+            .position(|row| matches!(row, Row::Group { category: c, .. } if c == category));
+        self.selected = heading.unwrap_or(0);
+    }
+
+    fn select_row(&mut self, row: &Row) {
+        if let Some(pos) = self.rows.iter().position(|r| r == row) {
+            self.selected = pos;
+        }
+    }
+
+    fn cycle_mode(&mut self) {
+        self.mode = Mode::cycle(self.mode);
+        self.apply_filter();
+    }
+
+    /// Step the category filter to the next category, in the order they
+    /// first appear, and back to all categories after the last one.
+    fn cycle_category(&mut self) {
+        let positions = self.categories().len() + 1;
+        self.set_category_position((self.category_position() + 1) % positions);
+    }
+
+    fn apply_filter(&mut self) {
+        if let Some(typed) = self.lookup.as_ref().filter(|t| !t.is_empty()) {
+            self.filtered = self.lookup_matches(typed, &self.lookup_candidates());
+            self.selected = 0;
+            self.build_rows();
+            return;
+        }
+        let query = Query::parse(&self.query);
+        let terms = index::Terms::new(&query, &self.synonyms);
+        let tabs = self.tabs();
+        let mut ranked: Vec<(i32, usize)> = self
+            .cheats
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| {
+                if !self.in_tab(&tabs, c) {
+                    return None;
+                }
+                if self.favorites_only && !self.favorites.contains(c) {
+                    return None;
+                }
+                if self.recent_only && self.recent.position(c).is_none() {
+                    return None;
+                }
+                if !self.show_hidden && self.hidden.contains(c) {
+                    return None;
+                }
+                if !c.at_level(self.level) {
+                    return None;
+                }
+                if self.mode.is_some_and(|m| m != c.mode()) {
+                    return None;
+                }
+                if self.category.as_ref().is_some_and(|cat| *cat != c.category) {
+                    return None;
+                }
+                let fields = self.index.get(i);
+                if !query.matches_tags(&fields.tags) || fields.excluded_by(&query) {
+                    return None;
+                }
+                if !query.has_terms() {
+                    return Some((0, i));
+                }
+                let boost = self.usage.boost(c);
+                fields.relevance(&terms).map(|score| (score + boost, i))
+            })
+            .collect();
+        // Stable, so equally relevant entries keep dataset order.
+        ranked.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        self.filtered = ranked.into_iter().map(|(_, i)| i).collect();
+        if self.recent_only {
+            let (cheats, recent) = (&self.cheats, &self.recent);
+            self.filtered.sort_by_key(|&i| recent.position(&cheats[i]));
+        }
+        if !self.table && self.list_sort == ListSort::Pack {
+            // Pack order (or relevance) has no key to sort by; reversed is
+            // just the other way round.
+            if self.sort_descending {
+                self.filtered.reverse();
+            }
+        } else if !self.table {
+            let cheats = &self.cheats;
+            let key = |&idx: &usize| {
+                let c = &cheats[idx];
+                match self.list_sort {
+                    ListSort::Pack => unreachable!("pack order is not sorted by key"),
+                    ListSort::Command => (0, c.command.to_lowercase()),
+                    ListSort::Category => (0, c.category.to_lowercase()),
+                    ListSort::Usage => (-i64::from(self.usage.count(c)), String::new()),
+                    ListSort::Added => (-(idx as i64), String::new()),
+                }
+            };
+            if self.sort_descending {
+                self.filtered.sort_by_cached_key(|idx| std::cmp::Reverse(key(idx)));
+            } else {
+                self.filtered.sort_by_cached_key(key);
+            }
+        }
+        if self.table && let Some(column) = self.sort {
+            let lang = self.lang.as_deref();
+            let cheats = &self.cheats;
+            let key = |&idx: &usize| {
+                let c = &cheats[idx];
+                match column {
+                    SortColumn::Category => c.category.to_lowercase(),
+                    SortColumn::Command => c.command.to_lowercase(),
+                    SortColumn::Description => c.description_in(lang).to_lowercase(),
+                }
+            };
+            if self.sort_descending {
+                self.filtered.sort_by_cached_key(|idx| std::cmp::Reverse(key(idx)));
+            } else {
+                self.filtered.sort_by_cached_key(key);
+            }
+        }
+        debug!("filter updated; query='{}', shown={}", self.query, self.filtered.len());
+        self.build_rows();
+    }
+
+    /// Lay out `filtered` as list rows: one per entry, or under a heading
+    /// per category (in the order of their best match) when grouped,
+    /// leaving out the entries of collapsed groups. The table has a
+    /// category column instead of headings.
+    fn build_rows(&mut self) {
+        let rows = if self.grouped && !self.table {
+            let mut groups: Vec<(&str, Vec<usize>)> = Vec::new();
+            for &idx in &self.filtered {
+                let category = self.cheats[idx].category.as_str();
+                match groups.iter_mut().find(|(name, _)| *name == category) {
+                    Some((_, entries)) => entries.push(idx),
+                    None => groups.push((category, vec![idx])),
+                }
+            }
+            let mut rows = Vec::new();
+            for (category, entries) in groups {
+                rows.push(Row::Group {
+                    category: category.to_string(),
+                    count: entries.len(),
+                });
+                if !self.collapsed.contains(category) {
+                    rows.extend(entries.into_iter().map(Row::Entry));
+                }
+            }
+            rows
+        } else {
+            self.filtered.iter().copied().map(Row::Entry).collect()
+        };
+        self.rows = rows;
+        if self.selected >= self.rows.len() {
+            self.selected = self.rows.len().saturating_sub(1);
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    // Answers the completion script instead of running, when it calls.
+    CompleteEnv::with_factory(Cli::command).var(shell::VAR).complete();
+    debug!("starting vimbo");
+    let cli = Cli::parse();
+    paths::set_overrides(paths::Overrides {
+        config: cli.config.clone(),
+        data_dir: cli.data_dir.clone(),
+        profile: cli.profile.clone(),
+    })?;
+    let command = match cli.command {
+        Some(Command::Tui(args)) => Command::Tui(TuiArgs {
+            theme: args.theme.or(cli.tui.theme),
+            plain: args.plain || cli.tui.plain,
+            pick: args.pick || cli.tui.pick,
+            resume: args.resume || cli.tui.resume,
+        }),
+        Some(mut command) => {
+            // `vimbo --theme light export svg` colors the poster too.
+            if let Command::Export { theme, .. } = &mut command {
+                *theme = theme.or(cli.tui.theme);
+            }
+            command
+        }
+        None if cli.list || cli.format.is_some() || cli.tip => Command::List {
+            format: cli.format.unwrap_or_default(),
+            tip: cli.tip,
+            fresh: cli.fresh,
+        },
+        None => Command::Tui(cli.tui),
+    };
+    // Piped or in CI there is no screen to draw on; print the entries as
+    // `list` would rather than garble the output with escape codes.
+    let command = match command {
+        Command::Tui(args) if !args.pick && !io::stdout().is_terminal() => {
+            debug!("stdout is not a terminal, listing instead");
+            Command::List {
+                format: output::Format::default(),
+                tip: false,
+                fresh: false,
+            }
+        }
+        command => command,
+    };
+    // Commands that do not need the entries.
+    let command = match command {
+        Command::Packs { command } => return run_packs(command.unwrap_or(PacksCommand::List)),
+        Command::Import(args) => return run_packs(PacksCommand::Import(args)),
+        Command::Install(args) => return run_packs(PacksCommand::Install(args)),
+        Command::Update(args) => return run_packs(PacksCommand::Update(args)),
+        Command::Config { command } => {
+            return match command.unwrap_or(ConfigCommand::Path) {
+                ConfigCommand::Path => config::print_path(),
+                ConfigCommand::Dirs => config::print_dirs(),
+                ConfigCommand::Show => config::print_file(),
+            };
+        }
+        Command::Completions { shell } => return shell::print(shell),
+        Command::Stats { top } => return usage::print_stats(top),
+        command => command,
+    };
+    let queries = match cli.stdin {
+        Some(_) => read_queries()?,
+        None => cli.query.into_iter().collect(),
+    };
+    let config = config::Config::load()?;
+
+    let pack_names = if cli.packs.is_empty() {
+        &config.packs
+    } else {
+        &cli.packs
+    };
+    let bundled = packs::load(pack_names)?;
+    let mut imported = packs::load_local()?;
+    imported.extend(registry::load_installed()?);
+    for path in &cli.markdown {
+        imported.extend(import::markdown::import_file(path)?);
+    }
+    let vimrc_paths = match cli.vimrc {
+        Some(Some(path)) => vec![path],
+        Some(None) => import::vimrc::default_paths(),
+        None if config.vimrc => import::vimrc::default_paths(),
+        None => Vec::new(),
+    };
+    imported.extend(import::vimrc::import_files(&vimrc_paths)?);
+    if let Some(address) = &cli.live {
+        imported.extend(import::nvim::fetch_mappings(address.as_deref())?);
+    }
+    let lang = cli.lang.or(config.lang);
+    let query = (!queries.is_empty()).then(|| queries.join(" "));
+    let editor = cli.editor.or(config.editor).unwrap_or_default();
+    let mut app = App::new(query, editor, bundled, imported, lang);
+    app.level = cli.level;
+    if let Some(name) = &cli.category {
+        app.category = Some(app.find_category(name)?);
+    }
+    app.synonyms = synonyms::Synonyms::new(&config.synonyms);
+    app.density = config.density;
+    app.icon_style = config.icons;
+    app.numbers = config.numbers;
+    if let Some(ms) = config.tick_rate {
+        if !(10..=5000).contains(&ms) {
+            bail!("tick_rate in config file must be 10 to 5000 ms, not {ms}");
+        }
+        app.tick = Duration::from_millis(ms);
+    }
+    if let Some(template) = config.status {
+        status::validate(&template).context("invalid status template in config file")?;
+        app.status_template = template;
+    }
+    app.keymap = keymap::Keymap::new(&config.keys).context("invalid [keys] in config file")?;
+    let tui = match &command {
+        Command::Tui(args) => args,
+        _ => &TuiArgs::default(),
+    };
+    let preset = tui.theme.or(config.theme.preset).unwrap_or_default();
+    app.theme = theme::Theme::new(preset, &config.theme.elements)
+        .context("invalid [theme] in config file")?;
+    app.theme_overrides = config.theme.elements;
+    app.pick = tui.pick;
+    let resume = tui.resume || config.resume;
+    if tui.plain {
+        app.plain = true;
+        app.theme = theme::Theme::plain();
+        if app.icon_style == config::Icons::Glyph {
+            app.icon_style = config::Icons::Ascii;
+        }
+    }
+    if config.history {
+        app.history = history::History::persistent()?;
+    }
+    if config.usage {
+        app.usage = usage::Usage::persistent()?;
+    }
+    app.panes = panes::Panes::persistent().unwrap_or_else(|err| {
+        warn!("pane sizes will not be saved: {err:#}");
+        panes::Panes::in_memory()
+    });
+    app.favorites = favorites::Favorites::persistent().unwrap_or_else(|err| {
+        warn!("favorites will not be saved: {err:#}");
+        favorites::Favorites::in_memory()
+    });
+    app.favorites.migrate(&app.cheats);
+    app.notes = notes::Notes::persistent().unwrap_or_else(|err| {
+        warn!("notes will not be saved: {err:#}");
+        notes::Notes::in_memory()
+    });
+    app.index = index::Index::build(&app.cheats, app.lang.as_deref(), &app.notes);
+    app.recent = recent::Recent::persistent().unwrap_or_else(|err| {
+        warn!("recent entries will not be saved: {err:#}");
+        recent::Recent::in_memory()
+    });
+    app.hidden = hidden::Hidden::persistent().unwrap_or_else(|err| {
+        warn!("hidden entries will not be saved: {err:#}");
+        hidden::Hidden::in_memory()
+    });
+    app.apply_filter();
+    let entries: Vec<&CheatEntry> = app.filtered.iter().map(|&i| &app.cheats[i]).collect();
+    let lang = app.lang.as_deref();
+    let paint = output::Paint::new(&app.theme, cli.color);
+    match command {
+        Command::List {
+            tip: true, fresh, ..
+        } => {
+            return tip::print(&entries, lang, paint, |c| {
+                fresh
+                    && !app.favorites.contains(c)
+                    && app.usage.count(c) == 0
+                    && app.recent.position(c).is_none()
+            });
+        }
+        Command::List { format, .. } if queries.len() > 1 => {
+            let mut batch = Vec::new();
+            for query in &queries {
+                app.query = query.clone();
+                app.apply_filter();
+                batch.push((query.as_str(), app.filtered.clone()));
+            }
+            let batch: Vec<_> = batch
+                .into_iter()
+                .map(|(query, filtered)| {
+                    (query, filtered.iter().map(|&i| &app.cheats[i]).collect())
+                })
+                .collect();
+            let paint = output::Paint::new(&app.theme, cli.color);
+            return output::print_batch(&batch, app.lang.as_deref(), format, paint);
+        }
+        Command::List { format, .. } => return output::print(&entries, lang, format, paint),
+        Command::Export {
+            format,
+            output,
+            page_size,
+            landscape,
+            columns,
+            width,
+            theme,
+        } => {
+            let layout = export::pdf::Layout {
+                page_size,
+                landscape,
+                columns: columns.into(),
+            };
+            let theme = match theme {
+                Some(preset) => theme::Theme::new(preset, &app.theme_overrides)
+                    .context("invalid [theme] in config file")?,
+                None => app.theme.clone(),
+            };
+            let poster = export::svg::Layout {
+                theme: &theme,
+                width,
+                columns: columns.into(),
+            };
+            let title = app.editor.title().trim();
+            return export::run(
+                format,
+                &entries,
+                title,
+                lang,
+                layout,
+                poster,
+                output.as_deref(),
+            );
+        }
+        Command::Quiz { count } => return quiz::run(&entries, lang, count),
+        Command::Lookup { keys } => {
+            let typed = keys::parse_typed(&keys);
+            let explained = app.explain_typed(&typed, &app.lookup_candidates());
+            return lookup::print(&app.cheats, &typed, &explained, lang, paint);
+        }
+        _ => {}
+    }
+
+    if resume && let Some(mut session) = session::Session::load()? {
+        // What the command line asks for wins over the session.
+        if !queries.is_empty() {
+            session.query = app.query.clone();
+        }
+        if cli.category.is_some() {
+            session.category = app.category.clone();
+        }
+        app.restore_session(session);
+    }
+
+    app.watcher = match watch::PackWatcher::new() {
+        Ok(watcher) => Some(watcher),
+        Err(err) => {
+            warn!("packs will not be reloaded as they change: {err:#}");
+            None
+        }
+    };
+
+    enable_raw_mode()?;
+    let mut screen = screen();
+    execute!(screen, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(screen);
+    let mut terminal = Terminal::new(backend)?;
+
+    let res = run_app(&mut terminal, &mut app);
+
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+    debug!("exiting vimbo");
+
+    // Picking is a one-off, not a session to come back to.
+    if !app.pick {
+        let session = session::Session {
+            query: app.query.clone(),
+            category: app.category.clone(),
+            selected: app
+                .selected_entry()
+                .map(|c| (c.category.clone(), c.command.clone())),
+            offset: app.areas.get().list_offset,
+        };
+        if let Err(err) = session.save() {
+            warn!("the session will not be resumed: {err:#}");
+        }
+    }
+    res?;
+    if app.pick {
+        let Some(picked) = app.picked else {
+            process::exit(1);
+        };
+        let mut stdout = io::stdout().lock();
+        for command in picked {
+            writeln!(stdout, "{command}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Where the TUI is drawn: stdout, unless that is captured (as for
+/// `--pick` in `$(...)`) and the terminal is only left on stderr.
+fn screen() -> Box<dyn Write> {
+    if io::stdout().is_terminal() {
+        Box::new(io::stdout())
+    } else {
+        Box::new(io::stderr())
+    }
+}
+
+/// Entries PgUp / PgDn move by.
+const PAGE: usize = 10;
+
+/// Categories reachable with the number keys 1-9.
+pub const CATEGORY_KEYS: usize = 9;
+
+/// How long to wait for input before redrawing anyway, which keeps the
+/// clock and the detail pane's demos moving; `tick_rate` in the config
+/// file changes it.
+pub const TICK: Duration = Duration::from_millis(200);
+
+/// How long the mouse pointer rests on an entry before its tooltip shows.
+pub const HOVER_DELAY: Duration = Duration::from_millis(600);
+
+/// The queries piped in for `-`: every non-blank line, trimmed.
+fn read_queries() -> Result<Vec<String>> {
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        bail!("`-` reads the query from stdin, but nothing is piped in");
+    }
+    let text = io::read_to_string(stdin).context("failed to read the query from stdin")?;
+    Ok(text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Run a `packs` subcommand.
+fn run_packs(command: PacksCommand) -> Result<()> {
+    match command {
+        PacksCommand::List => packs::print_list(),
+        PacksCommand::Install(args) => registry::install(&args.spec),
+        PacksCommand::Update(args) => registry::update(args.pack.as_deref()),
+        PacksCommand::Import(args) => import::run(args.source, &args.topics, args.from.as_deref()),
+    }
+}
+
+fn run_app<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+) -> Result<()> {
+    loop {
+        app.toasts.tick(Instant::now());
+        if app.watcher.as_ref().is_some_and(watch::PackWatcher::changed) {
+            app.reload_packs();
+        }
+        terminal.draw(|f| ui::draw(f, app))?;
+
+        if crossterm::event::poll(app.tick)? {
+            match event::read()? {
+                Event::Key(key) => {
+                    debug!("key: {:?}", key.code);
+                    app.hover = None;
+                    let selected = app.selected;
+                    if app.lookup.is_some()
+                        && app.palette.is_none()
+                        && app.form.is_none()
+                        && app.note.is_none()
+                        && handle_lookup_key(app, key.code, key.modifiers)
+                    {
+                        if app.selected != selected {
+                            app.related = None;
+                        }
+                        continue;
+                    }
+                    match key.code {
+                        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            return Ok(());
+                        }
+                        _ if app.form.is_some() => handle_form_key(app, key),
+                        _ if app.note.is_some() => handle_note_key(app, key),
+                        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.palette = match app.palette {
+                                Some(_) => None,
+                                None => Some(palette::Palette::default()),
+                            };
+                        }
+                        _ if app.palette.is_some() => {
+                            if handle_palette_key(terminal, app, key) {
+                                return Ok(());
+                            }
+                        }
+                        _ if app.hidden_screen.is_some() && !app.show_help => {
+                            handle_hidden_key(app, key)
+                        }
+                        KeyCode::Esc if app.show_stats && !app.show_help => app.show_stats = false,
+                        KeyCode::Enter
+                            if app.pick
+                                && !app.show_help
+                                && !app.zen
+                                && app.focus != Focus::Sidebar
+                                && !app.batch().is_empty() =>
+                        {
+                            app.pick_commands();
+                            return Ok(());
                         }
-                        KeyCode::Backspace => {
-                            app.query.pop();
+                        KeyCode::Esc if app.pick && !app.show_help && !app.zen => return Ok(()),
+                        KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.switch_editor(app.editor.next());
+                        }
+                        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.undo(true);
+                        }
+                        KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.level = Difficulty::cycle(app.level);
                             app.apply_filter();
                         }
-                        KeyCode::Char(c) => {
-                            app.query.push(c);
+                        KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.lookup = Some(Vec::new());
                             app.apply_filter();
                         }
-                        _ => {}
+                        KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.cycle_category();
+                        }
+                        KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.show_conflicts = !app.show_conflicts;
+                        }
+                        KeyCode::Left | KeyCode::Right
+                            if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            if let Some(sidebar) = app.areas.get().sidebar {
+                                app.panes.resize_sidebar(sidebar.width, key.code == KeyCode::Right);
+                            }
+                        }
+                        KeyCode::Up | KeyCode::Down
+                            if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            app.panes.resize_detail(key.code == KeyCode::Up);
+                        }
+                        KeyCode::Tab => match app.suggestions().first() {
+                            Some(suggestion) => app.accept_suggestion(suggestion),
+                            None => app.cycle_mode(),
+                        },
+                        KeyCode::F(1) => open_help(terminal, app),
+                        _ if app.focus == Focus::List
+                            && !app.show_help
+                            && app.keymap.action(key.code, key.modifiers)
+                                == Some(Action::VimHelp) =>
+                        {
+                            app.pending = None;
+                            open_help(terminal, app);
+                        }
+                        _ if app.show_help => handle_help_key(app, key),
+                        _ if app.zen => handle_zen_key(app, key),
+                        code => {
+                            let quit = match app.focus {
+                                Focus::Search => {
+                                    handle_search_key(app, code);
+                                    false
+                                }
+                                Focus::List => handle_list_key(app, key),
+                                Focus::Sidebar => handle_sidebar_key(app, key),
+                            };
+                            if quit {
+                                return Ok(());
+                            }
+                        }
+                    }
+                    if app.selected != selected {
+                        app.related = None;
                     }
                 }
+                Event::Mouse(mouse) => handle_mouse(app, mouse),
                 _ => {}
             }
         }
     }
 }
+
+/// Rows scrolled per mouse wheel step.
+const WHEEL_STEP: usize = 3;
+
+/// Clicks select list entries and sidebar categories, or focus the search
+/// bar; clicking the selected entry again toggles its details. The wheel
+/// scrolls the list, and resting on an entry shows its tooltip.
+fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    let selected = app.selected;
+    let hover = app.hover.take();
+    if app.zen {
+        return;
+    }
+    let areas = app.areas.get();
+    let inside = |area: Rect| area.contains(Position::new(mouse.column, mouse.row));
+    // Row clicked below the border and `header` lines of `area`, plus `offset`.
+    let row = |area: Rect, header: u16, offset: usize| {
+        (mouse.row - area.y)
+            .checked_sub(1 + header)
+            .map(|r| r as usize + offset)
+    };
+    // Index of the list row under the pointer.
+    let list_row = |app: &App| {
+        let area = areas.list.filter(|&a| inside(a))?;
+        let line = row(area, u16::from(app.table), 0)?;
+        let width = area.width.saturating_sub(2);
+        let index = ui::row_at(app, width, areas.narrow, areas.list_offset, line);
+        (index < app.rows.len()).then_some(index)
+    };
+    let last = app.rows.len().saturating_sub(1);
+    if let Some(help) = areas.help {
+        let last = ui::help_max_scroll(app, help);
+        match mouse.kind {
+            MouseEventKind::ScrollDown => app.help_scroll = (app.help_scroll + 1).min(last),
+            MouseEventKind::ScrollUp => app.help_scroll = app.help_scroll.saturating_sub(1),
+            _ => {}
+        }
+        return;
+    }
+    match mouse.kind {
+        MouseEventKind::ScrollDown => app.selected = (app.selected + WHEEL_STEP).min(last),
+        MouseEventKind::ScrollUp => app.selected = app.selected.saturating_sub(WHEEL_STEP),
+        MouseEventKind::Moved => {
+            app.hover = list_row(app)
+                .filter(|&index| matches!(app.rows[index], Row::Entry(_)))
+                .map(|index| Hover {
+                    row: index,
+                    column: mouse.column,
+                    line: mouse.row,
+                    since: hover
+                        .filter(|h| h.row == index)
+                        .map_or_else(Instant::now, |h| h.since),
+                });
+        }
+        MouseEventKind::Down(MouseButton::Left) => {
+            if inside(areas.search) {
+                app.focus = Focus::Search;
+            } else if let Some(area) = areas.sidebar.filter(|&a| inside(a)) {
+                app.focus = Focus::Sidebar;
+                if let Some(position) = row(area, 0, areas.sidebar_offset)
+                    && position <= app.categories().len()
+                {
+                    app.set_category_position(position);
+                }
+            } else if areas.list.is_some_and(inside) {
+                app.focus = Focus::List;
+                if let Some(index) = list_row(app) {
+                    if index == app.selected && app.selected_index().is_none() {
+                        app.toggle_group();
+                    } else if index == app.selected {
+                        app.show_detail = !app.show_detail;
+                        if app.show_detail {
+                            app.record_use();
+                        }
+                    } else {
+                        app.selected = index;
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    if app.selected != selected {
+        app.related = None;
+    }
+}
+
+/// Keys while typing in the search bar (insert mode).
+fn handle_search_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => app.focus = Focus::List,
+        KeyCode::Enter => {
+            app.history.push(&app.query);
+            app.usage.record_search(&app.query);
+            app.focus = Focus::List;
+        }
+        KeyCode::Up => {
+            if let Some(query) = app.history.older(&app.query) {
+                app.query = query.to_string();
+                app.apply_filter();
+            }
+        }
+        KeyCode::Down => match app.history.newer() {
+            Some(query) => {
+                app.query = query.to_string();
+                app.apply_filter();
+            }
+            None => app.focus = Focus::List,
+        },
+        KeyCode::Backspace => {
+            app.query.pop();
+            app.history.reset();
+            app.apply_filter();
+        }
+        KeyCode::Char(c) => {
+            app.query.push(c);
+            app.history.reset();
+            app.apply_filter();
+        }
+        _ => {}
+    }
+}
+
+/// Keys in the list (normal mode), Vim style. Returns whether to quit.
+fn handle_list_key(app: &mut App, key: KeyEvent) -> bool {
+    let pending = app.pending.take();
+    let count = app.count.take();
+    let last = app.rows.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Char('g') if pending == Some('g') => app.selected = 0,
+        KeyCode::Char('t') if pending == Some('g') => app.switch_tab(true),
+        KeyCode::Char('T') if pending == Some('g') => app.switch_tab(false),
+        KeyCode::Char('H') if pending == Some('g') => app.hidden_screen = Some(0),
+        KeyCode::Char('a') if pending == Some('z') => app.toggle_group(),
+        KeyCode::Char('M') if pending == Some('z') => app.collapse_all(true),
+        KeyCode::Char('R') if pending == Some('z') => app.collapse_all(false),
+        KeyCode::Char('i') if pending == Some('z') => app.toggle_grouped(),
+        KeyCode::Char(c @ ('g' | 'z')) => app.pending = Some(c),
+        KeyCode::Char(c @ '0'..='9') if app.numbers != config::Numbers::Off => {
+            let digit = c as usize - '0' as usize;
+            app.count = Some(count.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+        }
+        KeyCode::Char(c @ '0'..='9') => app.jump_to_category(c as usize - '0' as usize),
+        _ => {
+            let Some(action) = app.keymap.action(key.code, key.modifiers) else {
+                return false;
+            };
+            // Like Vim's count-prefixed motions: 12G (or 12 Enter) goes to
+            // row 12, 3j three rows down.
+            match (count, action) {
+                (Some(n), Action::Bottom | Action::Details) => {
+                    app.selected = n.saturating_sub(1).min(last);
+                }
+                (Some(n), Action::Down) => app.selected = app.selected.saturating_add(n).min(last),
+                (Some(n), Action::Up) => app.selected = app.selected.saturating_sub(n),
+                _ => return handle_list_action(app, action, key.code, last),
+            }
+        }
+    }
+    false
+}
+
+/// Run a list action; `code` is the key that was pressed. Returns whether
+/// to quit.
+fn handle_list_action(app: &mut App, action: Action, code: KeyCode, last: usize) -> bool {
+    match action {
+        Action::Quit => return true,
+        Action::Close if !app.show_detail && !app.marked.is_empty() => app.clear_marks(),
+        Action::Close => app.show_detail = false,
+        Action::Search => {
+            app.query.clear();
+            app.history.reset();
+            app.apply_filter();
+            app.focus = Focus::Search;
+        }
+        Action::EditSearch => app.focus = Focus::Search,
+        Action::Details if app.show_detail && app.related.is_some() => app.follow_related(),
+        Action::Details if app.selected_index().is_none() => app.toggle_group(),
+        Action::Details => {
+            app.show_detail = !app.show_detail;
+            if app.show_detail {
+                app.record_use();
+            }
+        }
+        Action::Left | Action::Right if app.show_detail => {
+            let count = app.selected_entry().map_or(0, |e| e.related.len());
+            app.related = match (action == Action::Right, app.related) {
+                _ if count == 0 => None,
+                (true, None) => Some(0),
+                (true, Some(i)) => Some((i + 1).min(count - 1)),
+                (false, Some(0) | None) => None,
+                (false, Some(i)) => Some(i - 1),
+            };
+        }
+        // Reports and narrow terminals hide the sidebar.
+        Action::Left if app.areas.get().sidebar.is_some() => app.focus = Focus::Sidebar,
+        Action::Left | Action::Right => {}
+        Action::Help => app.open_help(),
+        Action::Zen => app.open_zen(),
+        // Needs the terminal, so run_app handles it before the list does.
+        Action::VimHelp => {}
+        Action::Favorite => app.toggle_favorite(),
+        Action::Mark => app.toggle_mark(),
+        Action::Edit => app.open_form(),
+        Action::Note => app.open_note(),
+        Action::Add => app.open_add_form(),
+        Action::Copy => app.copy_commands(),
+        Action::Table => app.toggle_table(),
+        Action::Density => app.density = app.density.toggle(),
+        Action::Sort => app.cycle_sort(),
+        Action::ReverseSort => {
+            app.sort_descending = !app.sort_descending;
+            app.apply_filter();
+        }
+        Action::FavoritesOnly => {
+            app.favorites_only = !app.favorites_only;
+            app.apply_filter();
+        }
+        Action::Recent => app.toggle_recent(),
+        Action::Hide => app.toggle_hidden(),
+        Action::Undo => app.undo(false),
+        Action::ShowHidden => app.toggle_show_hidden(),
+        Action::Up if code == KeyCode::Up && app.selected == 0 => app.focus = Focus::Search,
+        Action::Up => app.selected = app.selected.saturating_sub(1),
+        Action::Down => app.selected = (app.selected + 1).min(last),
+        // Every listed entry matches the query, so n / N step through them,
+        // wrapping around like Vim's search.
+        Action::NextMatch | Action::PrevMatch if app.rows.is_empty() => {}
+        Action::NextMatch => {
+            app.selected = if app.selected >= last { 0 } else { app.selected + 1 };
+        }
+        Action::PrevMatch => {
+            app.selected = if app.selected == 0 { last } else { app.selected - 1 };
+        }
+        Action::PageUp => app.selected = app.selected.saturating_sub(PAGE),
+        Action::PageDown => app.selected = (app.selected + PAGE).min(last),
+        Action::Bottom => app.selected = last,
+    }
+    false
+}
+
+/// Keys while the entry form is open: typing edits the focused field, Tab
+/// and the arrows move between fields, Enter saves and Esc cancels.
+fn handle_form_key(app: &mut App, key: KeyEvent) {
+    let Some(form) = app.form.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => app.form = None,
+        KeyCode::Enter => app.save_form(),
+        KeyCode::Left | KeyCode::Right if form.pick_category(key.code == KeyCode::Right) => {}
+        KeyCode::Tab | KeyCode::Down => form.step(true),
+        KeyCode::BackTab | KeyCode::Up => form.step(false),
+        KeyCode::Backspace => {
+            form.input().pop();
+        }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => form.input().push(c),
+        _ => {}
+    }
+}
+
+/// Keys on the hidden entries screen: the list's up and down keys pick an
+/// entry, its hide key or Enter unhides it and Esc (or q) goes back.
+fn handle_hidden_key(app: &mut App, key: KeyEvent) {
+    let last = app.hidden.entries().len().saturating_sub(1);
+    let Some(row) = app.hidden_screen.as_mut() else {
+        return;
+    };
+    match app.keymap.action(key.code, key.modifiers) {
+        Some(Action::Up) => *row = row.saturating_sub(1),
+        Some(Action::Down) => *row = (*row + 1).min(last),
+        Some(Action::PageUp) => *row = row.saturating_sub(PAGE),
+        Some(Action::PageDown) => *row = (*row + PAGE).min(last),
+        Some(Action::Bottom) => *row = last,
+        Some(Action::Hide | Action::Details) => app.unhide_picked(),
+        Some(Action::Undo) => {
+            app.undo(false);
+            app.clamp_hidden_screen();
+        }
+        Some(Action::Close | Action::Quit) => app.hidden_screen = None,
+        Some(Action::Help) => app.open_help(),
+        _ => {}
+    }
+}
+
+/// Keys while writing a note: Enter saves it and Esc cancels.
+fn handle_note_key(app: &mut App, key: KeyEvent) {
+    let Some(note) = app.note.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => app.note = None,
+        KeyCode::Enter => app.save_note(),
+        KeyCode::Backspace => {
+            note.pop();
+        }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => note.push(c),
+        _ => {}
+    }
+}
+
+/// Keys while the command palette is open: typing filters the commands,
+/// Enter runs the picked one. Returns whether to quit.
+fn handle_palette_key<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    key: KeyEvent,
+) -> bool {
+    let Some(palette) = app.palette.as_mut() else {
+        return false;
+    };
+    let last = palette.matches().len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc => app.palette = None,
+        KeyCode::Enter => {
+            let command = palette.selected_command();
+            app.palette = None;
+            if let Some(command) = command {
+                return run_command(terminal, app, command);
+            }
+        }
+        KeyCode::Up => palette.selected = palette.selected.saturating_sub(1),
+        KeyCode::Down => palette.selected = (palette.selected + 1).min(last),
+        KeyCode::Backspace => {
+            palette.query.pop();
+            palette.selected = 0;
+        }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            palette.query.push(c);
+            palette.selected = 0;
+        }
+        _ => {}
+    }
+    false
+}
+
+/// Run a command picked from the palette. Returns whether to quit.
+fn run_command<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    command: palette::Command,
+) -> bool {
+    use palette::Command;
+    debug!("palette command: {command:?}");
+    match command {
+        Command::NewSearch => {
+            app.query.clear();
+            app.history.reset();
+            app.apply_filter();
+            app.focus = Focus::Search;
+        }
+        Command::Favorite => app.toggle_favorite(),
+        Command::Mark => app.toggle_mark(),
+        Command::Edit => app.open_form(),
+        Command::Note => app.open_note(),
+        Command::Add => app.open_add_form(),
+        Command::Copy => app.copy_commands(),
+        Command::Export => app.export_marked(),
+        Command::ClearMarks => app.clear_marks(),
+        Command::FavoritesOnly => {
+            app.favorites_only = !app.favorites_only;
+            app.apply_filter();
+        }
+        Command::Recent => app.toggle_recent(),
+        Command::Hide => app.toggle_hidden(),
+        Command::Undo => app.undo(false),
+        Command::Redo => app.undo(true),
+        Command::ShowHidden => app.toggle_show_hidden(),
+        Command::ManageHidden => app.hidden_screen = Some(0),
+        Command::Group => app.toggle_grouped(),
+        Command::CollapseAll => app.collapse_all(true),
+        Command::ExpandAll => app.collapse_all(false),
+        Command::Table => app.toggle_table(),
+        Command::Sort => app.cycle_sort(),
+        Command::ReverseSort => {
+            app.sort_descending = !app.sort_descending;
+            app.apply_filter();
+        }
+        Command::Density => app.density = app.density.toggle(),
+        Command::Numbers => app.numbers = app.numbers.next(),
+        Command::NextTab => app.switch_tab(true),
+        Command::PreviousTab => app.switch_tab(false),
+        Command::Level => {
+            app.level = Difficulty::cycle(app.level);
+            app.apply_filter();
+        }
+        Command::Mode => app.cycle_mode(),
+        Command::Category => app.cycle_category(),
+        Command::Editor => app.switch_editor(app.editor.next()),
+        Command::Theme => app.next_theme(),
+        Command::Lookup => {
+            app.lookup = Some(Vec::new());
+            app.apply_filter();
+        }
+        Command::Conflicts => app.show_conflicts = !app.show_conflicts,
+        Command::Stats => app.show_stats = !app.show_stats,
+        Command::VimHelp => open_help(terminal, app),
+        Command::Help => app.open_help(),
+        Command::Zen => app.open_zen(),
+        Command::Quit => return true,
+    }
+    false
+}
+
+/// Keys while the help overlay is open.
+fn handle_help_key(app: &mut App, key: KeyEvent) {
+    let last = app.areas.get().help.map_or(0, |area| ui::help_max_scroll(app, area));
+    match app.keymap.action(key.code, key.modifiers) {
+        Some(Action::Help | Action::Quit | Action::Close) => app.show_help = false,
+        Some(Action::Up) => app.help_scroll = app.help_scroll.saturating_sub(1),
+        Some(Action::Down) => app.help_scroll = (app.help_scroll + 1).min(last),
+        Some(Action::PageUp) => app.help_scroll = app.help_scroll.saturating_sub(PAGE),
+        Some(Action::PageDown) => app.help_scroll = (app.help_scroll + PAGE).min(last),
+        _ => {}
+    }
+}
+
+/// Keys in focus mode: n / p (or the list's up and down keys) step through
+/// the results.
+fn handle_zen_key(app: &mut App, key: KeyEvent) {
+    if key.code == KeyCode::Char('p') {
+        app.step_entry(false);
+        return;
+    }
+    match app.keymap.action(key.code, key.modifiers) {
+        Some(Action::Zen | Action::Quit | Action::Close) => app.zen = false,
+        Some(Action::NextMatch | Action::Down | Action::Right) => app.step_entry(true),
+        Some(Action::PrevMatch | Action::Up | Action::Left) => app.step_entry(false),
+        _ => {}
+    }
+}
+
+/// Keys in the category sidebar. Returns whether to quit.
+fn handle_sidebar_key(app: &mut App, key: KeyEvent) -> bool {
+    if let KeyCode::Char(c @ '0'..='9') = key.code {
+        app.jump_to_category(c as usize - '0' as usize);
+        return false;
+    }
+    match app.keymap.action(key.code, key.modifiers) {
+        Some(Action::Quit) => return true,
+        Some(Action::Up) => {
+            app.set_category_position(app.category_position().saturating_sub(1));
+        }
+        Some(Action::Down) => {
+            let last = app.categories().len();
+            app.set_category_position((app.category_position() + 1).min(last));
+        }
+        Some(Action::Right | Action::Details | Action::Close) => app.focus = Focus::List,
+        Some(Action::Search) => {
+            app.query.clear();
+            app.history.reset();
+            app.apply_filter();
+            app.focus = Focus::Search;
+        }
+        Some(Action::Help) => app.open_help(),
+        _ => {}
+    }
+    false
+}
+
+/// Keys in reverse lookup mode are recorded instead of typed into the
+/// query; navigation keys still move through the results. Returns whether
+/// the key was handled.
+fn handle_lookup_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> bool {
+    let Some(typed) = &mut app.lookup else {
+        return false;
+    };
+    match code {
+        KeyCode::Esc => app.lookup = None,
+        KeyCode::Char('k') if modifiers.contains(KeyModifiers::CONTROL) => app.lookup = None,
+        KeyCode::Backspace => {
+            typed.pop();
+        }
+        KeyCode::Up
+        | KeyCode::Down
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Enter
+        | KeyCode::F(1) => return false,
+        _ => match keys::Key::from_event(code, modifiers) {
+            Some(key) => typed.push(key),
+            None => return false,
+        },
+    }
+    app.apply_filter();
+    true
+}
+
+/// Suspend the TUI and open the selected entry's help topic in Vim (or
+/// Neovim), restoring the alternate screen when the editor exits. Counts
+/// as a use; failures are shown in the status bar.
+fn open_help<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut App) {
+    app.record_use();
+    if let Err(err) = run_help(terminal, app) {
+        app.toasts.push(format!("{err:#}"));
+    }
+}
+
+fn run_help<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+) -> Result<()> {
+    let Some(topic) = app.selected_entry().and_then(|e| e.help_topic.clone()) else {
+        app.toasts.push("No help topic for this entry");
+        return Ok(());
+    };
+    let command = app.editor.help_command();
+    let (program, args) = command.split_first().expect("help command is never empty");
+    debug!("opening :help {topic} in {}", command.join(" "));
+
+    // Hand the terminal over in its normal state, and take it back even
+    // when the editor could not be started.
+    let status = suspend_tui().and_then(|()| {
+        process::Command::new(program)
+            .args(args)
+            .arg(format!("+help {topic}"))
+            .arg("+only")
+            .status()
+            .with_context(|| format!("failed to run {program}"))
+    });
+    resume_tui(terminal)?;
+
+    let status = status?;
+    if !status.success() {
+        app.toasts.push(format!("{program} exited with {status}"));
+    }
+    Ok(())
+}
+
+/// Leave raw mode and the alternate screen so another program can use the
+/// terminal.
+fn suspend_tui() -> Result<()> {
+    disable_raw_mode()?;
+    execute!(screen(), LeaveAlternateScreen, DisableMouseCapture, Show)?;
+    Ok(())
+}
+
+/// Take the terminal back after [`suspend_tui`] and redraw from scratch.
+fn resume_tui<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>) -> Result<()> {
+    enable_raw_mode()?;
+    execute!(screen(), EnterAlternateScreen, EnableMouseCapture, Hide)?;
+    terminal.clear()?;
+    Ok(())
+}
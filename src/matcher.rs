@@ -0,0 +1,178 @@
+//! fzf-style fuzzy matching: the pattern's characters must appear in order,
+//! and matches score higher the more of them are consecutive or start a
+//! word, so `chnge` finds "change" and `yi w` finds `yiw`.
+
+const SCORE_MATCH: i32 = 16;
+const BONUS_CONSECUTIVE: i32 = 8;
+const BONUS_BOUNDARY: i32 = 8;
+const PENALTY_GAP_START: i32 = 3;
+const PENALTY_GAP_EXTENSION: i32 = 1;
+const BONUS_PREFIX: i32 = 32;
+const BONUS_WORD_PREFIX: i32 = 24;
+const BONUS_INFIX: i32 = 16;
+
+/// A search term, lowercased once so it can be matched against many fields.
+#[derive(Debug)]
+pub struct Pattern {
+    text: String,
+    /// The characters to match in order; whitespace is ignored.
+    chars: Vec<char>,
+}
+
+impl Pattern {
+    pub fn new(term: &str) -> Self {
+        let text = term.to_lowercase();
+        let chars = text.chars().filter(|c| !c.is_whitespace()).collect();
+        Self { text, chars }
+    }
+}
+
+/// An entry field, lowercased once when the search index is built.
+#[derive(Debug)]
+pub struct Text {
+    text: String,
+    chars: Vec<char>,
+}
+
+impl Text {
+    pub fn new(field: &str) -> Self {
+        let text = field.to_lowercase();
+        let chars = text.chars().collect();
+        Self { text, chars }
+    }
+
+    /// The lowercased field.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Score `pattern` against `text`, or `None` when it does not match.
+fn score(pattern: &Pattern, text: &Text) -> Option<i32> {
+    let (pattern, text) = (&pattern.chars, &text.chars);
+    if pattern.is_empty() {
+        return Some(0);
+    }
+    let (start, end) = window(pattern, text)?;
+
+    let mut score = 0;
+    let mut pi = 0;
+    let mut prev_match: Option<usize> = None;
+    for ti in start..=end {
+        if pi == pattern.len() {
+            break;
+        }
+        if text[ti] != pattern[pi] {
+            continue;
+        }
+        score += SCORE_MATCH;
+        // Like fzf, the very start of the text counts double.
+        if ti == 0 {
+            score += 2 * BONUS_BOUNDARY;
+        } else if !text[ti - 1].is_alphanumeric() {
+            score += BONUS_BOUNDARY;
+        }
+        match prev_match {
+            Some(prev) if prev + 1 == ti => score += BONUS_CONSECUTIVE,
+            Some(prev) => {
+                let gap = (ti - prev - 1) as i32;
+                score -= PENALTY_GAP_START + PENALTY_GAP_EXTENSION * (gap - 1);
+            }
+            None => {}
+        }
+        prev_match = Some(ti);
+        pi += 1;
+    }
+    Some(score)
+}
+
+/// Character indices in `text` that `pattern` matches, for highlighting: a
+/// verbatim occurrence when there is one, otherwise the fuzzy match that
+/// `score` rates.
+pub fn positions(pattern: &str, text: &str) -> Option<Vec<usize>> {
+    let pattern: Vec<char> = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if pattern.is_empty() {
+        return Some(Vec::new());
+    }
+    // Lowercase per character so indices line up with `text`'s characters.
+    let text: Vec<char> = text
+        .chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect();
+    if let Some(start) = text
+        .windows(pattern.len())
+        .position(|w| w == pattern.as_slice())
+    {
+        return Some((start..start + pattern.len()).collect());
+    }
+    let (start, end) = window(&pattern, &text)?;
+    let mut pi = 0;
+    let mut matched = Vec::new();
+    for (ti, &c) in text.iter().enumerate().take(end + 1).skip(start) {
+        if pi < pattern.len() && c == pattern[pi] {
+            matched.push(ti);
+            pi += 1;
+        }
+    }
+    Some(matched)
+}
+
+/// The shortest stretch of `text` containing `pattern` in order: find where
+/// the first in-order occurrence ends, then walk back from there to the
+/// latest possible start.
+fn window(pattern: &[char], text: &[char]) -> Option<(usize, usize)> {
+    let mut pi = 0;
+    let mut end = None;
+    for (ti, &c) in text.iter().enumerate() {
+        if c == pattern[pi] {
+            pi += 1;
+            if pi == pattern.len() {
+                end = Some(ti);
+                break;
+            }
+        }
+    }
+    let end = end?;
+    let mut start = end;
+    let mut pi = pattern.len();
+    for ti in (0..=end).rev() {
+        if text[ti] == pattern[pi - 1] {
+            pi -= 1;
+            if pi == 0 {
+                start = ti;
+                break;
+            }
+        }
+    }
+    Some((start, end))
+}
+
+/// How well a single search term matches a field: the fuzzy score, boosted
+/// when the term is a literal prefix of the field, of one of its words, or
+/// appears in it verbatim. Both sides are compared case-insensitively.
+pub fn relevance(term: &str, field: &str) -> Option<i32> {
+    relevance_in(&Pattern::new(term), &Text::new(field))
+}
+
+/// [`relevance`] for a term and field that were already prepared.
+pub fn relevance_in(pattern: &Pattern, field: &Text) -> Option<i32> {
+    let score = score(pattern, field)?;
+    let (term, field) = (pattern.text.as_str(), field.as_str());
+    let bonus = if field.starts_with(term) {
+        BONUS_PREFIX
+    } else if field
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.starts_with(term))
+    {
+        BONUS_WORD_PREFIX
+    } else if field.contains(term) {
+        BONUS_INFIX
+    } else {
+        0
+    };
+    Some(score + bonus)
+}
@@ -0,0 +1,63 @@
+//! Precedence between built-in cheats and user-provided ones.
+//!
+//! A user entry whose command matches a built-in entry replaces it in place
+//! (or, with `annotate = true`, adds its description to the built-in one)
+//! instead of showing up twice. Every such case is recorded as a
+//! [`Conflict`] for the overrides report.
+
+use crate::{
+    keys::{self, Notation},
+    CheatEntry,
+};
+
+#[derive(Clone, Debug)]
+pub struct Conflict {
+    pub builtin: CheatEntry,
+    pub user: CheatEntry,
+    pub annotated: bool,
+}
+
+/// Merge `user` entries into `builtin` ones, returning the combined list
+/// and the conflicts that were resolved.
+pub fn merge(builtin: Vec<CheatEntry>, user: &[CheatEntry]) -> (Vec<CheatEntry>, Vec<Conflict>) {
+    let mut merged = builtin;
+    let builtin_len = merged.len();
+    let mut conflicts = Vec::new();
+
+    for entry in user {
+        let key = normalize(&entry.command);
+        let existing = merged[..builtin_len]
+            .iter_mut()
+            .find(|b| normalize(&b.command) == key);
+        let Some(existing) = existing else {
+            merged.push(entry.clone());
+            continue;
+        };
+
+        conflicts.push(Conflict {
+            builtin: existing.clone(),
+            user: entry.clone(),
+            annotated: entry.annotate,
+        });
+        if entry.annotate {
+            existing.description = format!("{} — {}", existing.description, entry.description);
+        } else {
+            // Stay in the built-in entry's tab.
+            let pack = existing.pack.take();
+            *existing = entry.clone();
+            existing.pack = pack;
+        }
+    }
+
+    (merged, conflicts)
+}
+
+/// Whether two commands are the same keys, ignoring differences in
+/// whitespace and notation (`<C-w>` vs `Ctrl + w`).
+pub fn same_command(a: &str, b: &str) -> bool {
+    normalize(a) == normalize(b)
+}
+
+fn normalize(command: &str) -> String {
+    keys::parse(command).render(Notation::Vim)
+}
@@ -0,0 +1,70 @@
+//! Questions typed in plain English ("how do I delete until the end of the
+//! line"), reduced to the words the cheatsheet's descriptions use: common
+//! phrasings are rewritten, filler words dropped and the rest stemmed.
+
+/// Words that start a question.
+const QUESTION_WORDS: &[&str] = &[
+    "how", "what", "which", "where", "when", "why", "can", "is", "are", "do", "does",
+];
+
+/// Words that say nothing about the command being looked for.
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "any", "are", "at", "be", "by", "can", "could", "do", "does", "for", "from",
+    "get", "how", "i", "in", "into", "is", "it", "its", "me", "my", "of", "on", "or", "please",
+    "should", "some", "that", "the", "there", "this", "to", "until", "till", "use", "vim", "want",
+    "way", "what", "when", "where", "which", "why", "with", "would", "you",
+];
+
+/// Phrasings mapped to the wording the descriptions use.
+const INTENTS: &[(&str, &str)] = &[
+    ("rest of the line", "end of line"),
+    ("beginning of the line", "start of line"),
+    ("top of the file", "first line of file"),
+    ("start of the file", "first line of file"),
+    ("beginning of the file", "first line of file"),
+    ("bottom of the file", "last line of file"),
+    ("end of the file", "last line of file"),
+    ("find and replace", "replace"),
+    ("search and replace", "replace"),
+    ("whole line", "line"),
+    ("entire line", "line"),
+    ("beginning", "start"),
+];
+
+/// Whether the lowercased `words` read as a question rather than a search.
+/// Short queries never do, so `i` or `a` still find insert commands.
+pub fn is_question(words: &[String]) -> bool {
+    words.len() >= 3
+        && (QUESTION_WORDS.contains(&words[0].as_str())
+            || words.last().is_some_and(|w| w.ends_with('?')))
+}
+
+/// The search words a question boils down to.
+pub fn simplify(words: &[String]) -> Vec<String> {
+    let mut text = format!(" {} ", words.join(" ").replace('?', ""));
+    for (phrase, meaning) in INTENTS {
+        text = text.replace(&format!(" {phrase} "), &format!(" {meaning} "));
+    }
+    text.split_whitespace()
+        .filter(|word| !STOPWORDS.contains(word))
+        .map(stem)
+        .collect()
+}
+
+/// Strip common inflections so "deleting" and "lines" match "delete" and
+/// "line"; matching is by prefix, so a clipped stem like "delet" is fine.
+fn stem(word: &str) -> String {
+    if let Some(base) = word.strip_suffix("ied") {
+        return format!("{base}y");
+    }
+    let base = if word.len() > 5 && word.ends_with("ing") {
+        &word[..word.len() - 3]
+    } else if word.len() > 4 && word.ends_with("ed") {
+        &word[..word.len() - 2]
+    } else if word.len() > 3 && word.ends_with('s') && !word.ends_with("ss") {
+        &word[..word.len() - 1]
+    } else {
+        word
+    };
+    base.to_string()
+}
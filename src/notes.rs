@@ -0,0 +1,156 @@
+//! Personal notes on entries, written from the TUI, shown in the detail
+//! pane and searched along with the description. Saved to `notes` in the
+//! data directory as one `category<TAB>command<TAB>note` line per entry.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use log::warn;
+
+use crate::{paths, store, CheatEntry};
+
+/// Notes by category, then command.
+type NoteMap = BTreeMap<String, BTreeMap<String, String>>;
+
+#[derive(Clone, Debug, Default)]
+pub struct Notes {
+    notes: NoteMap,
+    /// Notes written (`Some`) or removed (`None`) since the last save, by
+    /// category and command. Saving applies them to what other instances
+    /// saved meanwhile.
+    changed: BTreeMap<(String, String), Option<String>>,
+    /// Everything this instance wrote or removed a note on, which is all
+    /// undoing may take back.
+    touched: BTreeSet<(String, String)>,
+    /// Where to save, when persisting.
+    path: Option<PathBuf>,
+}
+
+impl Notes {
+    /// Notes that live only for this session.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Notes loaded from and saved to the data directory.
+    pub fn persistent() -> Result<Self> {
+        let path = paths::data_dir()?.join("notes");
+        Ok(Self {
+            notes: read(&path),
+            changed: BTreeMap::new(),
+            touched: BTreeSet::new(),
+            path: Some(path),
+        })
+    }
+
+    /// The note on `entry`, if it has one.
+    pub fn get(&self, entry: &CheatEntry) -> Option<&str> {
+        get(&self.notes, &entry.category, &entry.command)
+    }
+
+    /// Replace the note on `entry` with `note`, or remove it if `note` is
+    /// blank, and save. Tabs and line breaks become spaces.
+    pub fn set(&mut self, entry: &CheatEntry, note: &str) {
+        let note: String = note
+            .trim()
+            .chars()
+            .map(|c| if c == '\t' || c == '\n' || c == '\r' { ' ' } else { c })
+            .collect();
+        let note = (!note.is_empty()).then_some(note);
+        self.put(&entry.category, &entry.command, note);
+        if let Err(err) = self.save() {
+            warn!("failed to save notes: {err:#}");
+        }
+    }
+
+    /// Go back to `before`, a copy from earlier, and save. Only what this
+    /// instance changed since is undone, so notes from others stay.
+    pub fn restore(&mut self, before: Self) {
+        for (category, command) in self.touched.clone() {
+            let note = get(&before.notes, &category, &command);
+            if get(&self.notes, &category, &command) != note {
+                self.put(&category, &command, note.map(String::from));
+            }
+        }
+        if let Err(err) = self.save() {
+            warn!("failed to save notes: {err:#}");
+        }
+    }
+
+    /// Write the note on `command` in `category`, or remove it with `None`,
+    /// to be saved.
+    fn put(&mut self, category: &str, command: &str, note: Option<String>) {
+        set(&mut self.notes, category, command, note.clone());
+        let key = (category.to_string(), command.to_string());
+        self.touched.insert(key.clone());
+        self.changed.insert(key, note);
+    }
+
+    /// Apply the changes since the last save to the saved notes, and take
+    /// up what other instances wrote meanwhile.
+    fn save(&mut self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let notes = store::locked(path, || {
+            let mut notes = read(path);
+            for ((category, command), note) in &self.changed {
+                set(&mut notes, category, command, note.clone());
+            }
+            let text: String = notes
+                .iter()
+                .flat_map(|(category, commands)| {
+                    commands
+                        .iter()
+                        .map(move |(command, note)| format!("{category}\t{command}\t{note}\n"))
+                })
+                .collect();
+            store::write(path, &text)?;
+            Ok(notes)
+        })?;
+        self.notes = notes;
+        self.changed.clear();
+        Ok(())
+    }
+}
+
+/// The notes saved at `path`; none if it cannot be read.
+fn read(path: &Path) -> NoteMap {
+    let mut notes = NoteMap::new();
+    let Ok(text) = fs::read_to_string(path) else {
+        return notes;
+    };
+    for line in text.lines() {
+        let mut fields = line.splitn(3, '\t');
+        let (Some(category), Some(command), Some(note)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        set(&mut notes, category, command, Some(note.to_string()));
+    }
+    notes
+}
+
+fn get<'a>(notes: &'a NoteMap, category: &str, command: &str) -> Option<&'a str> {
+    notes
+        .get(category)
+        .and_then(|commands| commands.get(command))
+        .map(String::as_str)
+}
+
+/// Write the note on `command` in `category`, or remove it with `None`.
+fn set(notes: &mut NoteMap, category: &str, command: &str, note: Option<String>) {
+    let commands = notes.entry(category.to_string()).or_default();
+    match note {
+        Some(note) => commands.insert(command.to_string(), note),
+        None => commands.remove(command),
+    };
+    if commands.is_empty() {
+        notes.remove(category);
+    }
+}
@@ -0,0 +1,271 @@
+//! Non-interactive output (`vimbo list`): the entries matching the query and
+//! filters printed to stdout, for grepping in scripts or over SSH, without
+//! entering the TUI. Besides aligned text, entries can be printed as JSON,
+//! YAML or CSV records with stable field names for other tools. Text is
+//! colored like the TUI when printed to a terminal, or with `--color`.
+
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use crossterm::style::{Attribute, ContentStyle};
+use ratatui::style::{Modifier, Style};
+use serde::{Serialize, Serializer};
+
+use crate::{cheat::Difficulty, theme::Theme, CheatEntry};
+
+/// Columns other than the description are padded to at most this width.
+const MAX_COLUMN_WIDTH: usize = 24;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Aligned category, command and description columns.
+    #[default]
+    Text,
+    Json,
+    Yaml,
+    /// With a header row; tags are separated by spaces.
+    Csv,
+}
+
+/// When text output is colored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// When stdout is a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    /// Also through pipes, e.g. into `less -R`.
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether stdout gets colored.
+    fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => {
+                io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+            }
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+/// Colors of the text output: the styles of the TUI's theme as ANSI escape
+/// codes, or none at all.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Paint<'a>(Option<&'a Theme>);
+
+impl<'a> Paint<'a> {
+    /// Paint with `theme` if `color` says stdout gets colored.
+    pub fn new(theme: &'a Theme, color: ColorChoice) -> Self {
+        Self(color.enabled().then_some(theme))
+    }
+
+    /// `text` in the style `element` picks from the theme.
+    pub fn paint(self, text: &str, element: fn(&Theme) -> Style) -> String {
+        match self.0 {
+            Some(theme) if !text.is_empty() => ansi(element(theme)).apply(text).to_string(),
+            _ => text.to_string(),
+        }
+    }
+}
+
+/// `style` in crossterm's terms, which writes it as escape codes.
+fn ansi(style: Style) -> ContentStyle {
+    let mut ansi = ContentStyle {
+        foreground_color: style.fg.map(Into::into),
+        background_color: style.bg.map(Into::into),
+        ..Default::default()
+    };
+    for (modifier, attribute) in [
+        (Modifier::BOLD, Attribute::Bold),
+        (Modifier::DIM, Attribute::Dim),
+        (Modifier::ITALIC, Attribute::Italic),
+        (Modifier::UNDERLINED, Attribute::Underlined),
+        (Modifier::REVERSED, Attribute::Reverse),
+    ] {
+        if style.add_modifier.contains(modifier) {
+            ansi.attributes.set(attribute);
+        }
+    }
+    ansi
+}
+
+/// An entry as printed in the machine-readable formats. Its fields are
+/// kept stable, whatever changes in `CheatEntry`.
+#[derive(Serialize)]
+struct Record<'a> {
+    /// The query the entry matched, in a batch of several.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<&'a str>,
+    category: &'a str,
+    command: &'a str,
+    /// In the chosen language, where the entry has a translation.
+    description: &'a str,
+    mode: &'a str,
+    difficulty: Option<&'a str>,
+    tags: Tags<'a>,
+    /// Pack the entry comes from; `None` for the editor profile's own.
+    pack: Option<&'a str>,
+    help_topic: Option<&'a str>,
+}
+
+impl<'a> Record<'a> {
+    fn new(c: &'a CheatEntry, lang: Option<&str>, query: Option<&'a str>) -> Self {
+        Self {
+            query,
+            category: &c.category,
+            command: &c.command,
+            description: c.description_in(lang),
+            mode: c.mode().label(),
+            difficulty: c.difficulty.map(Difficulty::label),
+            tags: Tags {
+                tags: c.tags.iter().map(String::as_str).collect(),
+                joined: false,
+            },
+            pack: c.pack.as_deref(),
+            help_topic: c.help_topic.as_deref(),
+        }
+    }
+}
+
+/// A record's tags: a list, or for CSV, which has no lists, one field of
+/// space-separated tags.
+struct Tags<'a> {
+    tags: Vec<&'a str>,
+    joined: bool,
+}
+
+impl Serialize for Tags<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.joined {
+            serializer.serialize_str(&self.tags.join(" "))
+        } else {
+            self.tags.serialize(serializer)
+        }
+    }
+}
+
+/// Print `entries` to stdout in `format`.
+pub fn print(
+    entries: &[&CheatEntry],
+    lang: Option<&str>,
+    format: Format,
+    paint: Paint,
+) -> Result<()> {
+    write(&render(&[(None, entries.to_vec())], lang, format, paint)?)
+}
+
+/// Print the entries matching each of a batch of queries to stdout in
+/// `format`: as text under a heading per query, as records with the query
+/// they matched in a `query` field.
+pub fn print_batch(
+    batch: &[(&str, Vec<&CheatEntry>)],
+    lang: Option<&str>,
+    format: Format,
+    paint: Paint,
+) -> Result<()> {
+    let batch: Vec<_> = batch
+        .iter()
+        .map(|(query, entries)| (Some(*query), entries.clone()))
+        .collect();
+    write(&render(&batch, lang, format, paint)?)
+}
+
+/// The entries of `batch`, each with the query it matched if any, in
+/// `format`; only text is painted.
+fn render(
+    batch: &[(Option<&str>, Vec<&CheatEntry>)],
+    lang: Option<&str>,
+    format: Format,
+    paint: Paint,
+) -> Result<String> {
+    let records = || {
+        batch
+            .iter()
+            .flat_map(|(query, entries)| entries.iter().map(move |c| Record::new(c, lang, *query)))
+    };
+    let text = match format {
+        Format::Text => {
+            let mut out = String::new();
+            for (query, entries) in batch {
+                if let Some(query) = query {
+                    if !out.is_empty() {
+                        out.push('\n');
+                    }
+                    out.push_str(&paint.paint(&format!("# {query}"), |t| t.title));
+                    out.push('\n');
+                }
+                out.push_str(&text(entries, lang, paint));
+            }
+            out
+        }
+        Format::Json => {
+            let records: Vec<Record> = records().collect();
+            serde_json::to_string_pretty(&records).context("failed to write JSON")? + "\n"
+        }
+        Format::Yaml => {
+            let records: Vec<Record> = records().collect();
+            serde_norway::to_string(&records).context("failed to write YAML")?
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            for mut record in records() {
+                record.tags.joined = true;
+                writer.serialize(record).context("failed to write CSV")?;
+            }
+            let bytes = writer.into_inner().context("failed to write CSV")?;
+            String::from_utf8(bytes).context("failed to write CSV")?
+        }
+    };
+    Ok(text)
+}
+
+/// Write `text` to stdout.
+fn write(text: &str) -> Result<()> {
+    match io::stdout().lock().write_all(text.as_bytes()) {
+        // A closed pipe (e.g. `| head`) just means nobody reads the rest.
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result.context("failed to write to stdout"),
+    }
+}
+
+/// `entries` as aligned category, command and description columns, one
+/// entry per line.
+pub fn text(entries: &[&CheatEntry], lang: Option<&str>, paint: Paint) -> String {
+    let width = |column: fn(&CheatEntry) -> &str| {
+        entries
+            .iter()
+            .map(|c| column(c).chars().count())
+            .max()
+            .unwrap_or_default()
+            .min(MAX_COLUMN_WIDTH)
+    };
+    let category_width = width(|c| &c.category);
+    let command_width = width(|c| &c.command);
+    let mut out = String::new();
+    for c in entries {
+        let description = c.description_in(lang);
+        // Padded before painting, which the widths would count otherwise.
+        let category = format!("{:<category_width$}", c.category);
+        let command = if description.is_empty() {
+            c.command.clone()
+        } else {
+            format!("{:<command_width$}", c.command)
+        };
+        let line = format!(
+            "{}  {}  {}",
+            paint.paint(&category, |t| t.category),
+            paint.paint(&command, |t| t.command),
+            paint.paint(description, |t| t.description)
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
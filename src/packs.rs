@@ -0,0 +1,340 @@
+//! Cheat packs: bundled packs for popular plugins and companion tools, and
+//! local pack files in the data directory.
+//!
+//! Bundled packs are off by default and enabled via `--packs` or the `packs`
+//! key in the config file. Each pack's entries live under their own
+//! category. Local packs (`*.toml` in the packs directory) are always
+//! loaded.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::{cheat::Icon, config::Config, merge, paths, registry, store, CheatEntry, Mode};
+
+/// Name of the pack that entries added or copied from the TUI go to.
+pub const PERSONAL: &str = "personal";
+
+/// On-disk pack format.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PackFile {
+    pub name: String,
+    /// Where the entries came from (URL or file), if imported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Icons of the pack's categories, by category name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub icons: BTreeMap<String, Icon>,
+    #[serde(default)]
+    pub entries: Vec<CheatEntry>,
+}
+
+impl PackFile {
+    pub fn read(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read pack {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("invalid pack file {}", path.display()))
+    }
+
+    /// The pack's entries, each tagged with the pack name and its
+    /// category's icon.
+    pub fn into_entries(self) -> Vec<CheatEntry> {
+        let mut entries = self.entries;
+        for entry in &mut entries {
+            entry.pack = Some(self.name.clone());
+            entry.icon = self.icons.get(&entry.category).cloned();
+        }
+        entries
+    }
+
+    /// Write the pack into the packs directory as `<name>.toml`.
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = path_of(&self.name)?;
+        self.write(&path)?;
+        Ok(path)
+    }
+
+    fn write(&self, path: &Path) -> Result<()> {
+        store::write(path, &toml::to_string_pretty(self)?)
+    }
+}
+
+/// Where the pack `name` is saved in the packs directory.
+fn path_of(name: &str) -> Result<PathBuf> {
+    Ok(paths::packs_dir()?.join(format!("{name}.toml")))
+}
+
+/// Where the personal pack is saved.
+pub fn personal_path() -> Result<PathBuf> {
+    path_of(PERSONAL)
+}
+
+/// Put back the pack file at `path` as it was: with `text`, or removed
+/// when it did not exist.
+pub fn restore_file(path: &Path, text: Option<&str>) -> Result<()> {
+    match text {
+        Some(text) => store::write(path, text),
+        None => fs::remove_file(path).map_err(Into::into),
+    }
+    .with_context(|| format!("failed to restore {}", path.display()))
+}
+
+/// Change the entry of the pack file at `path` with `category` and
+/// `command` by `apply`, and save the file. Returns the changed entry, as
+/// loaded.
+pub fn update_entry(
+    path: &Path,
+    category: &str,
+    command: &str,
+    apply: impl FnOnce(&mut CheatEntry),
+) -> Result<CheatEntry> {
+    let mut pack = PackFile::read(path)?;
+    let Some(entry) = pack
+        .entries
+        .iter_mut()
+        .find(|e| e.category == category && e.command == command)
+    else {
+        bail!("'{command}' is no longer in {}", path.display());
+    };
+    apply(entry);
+    let mut saved = entry.clone();
+    pack.write(path)?;
+    saved.pack = Some(pack.name.clone());
+    saved.icon = pack.icons.get(&saved.category).cloned();
+    saved.file = Some(path.to_path_buf());
+    Ok(saved)
+}
+
+/// Add `entry` to the personal pack, creating it if needed, in place of
+/// any entry there with the same command. Returns the entry, as loaded.
+pub fn add_personal(mut entry: CheatEntry) -> Result<CheatEntry> {
+    let path = personal_path()?;
+    let mut pack = if path.exists() {
+        PackFile::read(&path)?
+    } else {
+        PackFile {
+            name: PERSONAL.to_string(),
+            ..PackFile::default()
+        }
+    };
+    entry.pack = None;
+    entry.icon = None;
+    entry.file = None;
+    pack.entries
+        .retain(|e| !merge::same_command(&e.command, &entry.command));
+    pack.entries.push(entry.clone());
+    pack.write(&path)?;
+    entry.pack = Some(pack.name.clone());
+    entry.icon = pack.icons.get(&entry.category).cloned();
+    entry.file = Some(path);
+    Ok(entry)
+}
+
+pub struct BundledPack {
+    pub name: &'static str,
+    entries: fn() -> Vec<CheatEntry>,
+}
+
+pub const BUNDLED: &[BundledPack] = &[
+    BundledPack {
+        name: "surround",
+        entries: surround,
+    },
+    BundledPack {
+        name: "fugitive",
+        entries: fugitive,
+    },
+    BundledPack {
+        name: "commentary",
+        entries: commentary,
+    },
+    BundledPack {
+        name: "nerdtree",
+        entries: nerdtree,
+    },
+    BundledPack {
+        name: "tmux",
+        entries: tmux,
+    },
+];
+
+/// Collect the entries of every named pack, in the given order.
+pub fn load(names: &[String]) -> Result<Vec<CheatEntry>> {
+    let mut entries = Vec::new();
+    for name in names {
+        let name = name.trim().to_lowercase();
+        let Some(pack) = BUNDLED.iter().find(|p| p.name == name) else {
+            let known: Vec<_> = BUNDLED.iter().map(|p| p.name).collect();
+            bail!("unknown pack '{}' (available: {})", name, known.join(", "));
+        };
+        entries.extend((pack.entries)().into_iter().map(|mut entry| {
+            entry.pack = Some(pack.name.to_string());
+            entry
+        }));
+    }
+    Ok(entries)
+}
+
+/// Load every `*.toml` pack in the packs directory. Broken files are
+/// skipped with a warning so one bad pack doesn't keep the app from starting.
+pub fn load_local() -> Result<Vec<CheatEntry>> {
+    let mut entries = Vec::new();
+    for path in local_files()? {
+        match PackFile::read(&path) {
+            Ok(pack) => {
+                debug!("loaded pack '{}' ({} entries)", pack.name, pack.entries.len());
+                entries.extend(pack.into_entries().into_iter().map(|mut entry| {
+                    entry.file = Some(path.clone());
+                    entry
+                }));
+            }
+            Err(err) => warn!("skipping pack: {err:#}"),
+        }
+    }
+    Ok(entries)
+}
+
+/// The `*.toml` files in the packs directory, sorted.
+fn local_files() -> Result<Vec<PathBuf>> {
+    let dir = paths::packs_dir()?;
+    let Ok(read_dir) = fs::read_dir(&dir) else {
+        debug!("no packs directory at {}", dir.display());
+        return Ok(Vec::new());
+    };
+    let mut files: Vec<PathBuf> = read_dir
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// `vimbo packs`: the bundled packs, marking those the config file
+/// enables, then the installed and local ones.
+pub fn print_list() -> Result<()> {
+    let enabled = Config::load()?.packs;
+    println!("Bundled (enable with --packs or `packs` in the config file):");
+    for pack in BUNDLED {
+        let on = enabled.iter().any(|name| name.trim().eq_ignore_ascii_case(pack.name));
+        println!(
+            "  {:<16} {:>4} entries{}",
+            pack.name,
+            (pack.entries)().len(),
+            if on { "  enabled" } else { "" }
+        );
+    }
+    registry::print_installed()?;
+    let files = local_files()?;
+    if !files.is_empty() {
+        println!("\nLocal ({}):", paths::packs_dir()?.display());
+    }
+    for path in files {
+        let file = path.file_name().unwrap_or_default().to_string_lossy();
+        match PackFile::read(&path) {
+            Ok(pack) => println!("  {:<16} {:>4} entries  {file}", pack.name, pack.entries.len()),
+            Err(err) => println!("  {file}: {err:#}"),
+        }
+    }
+    Ok(())
+}
+
+fn surround() -> Vec<CheatEntry> {
+    vec![
+        CheatEntry::new("vim-surround", "ys{motion}{char}", "surround text covered by motion with {char}"),
+        CheatEntry::new("vim-surround", "ysiw\"", "surround inner word with double quotes"),
+        CheatEntry::new("vim-surround", "yss{char}", "surround current line with {char}"),
+        CheatEntry::new("vim-surround", "cs{old}{new}", "change surrounding {old} to {new} (e.g. cs\"')"),
+        CheatEntry::new("vim-surround", "ds{char}", "delete surrounding {char} (e.g. ds( )"),
+        CheatEntry::new("vim-surround", "cst{char}", "change surrounding HTML tag to {char}"),
+        CheatEntry::new("vim-surround", "dst", "delete surrounding HTML tag"),
+        CheatEntry::new("vim-surround", "S{char}", "surround visual selection with {char}").in_mode(Mode::Visual),
+        CheatEntry::new("vim-surround", "ysiw<em>", "wrap inner word in an <em> tag"),
+    ]
+}
+
+fn fugitive() -> Vec<CheatEntry> {
+    vec![
+        CheatEntry::new("vim-fugitive", ":Git / :G", "open the git status summary window"),
+        CheatEntry::new("vim-fugitive", ":Git {args}", "run any git command (e.g. :Git log)"),
+        CheatEntry::new("vim-fugitive", ":Git blame", "interactive blame of the current file"),
+        CheatEntry::new("vim-fugitive", ":Gdiffsplit", "diff working copy against the index"),
+        CheatEntry::new("vim-fugitive", ":Gread", "revert buffer to the index version (git checkout)"),
+        CheatEntry::new("vim-fugitive", ":Gwrite", "write buffer and stage it (git add)"),
+        CheatEntry::new("vim-fugitive", ":GMove {dest}", "git mv the current file and rename the buffer"),
+        CheatEntry::new("vim-fugitive", ":GBrowse", "open the current file on the git host"),
+        CheatEntry::new("vim-fugitive", "s / u / - (status)", "stage / unstage / toggle file in status window"),
+        CheatEntry::new("vim-fugitive", "cc / ca (status)", "commit / amend from the status window"),
+        CheatEntry::new("vim-fugitive", "= (status)", "toggle inline diff of file under cursor"),
+    ]
+}
+
+fn commentary() -> Vec<CheatEntry> {
+    vec![
+        CheatEntry::new("vim-commentary", "gcc", "toggle comment on current line"),
+        CheatEntry::new("vim-commentary", "gc{motion}", "toggle comments over a motion (e.g. gcap)"),
+        CheatEntry::new("vim-commentary", "gc (visual)", "toggle comments on the selection").in_mode(Mode::Visual),
+        CheatEntry::new("vim-commentary", "gcgc / gcu", "uncomment adjacent commented lines"),
+        CheatEntry::new("vim-commentary", ":{range}Commentary", "toggle comments over a range of lines"),
+    ]
+}
+
+fn nerdtree() -> Vec<CheatEntry> {
+    vec![
+        CheatEntry::new("NERDTree", ":NERDTreeToggle", "open / close the file tree"),
+        CheatEntry::new("NERDTree", ":NERDTreeFind", "reveal the current file in the tree"),
+        CheatEntry::new("NERDTree", "o / Enter", "open file or expand directory"),
+        CheatEntry::new("NERDTree", "t / T", "open in new tab / silently in new tab"),
+        CheatEntry::new("NERDTree", "i / s", "open in horizontal / vertical split"),
+        CheatEntry::new("NERDTree", "m", "show file system menu (add, move, delete)"),
+        CheatEntry::new("NERDTree", "I", "toggle hidden files"),
+        CheatEntry::new("NERDTree", "R", "refresh the tree root"),
+        CheatEntry::new("NERDTree", "C", "make the directory under cursor the tree root"),
+        CheatEntry::new("NERDTree", "?", "toggle NERDTree quick help"),
+    ]
+}
+
+/// tmux with the default `Ctrl + b` prefix.
+fn tmux() -> Vec<CheatEntry> {
+    vec![
+        CheatEntry::new("tmux - sessions", "tmux new -s {name}", "start a new named session"),
+        CheatEntry::new("tmux - sessions", "tmux attach -t {name}", "attach to an existing session"),
+        CheatEntry::new("tmux - sessions", "tmux ls", "list sessions"),
+        CheatEntry::new("tmux - sessions", "prefix d", "detach from the current session"),
+        CheatEntry::new("tmux - sessions", "prefix s", "choose a session from a list"),
+        CheatEntry::new("tmux - sessions", "prefix $", "rename the current session"),
+
+        CheatEntry::new("tmux - windows", "prefix c", "create a new window"),
+        CheatEntry::new("tmux - windows", "prefix n / prefix p", "next / previous window"),
+        CheatEntry::new("tmux - windows", "prefix {n}", "select window {n}"),
+        CheatEntry::new("tmux - windows", "prefix w", "choose a window from a tree"),
+        CheatEntry::new("tmux - windows", "prefix ,", "rename the current window"),
+        CheatEntry::new("tmux - windows", "prefix &", "kill the current window"),
+
+        CheatEntry::new("tmux - panes", "prefix % / prefix \"", "split pane vertically / horizontally"),
+        CheatEntry::new("tmux - panes", "prefix arrow", "move to the pane in that direction"),
+        CheatEntry::new("tmux - panes", "prefix o / prefix ;", "next pane / last active pane"),
+        CheatEntry::new("tmux - panes", "prefix z", "zoom / unzoom the current pane"),
+        CheatEntry::new("tmux - panes", "prefix x", "kill the current pane"),
+        CheatEntry::new("tmux - panes", "prefix { / prefix }", "swap pane with previous / next"),
+        CheatEntry::new("tmux - panes", "prefix Space", "cycle through pane layouts"),
+        CheatEntry::new("tmux - panes", "prefix Ctrl + arrow", "resize pane in that direction"),
+        CheatEntry::new("tmux - panes", "prefix q", "show pane numbers"),
+
+        CheatEntry::new("tmux - copy mode", "prefix [", "enter copy mode (scrollback)"),
+        CheatEntry::new("tmux - copy mode", "Space / Enter", "start selection / copy selection (vi mode-keys)"),
+        CheatEntry::new("tmux - copy mode", "v / y", "start selection / yank (with vi copy-mode bindings)"),
+        CheatEntry::new("tmux - copy mode", "/ / ?", "search forward / backward in copy mode"),
+        CheatEntry::new("tmux - copy mode", "prefix ]", "paste the most recent buffer"),
+        CheatEntry::new("tmux - copy mode", "set -g mode-keys vi", "use Vim keys in copy mode (tmux.conf)"),
+
+        CheatEntry::new("tmux - misc", "prefix :", "open the tmux command prompt"),
+        CheatEntry::new("tmux - misc", "prefix ?", "list all key bindings"),
+        CheatEntry::new("tmux - misc", "prefix r / tmux source ~/.tmux.conf", "reload config (prefix r if bound)"),
+    ]
+}
@@ -0,0 +1,228 @@
+//! The command palette (Ctrl+p): every app action listed by name, picked by
+//! fuzzy search, so vimbo's own keys need not be memorized.
+
+use crate::{
+    keymap::{Action, Keymap},
+    matcher,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    NewSearch,
+    Favorite,
+    FavoritesOnly,
+    Recent,
+    Hide,
+    ShowHidden,
+    ManageHidden,
+    Undo,
+    Redo,
+    Mark,
+    Copy,
+    Export,
+    Edit,
+    Note,
+    Add,
+    ClearMarks,
+    Group,
+    CollapseAll,
+    ExpandAll,
+    Table,
+    Sort,
+    ReverseSort,
+    Density,
+    Numbers,
+    Zen,
+    NextTab,
+    PreviousTab,
+    Level,
+    Mode,
+    Category,
+    Editor,
+    Theme,
+    Lookup,
+    Conflicts,
+    Stats,
+    VimHelp,
+    Help,
+    Quit,
+}
+
+/// Keys a command is run with outside the palette.
+enum Keys {
+    Fixed(&'static str),
+    Bound(Action),
+    None,
+}
+
+impl Command {
+    /// All commands, in the order the palette lists them before anything
+    /// is typed.
+    const ALL: &[Command] = &[
+        Command::NewSearch,
+        Command::Favorite,
+        Command::FavoritesOnly,
+        Command::Recent,
+        Command::Hide,
+        Command::ShowHidden,
+        Command::ManageHidden,
+        Command::Undo,
+        Command::Redo,
+        Command::Mark,
+        Command::Copy,
+        Command::Export,
+        Command::Edit,
+        Command::Note,
+        Command::Add,
+        Command::ClearMarks,
+        Command::Group,
+        Command::CollapseAll,
+        Command::ExpandAll,
+        Command::Table,
+        Command::Sort,
+        Command::ReverseSort,
+        Command::Density,
+        Command::Numbers,
+        Command::Zen,
+        Command::NextTab,
+        Command::PreviousTab,
+        Command::Level,
+        Command::Mode,
+        Command::Category,
+        Command::Editor,
+        Command::Theme,
+        Command::Lookup,
+        Command::Conflicts,
+        Command::Stats,
+        Command::VimHelp,
+        Command::Help,
+        Command::Quit,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Command::NewSearch => "New search",
+            Command::Favorite => "Toggle favorite (star) on the selected entry, or star the marked",
+            Command::Mark => "Mark / unmark the selected entry for batch actions",
+            Command::Copy => "Copy the marked (or selected) commands to the clipboard",
+            Command::Export => "Export the marked (or selected) entries to Markdown",
+            Command::Edit => "Edit the selected entry (built-in ones in your personal pack)",
+            Command::Note => "Write a note on the selected entry (searched too)",
+            Command::Add => "Add a new entry to your personal pack",
+            Command::ClearMarks => "Clear all marks",
+            Command::FavoritesOnly => "Toggle favorites only",
+            Command::Recent => "Toggle recently opened or copied entries",
+            Command::Hide => "Hide / unhide the selected (or marked) entries",
+            Command::ShowHidden => "Toggle showing hidden entries",
+            Command::ManageHidden => "Manage hidden entries: list them all and unhide",
+            Command::Undo => "Undo the last star, hide, note, edit or add",
+            Command::Redo => "Redo the last undone change",
+            Command::Group => "Toggle grouping by category",
+            Command::CollapseAll => "Collapse all groups",
+            Command::ExpandAll => "Expand all groups",
+            Command::Table => "Toggle table view",
+            Command::Sort => "Sort by next order (list) or column (table)",
+            Command::ReverseSort => "Reverse sort order",
+            Command::Density => "Toggle row density (compact / comfortable)",
+            Command::Numbers => "Cycle row numbers (off / absolute / relative)",
+            Command::Zen => "Focus mode: one entry at a time, large",
+            Command::NextTab => "Next tab",
+            Command::PreviousTab => "Previous tab",
+            Command::Level => "Cycle difficulty level filter",
+            Command::Mode => "Cycle mode filter",
+            Command::Category => "Cycle category filter",
+            Command::Editor => "Switch editor profile",
+            Command::Theme => "Next theme preset",
+            Command::Lookup => "Reverse key lookup",
+            Command::Conflicts => "Toggle overridden built-in entries report",
+            Command::Stats => "Toggle usage statistics: most looked up and copied, top searches",
+            Command::VimHelp => "Open the entry's :help in Vim",
+            Command::Help => "Show help with all key bindings",
+            Command::Quit => "Quit",
+        }
+    }
+
+    fn keys(self) -> Keys {
+        match self {
+            Command::NewSearch => Keys::Bound(Action::Search),
+            Command::Favorite => Keys::Bound(Action::Favorite),
+            Command::FavoritesOnly => Keys::Bound(Action::FavoritesOnly),
+            Command::Recent => Keys::Bound(Action::Recent),
+            Command::Hide => Keys::Bound(Action::Hide),
+            Command::ShowHidden => Keys::Bound(Action::ShowHidden),
+            Command::ManageHidden => Keys::Fixed("gH"),
+            Command::Undo => Keys::Bound(Action::Undo),
+            Command::Redo => Keys::Fixed("Ctrl + r"),
+            Command::Mark => Keys::Bound(Action::Mark),
+            Command::Copy => Keys::Bound(Action::Copy),
+            Command::Export => Keys::None,
+            Command::Edit => Keys::Bound(Action::Edit),
+            Command::Note => Keys::Bound(Action::Note),
+            Command::Add => Keys::Bound(Action::Add),
+            Command::ClearMarks => Keys::Bound(Action::Close),
+            Command::Group => Keys::Fixed("zi"),
+            Command::CollapseAll => Keys::Fixed("zM"),
+            Command::ExpandAll => Keys::Fixed("zR"),
+            Command::Table => Keys::Bound(Action::Table),
+            Command::Sort => Keys::Bound(Action::Sort),
+            Command::ReverseSort => Keys::Bound(Action::ReverseSort),
+            Command::Density => Keys::Bound(Action::Density),
+            Command::Numbers => Keys::None,
+            Command::Zen => Keys::Bound(Action::Zen),
+            Command::NextTab => Keys::Fixed("gt"),
+            Command::PreviousTab => Keys::Fixed("gT"),
+            Command::Level => Keys::Fixed("Ctrl + l"),
+            Command::Mode => Keys::Fixed("Tab"),
+            Command::Category => Keys::Fixed("Ctrl + t"),
+            Command::Editor => Keys::Fixed("Ctrl + e"),
+            Command::Theme => Keys::None,
+            Command::Lookup => Keys::Fixed("Ctrl + k"),
+            Command::Conflicts => Keys::Fixed("Ctrl + o"),
+            Command::Stats => Keys::None,
+            Command::VimHelp => Keys::Fixed("F1"),
+            Command::Help => Keys::Bound(Action::Help),
+            Command::Quit => Keys::Bound(Action::Quit),
+        }
+    }
+
+    /// Keys that run the command from the list, shown next to its label.
+    pub fn describe_keys(self, keymap: &Keymap) -> String {
+        match self.keys() {
+            Keys::Fixed(keys) => keys.to_string(),
+            Keys::Bound(action) => keymap.describe(action),
+            Keys::None => String::new(),
+        }
+    }
+}
+
+/// What is typed into the open palette and which match is picked.
+#[derive(Debug, Default)]
+pub struct Palette {
+    pub query: String,
+    pub selected: usize,
+}
+
+impl Palette {
+    /// Commands whose label matches every word of the query, best first.
+    pub fn matches(&self) -> Vec<Command> {
+        let mut scored: Vec<(i32, Command)> = Command::ALL
+            .iter()
+            .filter_map(|&command| {
+                let score = self
+                    .query
+                    .split_whitespace()
+                    .map(|word| matcher::relevance(word, command.label()).filter(|&s| s > 0))
+                    .sum::<Option<i32>>()?;
+                Some((score, command))
+            })
+            .collect();
+        // Stable, so equally good matches keep their listed order.
+        scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        scored.into_iter().map(|(_, command)| command).collect()
+    }
+
+    /// The command Enter would run.
+    pub fn selected_command(&self) -> Option<Command> {
+        self.matches().get(self.selected).copied()
+    }
+}
@@ -0,0 +1,150 @@
+//! Sizes of the category sidebar and the detail pane, adjusted with
+//! Ctrl+arrows and saved to `panes` in the data directory as
+//! `sidebar=<columns>` and `detail=<percent>` lines.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use log::warn;
+
+use crate::{paths, store};
+
+/// Columns the sidebar grows or shrinks by per key press.
+const SIDEBAR_STEP: u16 = 2;
+const SIDEBAR_MIN: u16 = 10;
+const SIDEBAR_MAX: u16 = 60;
+/// Percent of the list area the detail pane grows or shrinks by.
+const DETAIL_STEP: u16 = 5;
+const DETAIL_MIN: u16 = 20;
+const DETAIL_MAX: u16 = 80;
+const DETAIL_DEFAULT: u16 = 50;
+
+/// One of the resizable panes.
+#[derive(Clone, Copy, Debug)]
+enum Pane {
+    Sidebar,
+    Detail,
+}
+
+#[derive(Debug)]
+pub struct Panes {
+    /// Sidebar width in columns; `None` fits it to the category names.
+    sidebar: Option<u16>,
+    /// Share of the height below the search bar taken by the detail pane.
+    detail: u16,
+    /// Where to save, when persisting.
+    path: Option<PathBuf>,
+}
+
+impl Default for Panes {
+    fn default() -> Self {
+        Self {
+            sidebar: None,
+            detail: DETAIL_DEFAULT,
+            path: None,
+        }
+    }
+}
+
+impl Panes {
+    /// Pane sizes that live only for this session.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Pane sizes loaded from and saved to the data directory.
+    pub fn persistent() -> Result<Self> {
+        let path = paths::data_dir()?.join("panes");
+        let mut panes = read(&path);
+        panes.path = Some(path);
+        Ok(panes)
+    }
+
+    /// Sidebar width, or `fit` when it was never resized.
+    pub fn sidebar_width(&self, fit: u16) -> u16 {
+        self.sidebar.unwrap_or(fit)
+    }
+
+    /// Percent of the list area given to the detail pane.
+    pub fn detail_percent(&self) -> u16 {
+        self.detail
+    }
+
+    /// Widen or narrow the sidebar, starting from its `current` width.
+    pub fn resize_sidebar(&mut self, current: u16, grow: bool) {
+        let width = if grow {
+            current.saturating_add(SIDEBAR_STEP)
+        } else {
+            current.saturating_sub(SIDEBAR_STEP)
+        };
+        self.sidebar = Some(width.clamp(SIDEBAR_MIN, SIDEBAR_MAX));
+        self.save_or_warn(Pane::Sidebar);
+    }
+
+    /// Make the detail pane taller or shorter.
+    pub fn resize_detail(&mut self, grow: bool) {
+        let percent = if grow {
+            self.detail + DETAIL_STEP
+        } else {
+            self.detail.saturating_sub(DETAIL_STEP)
+        };
+        self.detail = percent.clamp(DETAIL_MIN, DETAIL_MAX);
+        self.save_or_warn(Pane::Detail);
+    }
+
+    fn save_or_warn(&mut self, changed: Pane) {
+        if let Err(err) = self.save(changed) {
+            warn!("failed to save pane sizes: {err:#}");
+        }
+    }
+
+    /// Save the size of the `changed` pane, and take up the other's as
+    /// another instance may have saved it meanwhile.
+    fn save(&mut self, changed: Pane) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let saved = store::locked(path, || {
+            let mut saved = read(path);
+            match changed {
+                Pane::Sidebar => saved.sidebar = self.sidebar,
+                Pane::Detail => saved.detail = self.detail,
+            }
+            let mut text = String::new();
+            if let Some(sidebar) = saved.sidebar {
+                text.push_str(&format!("sidebar={sidebar}\n"));
+            }
+            text.push_str(&format!("detail={}\n", saved.detail));
+            store::write(path, &text)?;
+            Ok(saved)
+        })?;
+        self.sidebar = saved.sidebar;
+        self.detail = saved.detail;
+        Ok(())
+    }
+}
+
+/// The pane sizes saved at `path`; the defaults if it cannot be read.
+fn read(path: &Path) -> Panes {
+    let mut panes = Panes::default();
+    let Ok(text) = fs::read_to_string(path) else {
+        return panes;
+    };
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let Ok(value) = value.trim().parse::<u16>() else {
+            continue;
+        };
+        match key.trim() {
+            "sidebar" => panes.sidebar = Some(value.clamp(SIDEBAR_MIN, SIDEBAR_MAX)),
+            "detail" => panes.detail = value.clamp(DETAIL_MIN, DETAIL_MAX),
+            _ => {}
+        }
+    }
+    panes
+}
@@ -0,0 +1,79 @@
+//! Where vimbo keeps its files on disk: the platform's directories (XDG
+//! on Linux, `~/Library/Application Support` on macOS, `%APPDATA%` on
+//! Windows), unless `--config` and `--data-dir` (or `VIMBO_CONFIG` and
+//! `VIMBO_DATA_DIR`) point elsewhere, e.g. to run from a USB stick. A
+//! `--profile` keeps its own config file and data directory under
+//! `profiles/<name>` in each.
+
+use std::{path::PathBuf, sync::OnceLock};
+
+use anyhow::{bail, Context, Result};
+
+/// Locations given on the command line or in the environment, which take
+/// the place of the platform's.
+#[derive(Debug, Default)]
+pub struct Overrides {
+    /// The config file.
+    pub config: Option<PathBuf>,
+    /// The data directory, holding packs, favorites and the other state.
+    pub data_dir: Option<PathBuf>,
+    /// Named profile whose config and data are used where not given.
+    pub profile: Option<String>,
+}
+
+static OVERRIDES: OnceLock<Overrides> = OnceLock::new();
+
+/// Use `overrides` for the rest of the run. Only the first call counts.
+/// Fails on profile names that are not a plain directory name.
+pub fn set_overrides(overrides: Overrides) -> Result<()> {
+    if let Some(name) = &overrides.profile
+        && (name.is_empty()
+            || name.starts_with('.')
+            || name.contains(|c: char| c == '/' || c == '\\' || c.is_control()))
+    {
+        bail!("invalid profile name '{name}' (use letters, digits, '-' or '_')");
+    }
+    let _ = OVERRIDES.set(overrides);
+    Ok(())
+}
+
+/// The profile in use, if any.
+pub fn profile() -> Option<&'static str> {
+    OVERRIDES.get().and_then(|o| o.profile.as_deref())
+}
+
+/// `dir`, or its directory for the profile in use.
+fn in_profile(dir: PathBuf) -> PathBuf {
+    match profile() {
+        Some(name) => dir.join("profiles").join(name),
+        None => dir,
+    }
+}
+
+/// The config file given with `--config`, if any.
+pub fn config_override() -> Option<PathBuf> {
+    OVERRIDES.get().and_then(|o| o.config.clone())
+}
+
+/// Location of the config file (e.g. `~/.config/vimbo/config.toml`), if
+/// given or the platform has a config directory.
+pub fn config_file() -> Option<PathBuf> {
+    config_override().or_else(|| {
+        dirs::config_dir().map(|dir| in_profile(dir.join("vimbo")).join("config.toml"))
+    })
+}
+
+/// Per-user data directory (e.g. `~/.local/share/vimbo`).
+pub fn data_dir() -> Result<PathBuf> {
+    if let Some(dir) = OVERRIDES.get().and_then(|o| o.data_dir.clone()) {
+        return Ok(dir);
+    }
+    dirs::data_dir()
+        .map(|dir| in_profile(dir.join("vimbo")))
+        .context("could not determine the user data directory")
+}
+
+/// Directory holding user and imported cheat packs.
+pub fn packs_dir() -> Result<PathBuf> {
+    Ok(data_dir()?.join("packs"))
+}
@@ -0,0 +1,92 @@
+//! Parsing of the search bar text into free text and filters.
+
+use crate::natural;
+
+/// A parsed search query.
+#[derive(Debug, Default)]
+pub struct Query {
+    /// Lowercased free text, matched against the entry text.
+    pub text: String,
+    /// `#tag` filters (lowercased, without the `#`); all must match.
+    pub tags: Vec<String>,
+    /// `cmd:`, `desc:` and `cat:` terms matched against a single field.
+    pub scoped: Vec<(Field, String)>,
+    /// `-word` terms (lowercased); entries containing any of them are hidden.
+    pub excluded: Vec<String>,
+    /// `-#tag` filters; entries with a matching tag are hidden.
+    pub excluded_tags: Vec<String>,
+}
+
+/// An entry field a query term can be restricted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    Command,
+    Description,
+    Category,
+}
+
+impl Field {
+    pub fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "cmd" | "command" => Some(Field::Command),
+            "desc" | "description" => Some(Field::Description),
+            "cat" | "category" => Some(Field::Category),
+            _ => None,
+        }
+    }
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Self {
+        let mut query = Query::default();
+        let mut words = Vec::new();
+        for word in input.split_whitespace() {
+            if let Some(negated) = word.strip_prefix('-')
+                && !negated.is_empty()
+            {
+                match negated.strip_prefix('#') {
+                    Some(tag) if !tag.is_empty() => query.excluded_tags.push(tag.to_lowercase()),
+                    Some(_) => {}
+                    None => query.excluded.push(negated.to_lowercase()),
+                }
+            } else if let Some(tag) = word.strip_prefix('#')
+                && !tag.is_empty()
+            {
+                query.tags.push(tag.to_lowercase());
+            } else if let Some((prefix, term)) = word.split_once(':')
+                && !term.is_empty()
+                && let Some(field) = Field::from_prefix(&prefix.to_lowercase())
+            {
+                query.scoped.push((field, term.to_lowercase()));
+            } else {
+                words.push(word.to_lowercase());
+            }
+        }
+        if natural::is_question(&words) {
+            words = natural::simplify(&words);
+        }
+        query.text = words.join(" ");
+        query
+    }
+
+    /// Whether there is anything to match besides tags.
+    pub fn has_terms(&self) -> bool {
+        !self.text.is_empty() || !self.scoped.is_empty()
+    }
+
+    /// Whether an entry with the lowercased `tags` passes the tag filters. A
+    /// filter matches any tag it is a prefix of, so `#text` finds
+    /// `text-objects`.
+    pub fn matches_tags(&self, tags: &[String]) -> bool {
+        let has = |want: &String| tags.iter().any(|t| t.starts_with(want.as_str()));
+        self.tags.iter().all(has) && !self.excluded_tags.iter().any(has)
+    }
+
+    /// Whether any of the lowercased `fields` contains an excluded `-word`.
+    /// Exclusion is literal rather than fuzzy, so it only hides what it names.
+    pub fn excludes(&self, fields: &[&str]) -> bool {
+        self.excluded
+            .iter()
+            .any(|word| fields.iter().any(|field| field.contains(word.as_str())))
+    }
+}
@@ -0,0 +1,93 @@
+//! `vimbo quiz`: drill on the cheatsheet over plain stdin and stdout. Each
+//! question shows a description and asks for its command; answers count
+//! when they are the command's keys, in any notation vimbo understands.
+
+use std::io::{self, BufRead, Write};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{
+    keys::{self, Keys, Token, Typed},
+    tip, CheatEntry,
+};
+
+/// Ask about `count` random ones of `entries` and print the score. Ends
+/// early, still scoring, when stdin does.
+pub fn run(entries: &[&CheatEntry], lang: Option<&str>, count: usize) -> Result<()> {
+    // Two entries described alike would make a question with two answers.
+    let mut pool: Vec<&CheatEntry> = entries
+        .iter()
+        .copied()
+        .filter(|c| {
+            entries
+                .iter()
+                .filter(|other| other.description_in(lang) == c.description_in(lang))
+                .count()
+                == 1
+        })
+        .collect();
+    if pool.is_empty() {
+        bail!("no entries match");
+    }
+    let count = count.min(pool.len());
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut stdout = io::stdout().lock();
+    let mut asked = 0;
+    let mut correct = 0;
+    while asked < count {
+        let c = pool.swap_remove((tip::random() % pool.len() as u64) as usize);
+        write!(
+            stdout,
+            "{}/{count}  Which command: {}? ({}, {} mode)\n> ",
+            asked + 1,
+            c.description_in(lang),
+            c.category,
+            c.mode().label()
+        )?;
+        stdout.flush().context("failed to write to stdout")?;
+        let Some(line) = lines.next() else {
+            writeln!(stdout)?;
+            break;
+        };
+        let answer = line.context("failed to read from stdin")?;
+        asked += 1;
+        if answer.trim().is_empty() {
+            writeln!(stdout, "Skipped: {}\n", c.command)?;
+        } else if is_answer(c, &answer) {
+            correct += 1;
+            writeln!(stdout, "Correct!\n")?;
+        } else {
+            writeln!(stdout, "Not quite: {}\n", c.command)?;
+        }
+    }
+    writeln!(stdout, "Score: {correct}/{asked}")?;
+    Ok(())
+}
+
+/// Whether `answer` is `c`'s command: typed as its keys (`J`, `<C-w>v`,
+/// `:e notes.txt` for `:e {file}`), or written as it is (`f{char}`). One
+/// of its alternatives will do.
+fn is_answer(c: &CheatEntry, answer: &str) -> bool {
+    let answer = answer.trim();
+    if c.command
+        .split(" / ")
+        .any(|alternative| alternative.trim() == answer)
+    {
+        return true;
+    }
+    let typed = keys::parse_typed(answer);
+    !typed.is_empty()
+        && c.keys().alternatives.into_iter().any(|seq| {
+            // A trailing placeholder takes the rest of the line.
+            let open = matches!(seq.last(), Some(Token::Placeholder(_)));
+            let keys = Keys {
+                alternatives: vec![seq],
+            };
+            match keys.match_typed(&typed) {
+                Some(Typed::Complete(n)) => n == typed.len() || open,
+                _ => false,
+            }
+        })
+}
@@ -0,0 +1,98 @@
+//! Entries recently opened (in the detail pane or in `:help`) or copied,
+//! listed by the recent view. Saved to `recent` in the data directory as
+//! one `category<TAB>command` line per entry, newest first.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use log::warn;
+
+use crate::{paths, store, CheatEntry};
+
+/// Entries kept, oldest dropped first.
+const MAX_ENTRIES: usize = 30;
+
+#[derive(Debug, Default)]
+pub struct Recent {
+    /// Category and command of each entry, newest first.
+    entries: Vec<(String, String)>,
+    /// Entries recorded since the last save, newest first. Saving puts
+    /// them before what other instances saved meanwhile.
+    added: Vec<(String, String)>,
+    /// Where to save, when persisting.
+    path: Option<PathBuf>,
+}
+
+impl Recent {
+    /// Recent entries that live only for this session.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Recent entries loaded from and saved to the data directory.
+    pub fn persistent() -> Result<Self> {
+        let path = paths::data_dir()?.join("recent");
+        Ok(Self {
+            entries: read(&path),
+            added: Vec::new(),
+            path: Some(path),
+        })
+    }
+
+    /// Record `entry` as the newest.
+    pub fn record(&mut self, entry: &CheatEntry) {
+        let key = (entry.category.clone(), entry.command.clone());
+        self.entries.retain(|recent| *recent != key);
+        self.entries.insert(0, key.clone());
+        self.entries.truncate(MAX_ENTRIES);
+        self.added.retain(|recent| *recent != key);
+        self.added.insert(0, key);
+        if let Err(err) = self.save() {
+            warn!("failed to save recent entries: {err:#}");
+        }
+    }
+
+    /// How recently `entry` was used: 0 for the newest, `None` if not
+    /// among the recent entries.
+    pub fn position(&self, entry: &CheatEntry) -> Option<usize> {
+        self.entries.iter().position(|(category, command)| {
+            *category == entry.category && *command == entry.command
+        })
+    }
+
+    /// Put the entries recorded since the last save before the saved
+    /// ones, and take up what other instances recorded meanwhile.
+    fn save(&mut self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let entries = store::locked(path, || {
+            let mut entries = self.added.clone();
+            entries.extend(read(path).into_iter().filter(|e| !self.added.contains(e)));
+            entries.truncate(MAX_ENTRIES);
+            let text: String = entries
+                .iter()
+                .map(|(category, command)| format!("{category}\t{command}\n"))
+                .collect();
+            store::write(path, &text)?;
+            Ok(entries)
+        })?;
+        self.entries = entries;
+        self.added.clear();
+        Ok(())
+    }
+}
+
+/// The recent entries saved at `path`; none if it cannot be read.
+fn read(path: &Path) -> Vec<(String, String)> {
+    let Ok(text) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    text.lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(category, command)| (category.to_string(), command.to_string()))
+        .collect()
+}
@@ -0,0 +1,299 @@
+//! Pack registry: cheat packs installed from git repositories.
+//!
+//! `vimbo packs install github:user/vim-cheats` clones the repository into
+//! the data directory and records it in `registry.toml` together with the
+//! installed commit. Every `*.toml` pack file inside an installed repository
+//! is loaded on startup; `vimbo packs update` pulls upstream changes.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{bail, Context, Result};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::{packs::PackFile, paths, store, CheatEntry};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Registry {
+    #[serde(default, rename = "pack")]
+    pub packs: Vec<InstalledPack>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledPack {
+    pub name: String,
+    /// The spec the pack was installed from (e.g. `github:user/repo`).
+    pub source: String,
+    /// Clone URL derived from `source`.
+    pub url: String,
+    /// Commit currently checked out.
+    #[serde(default)]
+    pub commit: String,
+}
+
+impl InstalledPack {
+    pub fn dir(&self) -> Result<PathBuf> {
+        Ok(installed_dir()?.join(&self.name))
+    }
+}
+
+fn installed_dir() -> Result<PathBuf> {
+    Ok(paths::data_dir()?.join("installed"))
+}
+
+fn registry_path() -> Result<PathBuf> {
+    Ok(paths::data_dir()?.join("registry.toml"))
+}
+
+impl Registry {
+    pub fn load() -> Result<Self> {
+        let path = registry_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("invalid registry {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = registry_path()?;
+        store::write(&path, &toml::to_string_pretty(self)?)
+    }
+}
+
+/// Turn an install spec into a clone URL and a pack name.
+///
+/// Accepts `github:user/repo`, `gitlab:user/repo`, `codeberg:user/repo`,
+/// or anything `git clone` understands (URLs, local paths).
+pub fn resolve_spec(spec: &str) -> Result<(String, String)> {
+    let hosts = [
+        ("github:", "https://github.com/"),
+        ("gitlab:", "https://gitlab.com/"),
+        ("codeberg:", "https://codeberg.org/"),
+    ];
+    let url = hosts
+        .iter()
+        .find_map(|(prefix, base)| {
+            spec.strip_prefix(prefix)
+                .map(|path| format!("{base}{}.git", path.trim_end_matches(".git")))
+        })
+        .unwrap_or_else(|| spec.to_string());
+    // git would take it for one of its options.
+    if url.starts_with('-') {
+        bail!("invalid pack source '{spec}'");
+    }
+
+    let name = url
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(".git")
+        .to_string();
+    if name.is_empty() || name.starts_with('.') {
+        bail!("cannot derive a pack name from '{spec}'");
+    }
+    Ok((url, name))
+}
+
+/// `vimbo packs install <spec>`
+pub fn install(spec: &str) -> Result<()> {
+    let (url, name) = resolve_spec(spec)?;
+    let mut registry = Registry::load()?;
+    if registry.packs.iter().any(|p| p.name == name) {
+        bail!("pack '{name}' is already installed");
+    }
+
+    let mut pack = InstalledPack {
+        name,
+        source: spec.to_string(),
+        url,
+        commit: String::new(),
+    };
+    let dir = pack.dir()?;
+    if let Some(parent) = dir.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    println!("Cloning {} ...", pack.url);
+    git(
+        None,
+        &["clone", "--depth", "1", "--", &pack.url, &dir.to_string_lossy()],
+    )?;
+    pack.commit = git(Some(&dir), &["rev-parse", "HEAD"])?;
+
+    let entries = load_pack_dir(&dir);
+    if entries.is_empty() {
+        warn!("no cheat entries found in {}", dir.display());
+    }
+    println!(
+        "Installed pack '{}' at {} ({} entries)",
+        pack.name,
+        short(&pack.commit),
+        entries.len()
+    );
+    registry.packs.push(pack);
+    registry.save()
+}
+
+/// `vimbo packs update [pack]`: fetch upstream changes for one or all installed
+/// packs and report which entries were added or removed.
+pub fn update(name: Option<&str>) -> Result<()> {
+    let mut registry = Registry::load()?;
+    if let Some(name) = name
+        && !registry.packs.iter().any(|p| p.name == name)
+    {
+        bail!("pack '{name}' is not installed");
+    }
+    if registry.packs.is_empty() {
+        println!("No packs installed.");
+        return Ok(());
+    }
+
+    for pack in registry
+        .packs
+        .iter_mut()
+        .filter(|p| name.is_none_or(|n| p.name == n))
+    {
+        let dir = pack.dir()?;
+        let before = load_pack_dir(&dir);
+        git(Some(&dir), &["fetch", "--depth", "1", "origin"])?;
+        let upstream = git(Some(&dir), &["rev-parse", "FETCH_HEAD"])?;
+        if upstream == pack.commit {
+            println!("{}: up to date ({})", pack.name, short(&pack.commit));
+            continue;
+        }
+        git(Some(&dir), &["reset", "--hard", "FETCH_HEAD"])?;
+        let after = load_pack_dir(&dir);
+
+        let added: Vec<_> = after.iter().filter(|e| !contains(&before, e)).collect();
+        let removed: Vec<_> = before.iter().filter(|e| !contains(&after, e)).collect();
+        println!(
+            "{}: {} -> {} (+{} -{})",
+            pack.name,
+            short(&pack.commit),
+            short(&upstream),
+            added.len(),
+            removed.len()
+        );
+        for e in added {
+            println!("  + [{}] {}  {}", e.category, e.command, e.description);
+        }
+        for e in removed {
+            println!("  - [{}] {}  {}", e.category, e.command, e.description);
+        }
+        pack.commit = upstream;
+    }
+    registry.save()
+}
+
+/// Print the installed packs for `vimbo packs`: name, source and commit.
+pub fn print_installed() -> Result<()> {
+    let registry = Registry::load()?;
+    if registry.packs.is_empty() {
+        return Ok(());
+    }
+    println!("\nInstalled from git (`vimbo packs update` pulls changes):");
+    for pack in &registry.packs {
+        let count = load_pack_dir(&pack.dir()?).len();
+        println!(
+            "  {:<16} {count:>4} entries  {} at {}",
+            pack.name,
+            pack.source,
+            short(&pack.commit)
+        );
+    }
+    Ok(())
+}
+
+fn contains(entries: &[CheatEntry], entry: &CheatEntry) -> bool {
+    entries.iter().any(|e| {
+        e.category == entry.category
+            && e.command == entry.command
+            && e.description == entry.description
+    })
+}
+
+fn short(commit: &str) -> &str {
+    commit.get(..7).unwrap_or(commit)
+}
+
+/// Entries from every installed pack, for loading at startup.
+pub fn load_installed() -> Result<Vec<CheatEntry>> {
+    let registry = Registry::load()?;
+    let mut entries = Vec::new();
+    for pack in &registry.packs {
+        let dir = pack.dir()?;
+        if !dir.is_dir() {
+            warn!(
+                "installed pack '{}' is missing from {}",
+                pack.name,
+                dir.display()
+            );
+            continue;
+        }
+        entries.extend(load_pack_dir(&dir));
+    }
+    Ok(entries)
+}
+
+/// Load every pack file under `dir`, skipping hidden directories such as
+/// `.git`. Files that aren't valid packs are skipped with a log message.
+pub fn load_pack_dir(dir: &Path) -> Vec<CheatEntry> {
+    let mut files = Vec::new();
+    collect_pack_files(dir, &mut files);
+    files.sort();
+
+    let mut entries = Vec::new();
+    for path in files {
+        match PackFile::read(&path) {
+            Ok(pack) => entries.extend(pack.into_entries()),
+            Err(err) => debug!("not a pack: {err:#}"),
+        }
+    }
+    entries
+}
+
+fn collect_pack_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in read_dir.filter_map(|e| e.ok().map(|e| e.path())) {
+        let hidden = path
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_pack_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "toml") {
+            files.push(path);
+        }
+    }
+}
+
+/// Run `git` (optionally inside `dir`) and return its trimmed stdout.
+fn git(dir: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut cmd = Command::new("git");
+    if let Some(dir) = dir {
+        cmd.current_dir(dir);
+    }
+    let output = cmd
+        .args(args)
+        .output()
+        .context("failed to run git; is it installed?")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
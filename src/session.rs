@@ -0,0 +1,63 @@
+//! Where the TUI was left: the query, category filter, selected entry and
+//! scroll position, saved to `session` in the data directory on exit for
+//! `--resume` to start from. One `key=value` line each; the entry is saved as
+//! `category<TAB>command`.
+
+use std::fs;
+
+use anyhow::Result;
+
+use crate::{paths, store};
+
+#[derive(Debug, Default)]
+pub struct Session {
+    pub query: String,
+    pub category: Option<String>,
+    /// Category and command of the selected entry.
+    pub selected: Option<(String, String)>,
+    /// First row the list showed.
+    pub offset: usize,
+}
+
+impl Session {
+    /// The session saved last, if any.
+    pub fn load() -> Result<Option<Self>> {
+        let path = paths::data_dir()?.join("session");
+        let Ok(text) = fs::read_to_string(&path) else {
+            return Ok(None);
+        };
+        let mut session = Self::default();
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            match key {
+                "query" => session.query = value.to_string(),
+                "category" => session.category = Some(value.to_string()),
+                "offset" => session.offset = value.parse().unwrap_or_default(),
+                "selected" => {
+                    session.selected = value
+                        .split_once('\t')
+                        .map(|(category, command)| (category.to_string(), command.to_string()));
+                }
+                _ => {}
+            }
+        }
+        Ok(Some(session))
+    }
+
+    /// Save the session, replacing the one saved before.
+    pub fn save(&self) -> Result<()> {
+        let dir = paths::data_dir()?;
+        let mut text = format!("query={}\n", self.query);
+        if let Some(category) = &self.category {
+            text.push_str(&format!("category={category}\n"));
+        }
+        if let Some((category, command)) = &self.selected {
+            text.push_str(&format!("selected={category}\t{command}\n"));
+        }
+        text.push_str(&format!("offset={}\n", self.offset));
+        let path = dir.join("session");
+        store::write(&path, &text)
+    }
+}
@@ -0,0 +1,78 @@
+//! Shell completions. `vimbo completions <shell>` prints a script that
+//! asks vimbo itself for the candidates while completing, so pack names
+//! and categories complete along with the options.
+
+use std::{
+    env,
+    io::{self, Write},
+};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use clap_complete::{env::Shells, CompletionCandidate, Shell};
+
+use crate::{config::Config, editor::Editor, packs, registry};
+
+/// Environment variable that has vimbo complete the command line instead
+/// of running, naming the shell.
+pub const VAR: &str = "VIMBO_COMPLETE";
+
+/// Print the completion script for `shell`.
+pub fn print(shell: Shell) -> Result<()> {
+    let name = shell.to_string();
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(&name)
+        .with_context(|| format!("no completions for {name}"))?;
+    // The script calls back into this very binary, wherever it is run from.
+    let bin = env::current_exe()
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| "vimbo".to_string());
+    let mut script = Vec::new();
+    completer.write_registration(VAR, "vimbo", "vimbo", &bin, &mut script)?;
+    match io::stdout().lock().write_all(&script) {
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result.context("failed to write to stdout"),
+    }
+}
+
+/// Names of the bundled packs, for `--packs`.
+pub fn bundled_packs() -> Vec<CompletionCandidate> {
+    packs::BUNDLED
+        .iter()
+        .map(|pack| CompletionCandidate::new(pack.name))
+        .collect()
+}
+
+/// Names of the packs installed from git, for `packs update`.
+pub fn installed_packs() -> Vec<CompletionCandidate> {
+    let Ok(registry) = registry::Registry::load() else {
+        return Vec::new();
+    };
+    registry
+        .packs
+        .into_iter()
+        .map(|pack| CompletionCandidate::new(pack.name))
+        .collect()
+}
+
+/// Categories of every editor profile and of the packs loaded on startup,
+/// for `--category`. Packs that fail to load are left out.
+pub fn categories() -> Vec<CompletionCandidate> {
+    let mut entries: Vec<_> = Editor::value_variants()
+        .iter()
+        .flat_map(|editor| editor.cheats())
+        .collect();
+    if let Ok(config) = Config::load() {
+        entries.extend(packs::load(&config.packs).unwrap_or_default());
+    }
+    entries.extend(packs::load_local().unwrap_or_default());
+    entries.extend(registry::load_installed().unwrap_or_default());
+    let mut categories: Vec<String> = entries.into_iter().map(|c| c.category).collect();
+    categories.sort();
+    categories.dedup();
+    categories
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
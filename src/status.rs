@@ -0,0 +1,84 @@
+//! The status line at the bottom of the screen, rendered from a template
+//! with `{placeholder}`s, e.g. `{shown}/{total}  {filters}{clock}`.
+
+use anyhow::{bail, Result};
+
+/// Template used when the config file sets none.
+pub const DEFAULT_TEMPLATE: &str =
+    "Editor: {editor}  {filters}Total: {total}  Shown: {shown}  {hints}";
+
+/// Placeholders a template may use.
+const PLACEHOLDERS: &[&str] = &[
+    "editor", "profile", "pack", "filters", "total", "shown", "position", "hints", "clock",
+];
+
+/// What the placeholders stand for in the current frame.
+pub struct Fields<'a> {
+    pub editor: &'a str,
+    /// Name of the `--profile` in use, or `default`.
+    pub profile: &'a str,
+    /// Label of the shown tab, or `all` without tabs.
+    pub pack: &'a str,
+    /// Active filters, each followed by two spaces (e.g. `Level: beginner  `).
+    pub filters: String,
+    pub total: usize,
+    pub shown: usize,
+    /// 1-based position of the selection in the list; 0 when empty.
+    pub position: usize,
+}
+
+/// Check that `template` only uses known placeholders.
+pub fn validate(template: &str) -> Result<()> {
+    for name in placeholders(template) {
+        if !PLACEHOLDERS.contains(&name) {
+            bail!(
+                "unknown placeholder '{{{name}}}' (available: {})",
+                PLACEHOLDERS.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `template` with its placeholders filled in from `fields`.
+pub fn render(template: &str, fields: &Fields) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let name = &rest[start + 1..start + len];
+        match value(name, fields) {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(&rest[start..=start + len]),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn value(name: &str, fields: &Fields) -> Option<String> {
+    Some(match name {
+        "editor" => fields.editor.to_string(),
+        "profile" => fields.profile.to_string(),
+        "pack" => fields.pack.to_string(),
+        "filters" => fields.filters.clone(),
+        "total" => fields.total.to_string(),
+        "shown" => fields.shown.to_string(),
+        "position" => fields.position.to_string(),
+        "hints" => "(? for help)".to_string(),
+        "clock" => chrono::Local::now().format("%H:%M").to_string(),
+        _ => return None,
+    })
+}
+
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|part| Some(part.split_once('}')?.0))
+}
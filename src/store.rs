@@ -0,0 +1,60 @@
+//! Writing vimbo's files safely while other instances (say, in other tmux
+//! panes) use them too. Files are replaced in one step, by renaming a
+//! finished copy over them, so no instance ever reads half a file. State
+//! that every instance adds to, like favorites, notes and counters, is
+//! also saved under a lock, reading the file again and applying only
+//! what this instance changed, so what the others saved meanwhile stays;
+//! where two instances changed the same thing, like one entry's counter,
+//! the last one to save wins.
+
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+    process,
+};
+
+use anyhow::{Context, Result};
+
+/// Replace the file at `path` with `text`, creating its directory if need
+/// be.
+pub fn write(path: &Path, text: &str) -> Result<()> {
+    create_parent(path)?;
+    let temp = sibling(path, &format!(".{}.tmp", process::id()));
+    fs::write(&temp, text).with_context(|| format!("failed to write {}", temp.display()))?;
+    fs::rename(&temp, path).with_context(|| {
+        let _ = fs::remove_file(&temp);
+        format!("failed to write {}", path.display())
+    })
+}
+
+/// Run `update` while holding a lock on `path`, which other instances
+/// wait for, so reading the file, merging and writing it back happen as
+/// one. The lock is a `.lock` file beside `path`.
+pub fn locked<T>(path: &Path, update: impl FnOnce() -> Result<T>) -> Result<T> {
+    create_parent(path)?;
+    let lock_path = sibling(path, ".lock");
+    let lock = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("failed to open {}", lock_path.display()))?;
+    lock.lock()
+        .with_context(|| format!("failed to lock {}", lock_path.display()))?;
+    // Unlocked when `lock` is dropped, after the update.
+    update()
+}
+
+/// `path` with `suffix` appended to its file name.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+fn create_parent(path: &Path) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    Ok(())
+}
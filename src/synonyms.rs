@@ -0,0 +1,57 @@
+//! Synonym expansion for search: everyday words ("copy", "cut", "find")
+//! also look for the Vim terms the cheatsheet uses ("yank", "delete",
+//! "search"). The built-in table can be extended from the config file.
+
+use std::collections::{BTreeMap, HashMap};
+
+const BUILTIN: &[(&str, &[&str])] = &[
+    ("copy", &["yank"]),
+    ("cut", &["delete"]),
+    ("remove", &["delete"]),
+    ("erase", &["delete"]),
+    ("find", &["search"]),
+    ("paste", &["put"]),
+    ("replace", &["substitute", "change"]),
+    ("save", &["write"]),
+    ("exit", &["quit"]),
+    ("close", &["quit"]),
+    ("open", &["edit"]),
+    ("select", &["visual"]),
+    ("highlight", &["visual"]),
+    ("go", &["move", "jump"]),
+    ("scroll", &["view"]),
+    ("record", &["macro"]),
+];
+
+#[derive(Debug)]
+pub struct Synonyms(HashMap<String, Vec<String>>);
+
+impl Default for Synonyms {
+    fn default() -> Self {
+        Self::new(&BTreeMap::new())
+    }
+}
+
+impl Synonyms {
+    /// The built-in table plus `extra` words (e.g. from the config file);
+    /// extra synonyms are added to any built-in ones for the same word.
+    pub fn new(extra: &BTreeMap<String, Vec<String>>) -> Self {
+        let mut map: HashMap<String, Vec<String>> = BUILTIN
+            .iter()
+            .map(|(word, synonyms)| {
+                let synonyms = synonyms.iter().map(|s| s.to_string()).collect();
+                (word.to_string(), synonyms)
+            })
+            .collect();
+        for (word, synonyms) in extra {
+            let entry = map.entry(word.to_lowercase()).or_default();
+            entry.extend(synonyms.iter().map(|s| s.to_lowercase()));
+        }
+        Self(map)
+    }
+
+    /// `word` followed by its synonyms.
+    pub fn alternatives<'a>(&'a self, word: &'a str) -> impl Iterator<Item = &'a str> {
+        std::iter::once(word).chain(self.0.get(word).into_iter().flatten().map(String::as_str))
+    }
+}
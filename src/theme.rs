@@ -0,0 +1,410 @@
+//! Colors and text styles of the TUI: a built-in preset, overridable per
+//! element from the `[theme]` table of the config file.
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, bail, Result};
+use clap::ValueEnum;
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+/// The `[theme]` table of the config file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// Preset to start from (same as `--theme`).
+    pub preset: Option<Preset>,
+    /// Per-element overrides applied on top of the preset.
+    #[serde(flatten)]
+    pub elements: BTreeMap<String, StyleConfig>,
+}
+
+/// Built-in color schemes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Preset {
+    /// The terminal's own colors, for dark backgrounds.
+    #[default]
+    Default,
+    /// Dark text for light backgrounds.
+    Light,
+    Solarized,
+    Gruvbox,
+    /// No colors, only bold, underline and reverse video.
+    Monochrome,
+    /// Blue and orange told apart by brightness and text styles rather than
+    /// red, green and magenta, for color vision deficiencies.
+    Colorblind,
+}
+
+impl Preset {
+    pub fn label(self) -> &'static str {
+        match self {
+            Preset::Default => "default",
+            Preset::Light => "light",
+            Preset::Solarized => "solarized",
+            Preset::Gruvbox => "gruvbox",
+            Preset::Monochrome => "monochrome",
+            Preset::Colorblind => "colorblind",
+        }
+    }
+
+    /// Cycle for the theme switcher key, wrapping back to the first preset.
+    pub fn next(self) -> Self {
+        let all = Self::value_variants();
+        let pos = all.iter().position(|p| *p == self).unwrap_or(0);
+        all[(pos + 1) % all.len()]
+    }
+}
+
+/// The colors a preset is made of.
+struct Palette {
+    text: Color,
+    /// Titles, the focus border and the search bar text.
+    title: Color,
+    accent: Color,
+    category: Color,
+    command: Color,
+    muted: Color,
+    selection_fg: Color,
+    selection_bg: Color,
+    /// Matched characters, messages and annotations.
+    highlight: Color,
+    error: Color,
+    /// Text on `command` or `accent` backgrounds.
+    contrast: Color,
+}
+
+/// Style of one UI element as written in the config file, e.g.
+/// `command = { fg = "blue", modifiers = ["bold"] }`. Unset parts keep the
+/// default.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StyleConfig {
+    /// Color name (`red`, `light-blue`), 256-color index or `#rrggbb`.
+    pub fg: Option<String>,
+    pub bg: Option<String>,
+    /// `bold`, `dim`, `italic`, `underlined`, `reversed`, ...; replaces the
+    /// default modifiers.
+    pub modifiers: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Theme {
+    pub preset: Preset,
+    /// Titles of the search bar and help pane, and example headings.
+    pub title: Style,
+    /// Titles of the list, sidebar and detail panes.
+    pub pane_title: Style,
+    /// Border of the focused search bar or sidebar.
+    pub focus_border: Style,
+    /// Text typed in the search bar.
+    pub input: Style,
+    pub category: Style,
+    pub command: Style,
+    pub description: Style,
+    /// Tags, labels and counts.
+    pub muted: Style,
+    /// The selected list entry.
+    pub selection: Style,
+    /// The selected sidebar category.
+    pub sidebar_selection: Style,
+    /// Scrollbar beside the list.
+    pub scrollbar: Style,
+    /// Characters matched by the query, layered over the field's style.
+    pub matched: Style,
+    pub status: Style,
+    /// Notifications popping up above the status bar.
+    pub message: Style,
+    pub help: Style,
+    /// Related commands in the detail pane.
+    pub link: Style,
+    pub link_selected: Style,
+    /// The cursor in example buffers.
+    pub cursor: Style,
+    pub suggestion: Style,
+    pub suggestion_selected: Style,
+    /// Pack tabs above the search bar.
+    pub tab: Style,
+    pub tab_selected: Style,
+    /// Star of favorite entries.
+    pub favorite: Style,
+    /// Check mark of entries marked for batch actions.
+    pub marked: Style,
+    /// Category headings of the grouped list.
+    pub group: Style,
+    pub overridden: Style,
+    pub annotated: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::preset(Preset::Default)
+    }
+}
+
+impl Theme {
+    /// `preset` with `overrides` from the config file applied.
+    pub fn new(preset: Preset, overrides: &BTreeMap<String, StyleConfig>) -> Result<Self> {
+        let mut theme = Self::preset(preset);
+        for (element, config) in overrides {
+            let Some(style) = theme.element_mut(element) else {
+                bail!("unknown theme element '{element}'");
+            };
+            if let Some(fg) = &config.fg {
+                *style = style.fg(parse_color(fg, element)?);
+            }
+            if let Some(bg) = &config.bg {
+                *style = style.bg(parse_color(bg, element)?);
+            }
+            if let Some(names) = &config.modifiers {
+                let mut modifiers = Modifier::empty();
+                for name in names {
+                    modifiers |= parse_modifier(name, element)?;
+                }
+                *style = style
+                    .remove_modifier(Modifier::all())
+                    .add_modifier(modifiers);
+            }
+        }
+        Ok(theme)
+    }
+
+    /// No colors and no text styles at all, for `--plain`; only the cursor
+    /// of example buffers, which has no text form, stays reversed.
+    pub fn plain() -> Self {
+        let none = Style::default();
+        Self {
+            preset: Preset::Monochrome,
+            title: none,
+            pane_title: none,
+            focus_border: none,
+            input: none,
+            category: none,
+            command: none,
+            description: none,
+            muted: none,
+            selection: none,
+            sidebar_selection: none,
+            scrollbar: none,
+            matched: none,
+            status: none,
+            message: none,
+            help: none,
+            link: none,
+            link_selected: none,
+            cursor: none.add_modifier(Modifier::REVERSED),
+            suggestion: none,
+            suggestion_selected: none,
+            tab: none,
+            tab_selected: none,
+            favorite: none,
+            marked: none,
+            group: none,
+            overridden: none,
+            annotated: none,
+        }
+    }
+
+    fn preset(preset: Preset) -> Self {
+        let rgb = Color::Rgb;
+        let palette = match preset {
+            Preset::Default => Palette {
+                text: Color::White,
+                title: Color::Yellow,
+                accent: Color::Cyan,
+                category: Color::Magenta,
+                command: Color::Green,
+                muted: Color::DarkGray,
+                selection_fg: Color::White,
+                selection_bg: Color::Blue,
+                highlight: Color::Yellow,
+                error: Color::Red,
+                contrast: Color::Black,
+            },
+            Preset::Light => Palette {
+                text: rgb(40, 40, 40),
+                title: rgb(0, 70, 160),
+                accent: rgb(0, 110, 140),
+                category: rgb(140, 0, 140),
+                command: rgb(0, 110, 0),
+                muted: rgb(110, 110, 110),
+                selection_fg: rgb(0, 0, 0),
+                selection_bg: rgb(200, 220, 255),
+                highlight: rgb(180, 80, 0),
+                error: rgb(190, 0, 0),
+                contrast: rgb(255, 255, 255),
+            },
+            Preset::Solarized => Palette {
+                text: rgb(131, 148, 150),
+                title: rgb(181, 137, 0),
+                accent: rgb(42, 161, 152),
+                category: rgb(211, 54, 130),
+                command: rgb(133, 153, 0),
+                muted: rgb(88, 110, 117),
+                selection_fg: rgb(238, 232, 213),
+                selection_bg: rgb(7, 54, 66),
+                highlight: rgb(203, 75, 22),
+                error: rgb(220, 50, 47),
+                contrast: rgb(0, 43, 54),
+            },
+            Preset::Gruvbox => Palette {
+                text: rgb(235, 219, 178),
+                title: rgb(250, 189, 47),
+                accent: rgb(142, 192, 124),
+                category: rgb(211, 134, 155),
+                command: rgb(184, 187, 38),
+                muted: rgb(146, 131, 116),
+                selection_fg: rgb(251, 241, 199),
+                selection_bg: rgb(80, 73, 69),
+                highlight: rgb(254, 128, 25),
+                error: rgb(251, 73, 52),
+                contrast: rgb(40, 40, 40),
+            },
+            Preset::Monochrome => Palette {
+                text: Color::Reset,
+                title: Color::Reset,
+                accent: Color::Reset,
+                category: Color::Reset,
+                command: Color::Reset,
+                muted: Color::Reset,
+                selection_fg: Color::Reset,
+                selection_bg: Color::Reset,
+                highlight: Color::Reset,
+                error: Color::Reset,
+                contrast: Color::Reset,
+            },
+            // The Okabe-Ito colors, which stay apart with deuteranopia.
+            Preset::Colorblind => Palette {
+                text: rgb(215, 215, 215),
+                title: rgb(240, 228, 66),
+                accent: rgb(86, 180, 233),
+                category: rgb(86, 180, 233),
+                command: rgb(230, 159, 0),
+                muted: rgb(130, 130, 130),
+                selection_fg: rgb(255, 255, 255),
+                selection_bg: rgb(0, 90, 150),
+                highlight: rgb(240, 228, 66),
+                error: rgb(213, 94, 0),
+                contrast: rgb(0, 0, 0),
+            },
+        };
+        let theme = Self::from_palette(preset, &palette);
+        if preset == Preset::Colorblind {
+            // Styles tell the fields apart even where the hues do not.
+            let italic = Modifier::ITALIC;
+            return Self {
+                category: theme.category.add_modifier(italic),
+                group: theme.group.add_modifier(italic),
+                overridden: theme
+                    .overridden
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                annotated: theme.annotated.add_modifier(italic),
+                ..theme
+            };
+        }
+        if preset != Preset::Monochrome {
+            return theme;
+        }
+        // Without colors, selections and dimmed text need modifiers instead.
+        let reversed = Style::default().add_modifier(Modifier::REVERSED);
+        Self {
+            focus_border: Style::default().add_modifier(Modifier::BOLD),
+            muted: Style::default().add_modifier(Modifier::DIM),
+            selection: reversed.add_modifier(Modifier::BOLD),
+            status: Style::default().add_modifier(Modifier::DIM | Modifier::ITALIC),
+            message: Style::default().add_modifier(Modifier::BOLD),
+            link: Style::default().add_modifier(Modifier::UNDERLINED),
+            link_selected: reversed,
+            suggestion_selected: reversed,
+            tab_selected: reversed.add_modifier(Modifier::BOLD),
+            overridden: Style::default().add_modifier(Modifier::BOLD),
+            annotated: Style::default().add_modifier(Modifier::ITALIC),
+            ..theme
+        }
+    }
+
+    fn from_palette(preset: Preset, p: &Palette) -> Self {
+        let bold = Modifier::BOLD;
+        let fg = |color| Style::default().fg(color);
+        Self {
+            preset,
+            title: fg(p.title).add_modifier(bold),
+            pane_title: fg(p.accent).add_modifier(bold),
+            focus_border: fg(p.title),
+            input: fg(p.accent),
+            category: fg(p.category),
+            command: fg(p.command).add_modifier(bold),
+            description: fg(p.text),
+            muted: fg(p.muted),
+            selection: fg(p.selection_fg).bg(p.selection_bg).add_modifier(bold),
+            sidebar_selection: Style::default().add_modifier(Modifier::REVERSED),
+            scrollbar: fg(p.muted),
+            matched: fg(p.highlight).add_modifier(Modifier::UNDERLINED),
+            status: fg(p.muted).add_modifier(Modifier::ITALIC),
+            message: fg(p.highlight),
+            help: fg(p.text),
+            link: fg(p.command),
+            link_selected: fg(p.contrast).bg(p.command),
+            cursor: Style::default().add_modifier(Modifier::REVERSED),
+            suggestion: fg(p.accent),
+            suggestion_selected: fg(p.contrast).bg(p.accent),
+            tab: fg(p.muted),
+            tab_selected: fg(p.title).add_modifier(bold | Modifier::UNDERLINED),
+            favorite: fg(p.highlight),
+            marked: fg(p.accent).add_modifier(bold),
+            group: fg(p.category).add_modifier(bold),
+            overridden: fg(p.error),
+            annotated: fg(p.highlight),
+        }
+    }
+
+    fn element_mut(&mut self, name: &str) -> Option<&mut Style> {
+        Some(match name {
+            "title" => &mut self.title,
+            "pane_title" => &mut self.pane_title,
+            "focus_border" => &mut self.focus_border,
+            "input" => &mut self.input,
+            "category" => &mut self.category,
+            "command" => &mut self.command,
+            "description" => &mut self.description,
+            "muted" => &mut self.muted,
+            "selection" => &mut self.selection,
+            "sidebar_selection" => &mut self.sidebar_selection,
+            "scrollbar" => &mut self.scrollbar,
+            "matched" => &mut self.matched,
+            "status" => &mut self.status,
+            "message" => &mut self.message,
+            "help" => &mut self.help,
+            "link" => &mut self.link,
+            "link_selected" => &mut self.link_selected,
+            "cursor" => &mut self.cursor,
+            "suggestion" => &mut self.suggestion,
+            "suggestion_selected" => &mut self.suggestion_selected,
+            "tab" => &mut self.tab,
+            "tab_selected" => &mut self.tab_selected,
+            "favorite" => &mut self.favorite,
+            "marked" => &mut self.marked,
+            "group" => &mut self.group,
+            "overridden" => &mut self.overridden,
+            "annotated" => &mut self.annotated,
+            _ => return None,
+        })
+    }
+}
+
+fn parse_color(value: &str, element: &str) -> Result<Color> {
+    value
+        .parse()
+        .map_err(|_| anyhow!("invalid color '{value}' for theme element '{element}'"))
+}
+
+fn parse_modifier(name: &str, element: &str) -> Result<Modifier> {
+    let flag = match name.to_lowercase().replace('-', "_").as_str() {
+        "underline" => "UNDERLINED".to_string(),
+        "reverse" => "REVERSED".to_string(),
+        other => other.to_uppercase(),
+    };
+    Modifier::from_name(&flag)
+        .ok_or_else(|| anyhow!("invalid modifier '{name}' for theme element '{element}'"))
+}
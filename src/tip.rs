@@ -0,0 +1,76 @@
+//! `vimbo list --tip`: one random entry printed before exiting, to learn a command a
+//! day from a shell startup file.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    io::{self, Write},
+    time::SystemTime,
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{output::Paint, CheatEntry};
+
+/// How much likelier preferred entries are to be picked.
+const PREFERRED_WEIGHT: u64 = 8;
+
+/// Print a random one of `entries`, making those `prefer` returns true
+/// for likelier.
+pub fn print(
+    entries: &[&CheatEntry],
+    lang: Option<&str>,
+    paint: Paint,
+    prefer: impl Fn(&CheatEntry) -> bool,
+) -> Result<()> {
+    let weights: Vec<u64> = entries
+        .iter()
+        .map(|c| if prefer(c) { PREFERRED_WEIGHT } else { 1 })
+        .collect();
+    let total: u64 = weights.iter().sum();
+    if total == 0 {
+        bail!("no entries match");
+    }
+    let mut roll = random() % total;
+    let mut picked = entries[0];
+    for (c, weight) in entries.iter().zip(weights) {
+        if roll < weight {
+            picked = c;
+            break;
+        }
+        roll -= weight;
+    }
+    match io::stdout().lock().write_all(tip(picked, lang, paint).as_bytes()) {
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result.context("failed to write to stdout"),
+    }
+}
+
+/// `c` as a few lines: the command and its category, then what it does.
+fn tip(c: &CheatEntry, lang: Option<&str>, paint: Paint) -> String {
+    let mut out = format!(
+        "{} {}  {}\n  {}\n",
+        paint.paint("Tip:", |t| t.title),
+        paint.paint(&c.command, |t| t.command),
+        paint.paint(&format!("({})", c.category), |t| t.category),
+        paint.paint(c.description_in(lang), |t| t.description)
+    );
+    if let Some(notes) = &c.notes {
+        out.push_str(&format!("  {}\n", paint.paint(notes, |t| t.muted)));
+    }
+    if let Some(topic) = &c.help_topic {
+        let see = format!("See :help {topic}");
+        out.push_str(&format!("  {}\n", paint.paint(&see, |t| t.muted)));
+    }
+    out
+}
+
+/// A number that differs from run to run, from the random keys std seeds
+/// its hash maps with; good enough to pick tips and quiz questions.
+pub fn random() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    if let Ok(elapsed) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        hasher.write_u128(elapsed.as_nanos());
+    }
+    hasher.finish()
+}
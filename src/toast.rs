@@ -0,0 +1,56 @@
+//! Short notifications ("Copied dd", "Theme: gruvbox") shown in a corner of
+//! the screen for a couple of seconds, one after another in the order they
+//! were raised.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// How long a toast stays up when nothing waits behind it.
+const DURATION: Duration = Duration::from_millis(2500);
+/// How long a toast stays up while later ones wait, so a burst of them
+/// (e.g. marking several entries) does not lag behind.
+const QUEUED_DURATION: Duration = Duration::from_millis(600);
+/// Toasts waiting beyond this drop the oldest.
+const MAX_QUEUED: usize = 5;
+
+#[derive(Debug, Default)]
+pub struct Toasts {
+    queue: VecDeque<String>,
+    /// When the first toast in `queue` came up.
+    shown_since: Option<Instant>,
+}
+
+impl Toasts {
+    /// Queue `text`, shown right away if no other toast is up.
+    pub fn push(&mut self, text: impl Into<String>) {
+        self.queue.push_back(text.into());
+        if self.queue.len() > MAX_QUEUED {
+            self.queue.pop_front();
+            self.shown_since = Some(Instant::now());
+        }
+        self.shown_since.get_or_insert_with(Instant::now);
+    }
+
+    /// Take down the shown toast once its time is up, bringing up the next.
+    pub fn tick(&mut self, now: Instant) {
+        let Some(since) = self.shown_since else {
+            return;
+        };
+        let duration = if self.queue.len() > 1 {
+            QUEUED_DURATION
+        } else {
+            DURATION
+        };
+        if now.duration_since(since) >= duration {
+            self.queue.pop_front();
+            self.shown_since = (!self.queue.is_empty()).then_some(now);
+        }
+    }
+
+    /// The toast to draw, if any.
+    pub fn current(&self) -> Option<&str> {
+        self.queue.front().map(String::as_str)
+    }
+}
@@ -0,0 +1,1794 @@
+//! Rendering of the TUI.
+
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row as TableRow,
+        Scrollbar, ScrollbarOrientation, ScrollbarState, Table, TableState, Tabs, Wrap,
+    },
+    Frame,
+};
+
+use crate::{
+    cheat::{DemoStep, Example},
+    config::{Density, Numbers},
+    form::{self, Form},
+    keymap::Action,
+    keys::Notation,
+    matcher,
+    palette::Palette,
+    paths,
+    query::Field,
+    status,
+    theme::Theme,
+    App, CheatEntry, Difficulty, Focus, Hover, ListSort, Row, SortColumn, CATEGORY_KEYS,
+    HOVER_DELAY,
+};
+
+/// Narrower screens leave the whole width to the list, whose rows drop
+/// their category and tags.
+const NARROW_WIDTH: u16 = 100;
+/// Lower screens hide the tab bar and shrink the search bar to one line.
+const SHORT_HEIGHT: u16 = 24;
+/// Long category names are cut off beyond this, unless the sidebar was
+/// resized.
+const SIDEBAR_MAX_WIDTH: u16 = 30;
+/// The help overlay is no wider than this.
+const HELP_MAX_WIDTH: u16 = 90;
+/// Marks the selected row.
+const HIGHLIGHT_SYMBOL: &str = ">> ";
+/// Marks the selected item of other lists in plain mode, where it is not
+/// highlighted.
+const PLAIN_SELECTION: &str = "> ";
+/// Blank borders for plain mode, keeping the layout of the boxed one.
+const PLAIN_BORDER: border::Set = border::Set {
+    top_left: " ",
+    top_right: " ",
+    bottom_left: " ",
+    bottom_right: " ",
+    vertical_left: " ",
+    vertical_right: " ",
+    horizontal_top: " ",
+    horizontal_bottom: " ",
+};
+/// Indent of descriptions in comfortable rows.
+const COMFORTABLE_INDENT: &str = "    ";
+/// Descriptions are never wrapped narrower than this, even when the
+/// command leaves less room.
+const MIN_WRAP_WIDTH: usize = 16;
+/// Table columns other than the description are cut off beyond this.
+const TABLE_MAX_COLUMN_WIDTH: usize = 24;
+
+/// Where the last frame drew the parts that respond to the mouse.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Areas {
+    pub search: Rect,
+    pub sidebar: Option<Rect>,
+    pub list: Option<Rect>,
+    /// First category shown in the sidebar, as scrolled by the widget.
+    pub sidebar_offset: usize,
+    /// First entry shown in the list, as scrolled by the widget.
+    pub list_offset: usize,
+    /// The help overlay, while open.
+    pub help: Option<Rect>,
+    /// Whether the list was drawn in the narrow layout.
+    pub narrow: bool,
+}
+
+pub fn draw(f: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let mut size = f.area();
+    let short = size.height < SHORT_HEIGHT;
+    let tabs = app.tabs();
+    if !tabs.is_empty() && !short {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(size);
+        let titles = tabs.iter().enumerate().map(|(i, &tab)| {
+            if app.plain && i == app.tab {
+                format!("{PLAIN_SELECTION}{tab}")
+            } else {
+                tab.to_string()
+            }
+        });
+        let bar = Tabs::new(titles)
+            .select(app.tab)
+            .style(theme.tab)
+            .highlight_style(theme.tab_selected);
+        f.render_widget(bar, rows[0]);
+        size = rows[1];
+    }
+    let constraints = [
+        Constraint::Length(if short { 1 } else { 3 }), // search bar
+        Constraint::Min(5),                            // list
+        Constraint::Length(1),                         // status
+    ];
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(size);
+
+    // Search input, or the keys typed in reverse lookup mode
+    let (title, input) = match &app.lookup {
+        Some(typed) => (
+            " Lookup: press Vim keys to see what they do (Ctrl+k / Esc to leave) ".to_string(),
+            typed.iter().map(|k| k.render(Notation::Vim)).collect(),
+        ),
+        None if app.focus == Focus::Search => {
+            let title = match app.history.position() {
+                Some((n, total)) => format!(" Search history {n}/{total} (↑/↓, Enter to submit) "),
+                None => " Search (↑/↓ history, Enter to submit, Esc to browse) ".to_string(),
+            };
+            (title, app.query.clone())
+        }
+        None => (
+            " Search (/ to search, j/k to move, q to quit) ".to_string(),
+            app.query.clone(),
+        ),
+    };
+    let border = if app.focus == Focus::Search {
+        theme.focus_border
+    } else {
+        Style::default()
+    };
+    // Where the typed text starts, for the completion dropdown.
+    let input_area = if short {
+        // A bare prompt instead of the titled box.
+        let prompt = if app.lookup.is_some() { "keys: " } else { "/ " };
+        let prompt_style = if app.focus == Focus::Search {
+            border
+        } else {
+            theme.muted
+        };
+        let line = Line::from(vec![
+            Span::styled(prompt, prompt_style),
+            Span::styled(input, theme.input),
+        ]);
+        f.render_widget(Paragraph::new(line), chunks[0]);
+        let skip = (prompt.len() as u16).min(chunks[0].width);
+        Rect {
+            x: chunks[0].x + skip,
+            width: chunks[0].width - skip,
+            ..chunks[0]
+        }
+    } else {
+        let search = Paragraph::new(input)
+            .block(
+                block(app)
+                    .title(Span::styled(title, theme.title))
+                    .border_style(border),
+            )
+            .style(theme.input);
+        f.render_widget(search, chunks[0]);
+        chunks[0].inner(Margin::new(1, 1))
+    };
+    let mut areas = Areas {
+        search: chunks[0],
+        narrow: size.width < NARROW_WIDTH,
+        ..Areas::default()
+    };
+
+    let categories = app.categories();
+    let report = app.show_conflicts || app.show_stats || app.hidden_screen.is_some();
+    let main = if report || size.width < NARROW_WIDTH {
+        chunks[1]
+    } else {
+        let width = categories
+            .iter()
+            .map(|(name, count)| {
+                let icon = icon_span(app, name).map_or(0, |icon| icon.width());
+                let marker = selection_symbol(app).len();
+                (marker + icon + name.chars().count() + count.to_string().len()) as u16 + 5
+            })
+            .max()
+            .unwrap_or_default()
+            .min(SIDEBAR_MAX_WIDTH);
+        let width = app.panes.sidebar_width(width);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(width), Constraint::Min(20)])
+            .split(chunks[1]);
+        areas.sidebar = Some(columns[0]);
+        areas.sidebar_offset = draw_sidebar(f, app, &categories, columns[0]);
+        columns[1]
+    };
+
+    let main = if report || short {
+        main
+    } else {
+        let [pinned, rest] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .areas(main);
+        draw_pinned(f, app, pinned);
+        rest
+    };
+    let draw_results = if app.table { draw_table } else { draw_list };
+    // Whether the row under the mouse pointer did not fit, and so gets a
+    // tooltip.
+    let mut hover_cut = false;
+    if let Some(selected) = app.hidden_screen {
+        draw_hidden(f, app, selected, main);
+    } else if app.show_stats {
+        draw_stats(f, app, main);
+    } else if app.show_conflicts {
+        draw_conflicts(f, app, main);
+    } else if let Some(idx) = app.selected_index().filter(|_| app.show_detail) {
+        let panes = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(100 - app.panes.detail_percent()),
+                Constraint::Percentage(app.panes.detail_percent()),
+            ])
+            .split(main);
+        areas.list = Some(panes[0]);
+        (areas.list_offset, hover_cut) = draw_results(f, app, panes[0]);
+        draw_detail(f, app, idx, panes[1]);
+    } else {
+        app.demo.set(None);
+        areas.list = Some(main);
+        (areas.list_offset, hover_cut) = draw_results(f, app, main);
+    }
+    draw_suggestions(f, app, input_area);
+    draw_status(f, app, tabs.get(app.tab).copied(), chunks[2]);
+    if app.zen
+        && let Some(idx) = app.selected_index()
+    {
+        let screen = f.area();
+        let above_status = Rect {
+            height: chunks[2].y - screen.y,
+            ..screen
+        };
+        draw_zen(f, app, idx, above_status);
+    }
+    if let Some(hover) = app.hover
+        && hover_cut
+        && hover.since.elapsed() >= HOVER_DELAY
+        && !app.zen
+        && !app.show_help
+        && app.palette.is_none()
+        && app.form.is_none()
+        && app.note.is_none()
+    {
+        draw_tooltip(f, app, hover);
+    }
+    if app.show_help {
+        areas.help = Some(draw_help(f, app, f.area()));
+    }
+    if let Some(palette) = &app.palette {
+        draw_palette(f, app, palette, f.area());
+    }
+    if let Some(form) = &app.form {
+        draw_form(f, app, form, f.area());
+    }
+    if let Some(note) = &app.note {
+        draw_note(f, app, note, f.area());
+    }
+    if let Some(toast) = app.toasts.current() {
+        draw_toast(f, app, toast, chunks[2]);
+    }
+    app.areas.set(areas);
+}
+
+/// Text in focus mode is no wider than this.
+const ZEN_MAX_WIDTH: u16 = 70;
+
+/// Focus mode: entry `idx` alone over the tabs and results, its command
+/// letter-spaced in bold, with its description, tags and examples below,
+/// all centered.
+fn draw_zen(f: &mut Frame, app: &App, idx: usize, area: Rect) {
+    let theme = &app.theme;
+    let c = &app.cheats[idx];
+    let position = app
+        .filtered
+        .iter()
+        .position(|&i| i == idx)
+        .map_or(0, |p| p + 1);
+    let title = format!(" Focus  {position}/{} ", app.filtered.len());
+    let outer = block(app)
+        .title(Span::styled(title, theme.pane_title))
+        .title_bottom(Span::styled(
+            " n / p: next / previous   Esc: back ",
+            theme.muted,
+        ));
+    let inner = outer.inner(area);
+    f.render_widget(Clear, area);
+    f.render_widget(outer, area);
+
+    let width = inner.width.min(ZEN_MAX_WIDTH);
+    let command: Vec<String> = c
+        .keys()
+        .render(Notation::Readable)
+        .chars()
+        .map(String::from)
+        .collect();
+    let mut lines = vec![
+        Line::from(Span::styled(
+            command.join(" "),
+            theme.command.add_modifier(Modifier::BOLD),
+        )),
+        Line::default(),
+        Line::from(Span::styled(c.category.clone(), theme.category)),
+        Line::default(),
+    ];
+    for text in wrap(c.description_in(app.lang.as_deref()), width as usize) {
+        lines.push(Line::from(Span::styled(text, theme.description)));
+    }
+    let tags: Vec<String> = c
+        .difficulty
+        .map(Difficulty::label)
+        .into_iter()
+        .chain(c.tags.iter().map(String::as_str))
+        .map(|tag| format!("#{tag}"))
+        .collect();
+    if !tags.is_empty() {
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(tags.join("  "), theme.muted)));
+    }
+    let centered = lines.len();
+    for example in &c.examples {
+        lines.push(Line::default());
+        lines.extend(example_lines(example, theme));
+    }
+    let height = (lines.len() as u16).min(inner.height);
+    let text = Rect {
+        x: inner.x + (inner.width - width) / 2,
+        y: inner.y + (inner.height - height) / 2,
+        width,
+        height,
+    };
+    // Examples are laid out as buffers, so they stay left-aligned.
+    let lines = lines.into_iter().enumerate().map(|(i, line)| {
+        if i < centered {
+            line.alignment(Alignment::Center)
+        } else {
+            line
+        }
+    });
+    f.render_widget(Paragraph::new(lines.collect::<Vec<_>>()), text);
+}
+
+/// Tooltips are no wider than this.
+const TOOLTIP_MAX_WIDTH: u16 = 60;
+
+/// The full description, tags and first example of the entry under the
+/// mouse pointer, when its row was cut to fit, in a box below the pointer
+/// (above it near the bottom).
+fn draw_tooltip(f: &mut Frame, app: &App, hover: Hover) {
+    let Some(&Row::Entry(idx)) = app.rows.get(hover.row) else {
+        return;
+    };
+    let theme = &app.theme;
+    let c = &app.cheats[idx];
+    let screen = f.area();
+    let width = screen.width.min(TOOLTIP_MAX_WIDTH);
+    let mut lines = vec![Line::from(Span::styled(
+        c.keys().render(Notation::Readable),
+        theme.command,
+    ))];
+    let description = c.description_in(app.lang.as_deref());
+    for text in wrap(description, (width as usize).saturating_sub(2)) {
+        lines.push(Line::from(Span::styled(text, theme.description)));
+    }
+    let tags: Vec<String> = c
+        .difficulty
+        .map(Difficulty::label)
+        .into_iter()
+        .chain(c.tags.iter().map(String::as_str))
+        .map(|tag| format!("#{tag}"))
+        .collect();
+    if !tags.is_empty() {
+        lines.push(Line::from(Span::styled(tags.join("  "), theme.muted)));
+    }
+    if let Some(example) = c.examples.first() {
+        lines.push(Line::default());
+        lines.extend(example_lines(example, theme));
+    }
+    let height = (lines.len() as u16 + 2).min(screen.height);
+    let y = if hover.line + 1 + height <= screen.bottom() {
+        hover.line + 1
+    } else {
+        hover.line.saturating_sub(height)
+    };
+    let area = Rect {
+        x: hover.column.min(screen.right() - width),
+        y,
+        width,
+        height,
+    };
+    let tooltip = Paragraph::new(lines).block(block(app).border_style(theme.muted));
+    f.render_widget(Clear, area);
+    f.render_widget(tooltip, area);
+}
+
+/// A notification in a small box at the right end of the screen, just
+/// above the `status` line.
+fn draw_toast(f: &mut Frame, app: &App, toast: &str, status: Rect) {
+    let width = (toast.chars().count() as u16 + 4).min(status.width);
+    let height = 3.min(status.y);
+    let area = Rect {
+        x: status.right() - width,
+        y: status.y - height,
+        width,
+        height,
+    };
+    let toast = Paragraph::new(toast)
+        .block(block(app).border_style(app.theme.message))
+        .style(app.theme.message)
+        .alignment(Alignment::Center);
+    f.render_widget(Clear, area);
+    f.render_widget(toast, area);
+}
+
+/// The status line rendered from its template.
+fn draw_status(f: &mut Frame, app: &App, tab: Option<&str>, area: Rect) {
+    let mut filters = String::new();
+    if let Some(level) = app.level {
+        filters.push_str(&format!("Level: {}  ", level.label()));
+    }
+    if let Some(mode) = app.mode {
+        filters.push_str(&format!("Mode: {}  ", mode.label()));
+    }
+    if let Some(category) = &app.category {
+        filters.push_str(&format!("Category: {category}  "));
+    }
+    if app.favorites_only {
+        filters.push_str("Favorites only  ");
+    }
+    if app.recent_only {
+        filters.push_str("Recent  ");
+    }
+    if app.show_hidden {
+        filters.push_str("Showing hidden  ");
+    }
+    if let Some(count) = app.count {
+        filters.push_str(&format!("Count: {count}  "));
+    }
+    if !app.table && (app.list_sort != ListSort::Pack || app.sort_descending) {
+        let reversed = if app.sort_descending {
+            ", reversed"
+        } else {
+            ""
+        };
+        filters.push_str(&format!("Sort: {}{reversed}  ", app.list_sort.label()));
+    }
+    let fields = status::Fields {
+        editor: app.editor.label(),
+        profile: paths::profile().unwrap_or("default"),
+        pack: tab.unwrap_or("all"),
+        filters,
+        total: app.cheats.len(),
+        shown: app.filtered.len(),
+        position: if app.rows.is_empty() {
+            0
+        } else {
+            app.selected + 1
+        },
+    };
+    let status =
+        Paragraph::new(status::render(&app.status_template, &fields)).style(app.theme.status);
+    f.render_widget(status, area);
+}
+
+/// Keys listed in the help overlay.
+enum HelpKeys {
+    Fixed(&'static str),
+    /// The keys bound to these actions, which may be remapped.
+    Bound(&'static [Action]),
+}
+
+use HelpKeys::{Bound, Fixed};
+
+/// Key bindings listed in the help overlay, by section.
+const HELP: &[(&str, &[(HelpKeys, &str)])] = &[
+    (
+        "Search bar",
+        &[
+            (
+                Fixed("type"),
+                "filter the list; see the README for the query syntax",
+            ),
+            (Fixed("Backspace"), "delete the last character"),
+            (Fixed("↑ / ↓"), "recall earlier queries"),
+            (Fixed("Enter"), "submit the query and move to the list"),
+            (Fixed("Esc"), "move to the list, keeping the query"),
+            (Fixed("Tab"), "accept the first completion"),
+        ],
+    ),
+    (
+        "List",
+        &[
+            (
+                Bound(&[Action::Down, Action::Up]),
+                "move the selection (↑ at the top focuses the search bar)",
+            ),
+            (
+                Bound(&[Action::NextMatch, Action::PrevMatch]),
+                "next / previous match, wrapping around",
+            ),
+            (Bound(&[Action::PageUp, Action::PageDown]), "move by a page"),
+            (Fixed("gg"), "jump to the top"),
+            (Bound(&[Action::Bottom]), "jump to the bottom"),
+            (
+                Bound(&[Action::Search, Action::EditSearch]),
+                "start a new search / edit the current one",
+            ),
+            (
+                Bound(&[Action::Details]),
+                "open / close the details (or a group)",
+            ),
+            (
+                Bound(&[Action::Left, Action::Right]),
+                "pick a related command in the details",
+            ),
+            (Bound(&[Action::Left]), "focus the category sidebar"),
+            (
+                Fixed("1-9 / 0"),
+                "show only the nth category (see below) / all",
+            ),
+            (
+                Fixed("{n}G / {n}j / {n}k"),
+                "with row numbers on: go to row n / move n rows",
+            ),
+            (Bound(&[Action::VimHelp]), "open the entry's :help in Vim"),
+            (
+                Bound(&[Action::Favorite]),
+                "star / unstar the selected entry, or star the marked ones",
+            ),
+            (
+                Bound(&[Action::Mark]),
+                "mark / unmark the entry for batch actions",
+            ),
+            (
+                Bound(&[Action::Edit]),
+                "edit the entry (built-in ones as a copy in your pack)",
+            ),
+            (
+                Bound(&[Action::Note]),
+                "write a note on the entry, shown here and searched",
+            ),
+            (
+                Bound(&[Action::Add]),
+                "add a new entry to your personal pack",
+            ),
+            (
+                Bound(&[Action::Copy]),
+                "copy the marked (or selected) commands to the clipboard",
+            ),
+            (Bound(&[Action::FavoritesOnly]), "show only favorites"),
+            (
+                Bound(&[Action::Hide, Action::ShowHidden]),
+                "hide / unhide the entry (or the marked) / list hidden ones",
+            ),
+            (Fixed("gH"), "list all hidden entries to unhide them"),
+            (
+                Bound(&[Action::Undo]),
+                "undo the last star, hide, note, edit or add (Ctrl+r redoes)",
+            ),
+            (
+                Bound(&[Action::Recent]),
+                "show recently opened or copied entries, newest first",
+            ),
+            (Fixed("zi"), "group the list by category"),
+            (Fixed("za / zM / zR"), "collapse / expand one or all groups"),
+            (
+                Bound(&[Action::Table]),
+                "switch between the list and the table",
+            ),
+            (
+                Bound(&[Action::Sort, Action::ReverseSort]),
+                "sort order (list) or column (table) / reverse it",
+            ),
+            (Bound(&[Action::Density]), "compact / comfortable rows"),
+            (
+                Bound(&[Action::Zen]),
+                "focus mode: one entry at a time (n / p to step)",
+            ),
+            (Fixed("gt / gT"), "next / previous tab"),
+            (
+                Bound(&[Action::Close]),
+                "close the details, or clear the marks",
+            ),
+            (Bound(&[Action::Quit]), "quit"),
+        ],
+    ),
+    (
+        "Sidebar",
+        &[
+            (
+                Bound(&[Action::Down, Action::Up]),
+                "pick the category to show",
+            ),
+            (Fixed("1-9 / 0"), "jump to the nth category / all"),
+            (
+                Bound(&[Action::Right, Action::Details, Action::Close]),
+                "back to the list",
+            ),
+        ],
+    ),
+    (
+        "Anywhere",
+        &[
+            (
+                Fixed("Tab"),
+                "cycle the mode filter (when no completion is shown)",
+            ),
+            (Fixed("Ctrl+t"), "cycle the category filter"),
+            (Fixed("Ctrl+l"), "cycle the difficulty filter"),
+            (Fixed("Ctrl+e"), "switch editor profile"),
+            (Fixed("Ctrl+k"), "reverse key lookup"),
+            (Fixed("Ctrl+o"), "show overridden built-in entries"),
+            (Fixed("Ctrl+p"), "command palette: run any action by name"),
+            (Fixed("Ctrl+r"), "redo the last undone change"),
+            (Fixed("Ctrl+arrows"), "resize the sidebar and detail pane"),
+            (Fixed("F1"), "open the entry's :help in Vim"),
+            (Fixed("Ctrl+c"), "quit"),
+        ],
+    ),
+    (
+        "Help",
+        &[
+            (Bound(&[Action::Down, Action::Up]), "scroll"),
+            (
+                Bound(&[Action::PageUp, Action::PageDown]),
+                "scroll by a page",
+            ),
+            (Bound(&[Action::Help, Action::Close, Action::Quit]), "close"),
+        ],
+    ),
+];
+
+/// Width of the key column in the help overlay.
+const HELP_KEY_WIDTH: usize = 24;
+
+/// Lines of the help overlay: a heading per section, one line per key and
+/// a blank line between sections, ending with the categories the number
+/// keys jump to.
+fn help_lines(app: &App) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let line = |key: &str, action: &str| {
+        Line::from(vec![
+            Span::styled(format!("  {key:<HELP_KEY_WIDTH$}"), theme.command),
+            Span::styled(action.to_string(), theme.help),
+        ])
+    };
+    let mut lines = Vec::new();
+    for (i, (section, keys)) in HELP.iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::default());
+        }
+        lines.push(Line::from(Span::styled(*section, theme.title)));
+        for (keys, action) in *keys {
+            let key = match keys {
+                Fixed(key) => key.to_string(),
+                Bound(actions) => actions
+                    .iter()
+                    .map(|&action| app.keymap.describe(action))
+                    .collect::<Vec<_>>()
+                    .join(" / "),
+            };
+            lines.push(line(&key, action));
+        }
+    }
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled("Categories", theme.title)));
+    for (n, (name, _)) in app.categories().iter().take(CATEGORY_KEYS).enumerate() {
+        lines.push(line(&(n + 1).to_string(), name));
+    }
+    lines.push(line("0", "all categories"));
+    lines
+}
+
+/// How far the help overlay drawn in `area` scrolls.
+pub fn help_max_scroll(app: &App, area: Rect) -> usize {
+    help_lines(app)
+        .len()
+        .saturating_sub(area.height.saturating_sub(2) as usize)
+}
+
+/// All key bindings in a scrollable popup centered over the dimmed screen.
+/// Returns the popup's area.
+fn draw_help(f: &mut Frame, app: &App, screen: Rect) -> Rect {
+    dim(f, app, screen);
+    let width = screen.width.saturating_sub(4).min(HELP_MAX_WIDTH);
+    let height = screen.height.saturating_sub(2);
+    let area = Rect {
+        x: screen.x + (screen.width - width) / 2,
+        y: screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    };
+    let scroll = app.help_scroll.min(help_max_scroll(app, area));
+    let help = Paragraph::new(help_lines(app))
+        .block(block(app).title(Span::styled(
+            " Help (j/k to scroll, ? to close) ",
+            app.theme.title,
+        )))
+        .style(app.theme.help)
+        .scroll((scroll as u16, 0));
+    f.render_widget(Clear, area);
+    f.render_widget(help, area);
+    area
+}
+
+/// Widest the command palette gets.
+const PALETTE_MAX_WIDTH: u16 = 70;
+
+/// The command palette near the top of the dimmed screen: the typed
+/// query, then the matching commands with their keys.
+fn draw_palette(f: &mut Frame, app: &App, palette: &Palette, screen: Rect) {
+    dim(f, app, screen);
+    let theme = &app.theme;
+    let commands = palette.matches();
+    let width = screen.width.saturating_sub(4).min(PALETTE_MAX_WIDTH);
+    let height = (commands.len().max(1) as u16 + 4).min(screen.height.saturating_sub(2));
+    let area = Rect {
+        x: screen.x + (screen.width - width) / 2,
+        y: screen.y + 1,
+        width,
+        height,
+    };
+    let block = block(app)
+        .title(Span::styled(
+            " Commands (Enter to run, Esc to close) ",
+            theme.title,
+        ))
+        .border_style(theme.focus_border);
+    let inner = block.inner(area);
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+    let [input, list] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .areas(inner);
+    let prompt = Line::from(vec![
+        Span::styled("> ", theme.muted),
+        Span::styled(palette.query.as_str(), theme.input),
+        Span::styled(" ", theme.cursor),
+    ]);
+    f.render_widget(Paragraph::new(prompt), input);
+
+    if commands.is_empty() {
+        f.render_widget(
+            Paragraph::new(Span::styled("No matching commands", theme.muted)),
+            list,
+        );
+        return;
+    }
+    let words: Vec<String> = palette
+        .query
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+    let items: Vec<ListItem> = commands
+        .iter()
+        .map(|command| {
+            let label = command.label();
+            let keys = command.describe_keys(&app.keymap);
+            let gap = (list.width as usize)
+                .saturating_sub(label.chars().count() + keys.chars().count() + 1);
+            let mut spans = highlighted(label, &words, Style::default(), theme);
+            spans.push(Span::raw(" ".repeat(gap)));
+            spans.push(Span::styled(keys, theme.muted));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let mut state = ListState::default().with_selected(Some(palette.selected));
+    let commands = List::new(items)
+        .highlight_style(theme.suggestion_selected)
+        .highlight_symbol(selection_symbol(app));
+    f.render_stateful_widget(commands, list, &mut state);
+}
+
+/// Widest the entry form gets.
+const FORM_MAX_WIDTH: u16 = 80;
+
+/// The entry form near the top of the dimmed screen: a labelled line per
+/// field, with the cursor in the one being typed into.
+fn draw_form(f: &mut Frame, app: &App, form: &Form, screen: Rect) {
+    dim(f, app, screen);
+    let theme = &app.theme;
+    let width = screen.width.saturating_sub(4).min(FORM_MAX_WIDTH);
+    let height = (form::LABELS.len() as u16 + 2).min(screen.height.saturating_sub(2));
+    let area = Rect {
+        x: screen.x + (screen.width - width) / 2,
+        y: screen.y + 1,
+        width,
+        height,
+    };
+    let label_width = form::LABELS.iter().map(|l| l.len()).max().unwrap_or(0);
+    let lines: Vec<Line> = form::LABELS
+        .iter()
+        .zip(&form.values)
+        .enumerate()
+        .map(|(i, (label, value))| {
+            let focused = i == form.field;
+            let marker = if focused { selection_symbol(app) } else { "" };
+            let mut spans = vec![
+                Span::styled(format!("{marker}{label:>label_width$}: "), theme.muted),
+                Span::styled(value.as_str(), theme.input),
+            ];
+            if focused {
+                spans.push(Span::styled(" ", theme.cursor));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    let fields = Paragraph::new(lines).block(
+        block(app)
+            .title(Span::styled(form.title(), theme.title))
+            .border_style(theme.focus_border),
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(fields, area);
+}
+
+/// The note being written, in a one-line box placed like the entry form,
+/// titled with the entry it is on.
+fn draw_note(f: &mut Frame, app: &App, note: &str, screen: Rect) {
+    let Some(entry) = app.selected_entry() else {
+        return;
+    };
+    dim(f, app, screen);
+    let theme = &app.theme;
+    let width = screen.width.saturating_sub(4).min(FORM_MAX_WIDTH);
+    let area = Rect {
+        x: screen.x + (screen.width - width) / 2,
+        y: screen.y + 1,
+        width,
+        height: 3.min(screen.height),
+    };
+    let title = format!(
+        " Note on {} (Enter to save, empty to remove, Esc to cancel) ",
+        entry.command
+    );
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(note, theme.input),
+        Span::styled(" ", theme.cursor),
+    ]))
+    .block(
+        block(app)
+            .title(Span::styled(title, theme.title))
+            .border_style(theme.focus_border),
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(input, area);
+}
+
+/// Categories with their entry counts; the selected one filters the list.
+/// Returns the scroll offset.
+fn draw_sidebar(f: &mut Frame, app: &App, categories: &[(&str, usize)], area: Rect) -> usize {
+    let mut items = vec![ListItem::new(Line::from(vec![
+        Span::raw("All "),
+        Span::styled(
+            categories
+                .iter()
+                .map(|(_, count)| count)
+                .sum::<usize>()
+                .to_string(),
+            app.theme.muted,
+        ),
+    ]))];
+    items.extend(categories.iter().map(|(name, count)| {
+        let mut spans: Vec<Span> = icon_span(app, name).into_iter().collect();
+        spans.push(Span::styled(format!("{name} "), app.theme.category));
+        spans.push(Span::styled(count.to_string(), app.theme.muted));
+        ListItem::new(Line::from(spans))
+    }));
+    let border = if app.focus == Focus::Sidebar {
+        app.theme.focus_border
+    } else {
+        Style::default()
+    };
+    let sidebar = List::new(items)
+        .block(
+            block(app)
+                .title(Span::styled(" Categories ", app.theme.pane_title))
+                .border_style(border),
+        )
+        .highlight_style(app.theme.sidebar_selection)
+        .highlight_symbol(selection_symbol(app));
+    let mut state = ListState::default().with_selected(Some(app.category_position()));
+    f.render_stateful_widget(sidebar, area, &mut state);
+    state.offset()
+}
+
+/// The selected entry's command and whole description above the results,
+/// so the pick stays in sight while scrolling.
+fn draw_pinned(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let line = match app.selected_index() {
+        Some(idx) => {
+            let c = &app.cheats[idx];
+            Line::from(vec![
+                Span::raw(" "),
+                Span::styled(selection_symbol(app), theme.muted),
+                Span::styled(c.keys().render(Notation::Readable), theme.command),
+                Span::raw("  "),
+                Span::styled(c.description_in(app.lang.as_deref()), theme.description),
+            ])
+        }
+        None => Line::styled(" No entry selected", theme.muted),
+    };
+    f.render_widget(Paragraph::new(line), area);
+}
+
+/// The cheats list, with a scrollbar on its right border when it does not
+/// fit. Returns the scroll offset.
+/// Returns the offset the list is scrolled to, and whether the row under
+/// the mouse pointer was cut off at the right edge.
+fn draw_list(f: &mut Frame, app: &App, area: Rect) -> (usize, bool) {
+    let theme = &app.theme;
+    let narrow = f.area().width < NARROW_WIDTH;
+    let category_terms = app.highlight_terms(Field::Category);
+    let command_terms = app.highlight_terms(Field::Command);
+    let description_terms = app.highlight_terms(Field::Description);
+    let items: Vec<ListItem> = app
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let number = number_span(app, i);
+            let idx = match row {
+                Row::Group { category, count } => {
+                    let marker = if app.collapsed.contains(category) {
+                        glyph(app, "▸ ", "+ ")
+                    } else {
+                        glyph(app, "▾ ", "- ")
+                    };
+                    let mut spans: Vec<Span> = number.into_iter().collect();
+                    spans.push(Span::styled(marker, theme.group));
+                    spans.extend(icon_span(app, category));
+                    spans.extend(highlighted(category, &category_terms, theme.group, theme));
+                    spans.push(Span::styled(format!(" ({count})"), theme.muted));
+                    return ListItem::new(Line::from(spans));
+                }
+                Row::Entry(idx) => *idx,
+            };
+            let c = &app.cheats[idx];
+            let star = if app.favorites.contains(c) {
+                glyph(app, "★ ", "* ")
+            } else {
+                "  "
+            };
+            let mut spans: Vec<Span> = number.into_iter().collect();
+            spans.push(Span::styled(mark(app, idx), theme.marked));
+            spans.push(Span::styled(star, theme.favorite));
+            // Grouped entries sit under their category's heading instead.
+            if !app.grouped && !narrow {
+                spans.push(Span::styled("[", theme.category));
+                spans.extend(icon_span(app, &c.category));
+                spans.extend(highlighted(
+                    &c.category,
+                    &category_terms,
+                    theme.category,
+                    theme,
+                ));
+                spans.push(Span::styled("] ", theme.category));
+            }
+            spans.extend(highlighted(
+                &format!("{:<12}", c.keys().render(Notation::Readable)),
+                &command_terms,
+                theme.command,
+                theme,
+            ));
+            let description = c.description_in(app.lang.as_deref());
+            let difficulty = c.difficulty.map(Difficulty::label);
+            let mut tags: Vec<Span> = difficulty
+                .into_iter()
+                .chain(c.tags.iter().map(String::as_str))
+                .filter(|_| !narrow)
+                .map(|tag| Span::styled(format!("  #{tag}"), theme.muted))
+                .collect();
+            tags.splice(0..0, hidden_span(app, c));
+            if app.density == Density::Compact {
+                // Descriptions too long for the line continue below,
+                // lined up with the first line's.
+                let indent = " ".repeat(compact_prefix_width(app, c, narrow));
+                let mut lines = Vec::new();
+                let width = area.width.saturating_sub(2);
+                for (i, text) in compact_description(app, c, width, narrow).iter().enumerate() {
+                    let mut line = if i == 0 {
+                        std::mem::take(&mut spans)
+                    } else {
+                        vec![Span::raw(indent.clone())]
+                    };
+                    line.push(Span::raw(if i == 0 { " " } else { "" }));
+                    line.extend(highlighted(
+                        text,
+                        &description_terms,
+                        theme.description,
+                        theme,
+                    ));
+                    lines.push(line);
+                }
+                if let Some(last) = lines.last_mut() {
+                    last.extend(tags);
+                }
+                return ListItem::new(lines.into_iter().map(Line::from).collect::<Vec<_>>());
+            }
+            spans.extend(tags);
+            let mut lines = vec![Line::from(spans)];
+            let width = description_width(area.width.saturating_sub(2));
+            for text in wrap(description, width) {
+                let mut spans = vec![Span::raw(COMFORTABLE_INDENT)];
+                spans.extend(highlighted(
+                    &text,
+                    &description_terms,
+                    theme.description,
+                    theme,
+                ));
+                lines.push(Line::from(spans));
+            }
+            lines.push(Line::default());
+            ListItem::new(lines)
+        })
+        .collect();
+    let fits = (area.width.saturating_sub(2) as usize).saturating_sub(HIGHLIGHT_SYMBOL.len());
+    let hover_cut = app
+        .hover
+        .and_then(|hover| items.get(hover.row))
+        .is_some_and(|item| item.width() > fits);
+
+    let cheats_block = List::new(items)
+        .block(block(app).title(Span::styled(app.editor.title(), theme.pane_title)))
+        .highlight_style(theme.selection)
+        .highlight_symbol(HIGHLIGHT_SYMBOL);
+
+    // Scrolled from where the last frame was, as far as the selection needs.
+    let mut state = ListState::default()
+        .with_offset(app.areas.get().list_offset)
+        .with_selected(if app.rows.is_empty() {
+            None
+        } else {
+            Some(app.selected)
+        });
+    f.render_stateful_widget(cheats_block, area, &mut state);
+    let height = area.height.saturating_sub(2) as usize;
+    let width = area.width.saturating_sub(2);
+    let shown = row_at(app, width, narrow, state.offset(), height) - state.offset();
+    draw_scrollbar(f, app, area, 0, shown);
+    (state.offset(), hover_cut)
+}
+
+/// Lines `row` takes in the list, `width` columns wide inside the borders
+/// in the `narrow` layout or not.
+fn row_height(app: &App, row: &Row, width: u16, narrow: bool) -> usize {
+    match row {
+        Row::Entry(_) if app.table => 1,
+        Row::Entry(idx) if app.density == Density::Comfortable => {
+            let description = app.cheats[*idx].description_in(app.lang.as_deref());
+            // Command line, description lines, blank line.
+            wrap(description, description_width(width)).len() + 2
+        }
+        Row::Entry(idx) => compact_description(app, &app.cheats[*idx], width, narrow).len(),
+        Row::Group { .. } => 1,
+    }
+}
+
+/// Columns before the description in a compact row: the star, the
+/// category unless grouped or narrow, and the padded command.
+fn compact_prefix_width(app: &App, c: &CheatEntry, narrow: bool) -> usize {
+    let mut width = number_width(app) + mark_width(app) + 2;
+    if !app.grouped && !narrow {
+        let icon = icon_span(app, &c.category).map_or(0, |icon| icon.width());
+        width += icon + c.category.chars().count() + 3;
+    }
+    width + c.keys().render(Notation::Readable).chars().count().max(12) + 1
+}
+
+/// The description of a compact row, wrapped to the room left of it in
+/// `width` columns; a single line when it fits.
+fn compact_description(app: &App, c: &CheatEntry, width: u16, narrow: bool) -> Vec<String> {
+    let room = (width as usize)
+        .saturating_sub(HIGHLIGHT_SYMBOL.len() + compact_prefix_width(app, c, narrow));
+    wrap(
+        c.description_in(app.lang.as_deref()),
+        room.max(MIN_WRAP_WIDTH),
+    )
+}
+
+/// Index of the row shown `line` lines into the results scrolled to
+/// `offset`, `width` columns wide inside the borders; the number of rows
+/// when `line` is past the last one.
+pub fn row_at(app: &App, width: u16, narrow: bool, offset: usize, line: usize) -> usize {
+    let mut bottom = 0;
+    for (i, row) in app.rows.iter().enumerate().skip(offset) {
+        bottom += row_height(app, row, width, narrow);
+        if line < bottom {
+            return i;
+        }
+    }
+    app.rows.len()
+}
+
+/// Room for descriptions below the command in comfortable rows.
+fn description_width(width: u16) -> usize {
+    (width as usize).saturating_sub(HIGHLIGHT_SYMBOL.len() + COMFORTABLE_INDENT.len())
+}
+
+/// Split `text` at spaces into lines of at most `width` characters; longer
+/// words get a line of their own.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// The cheats as a table with aligned columns, sorted by the column marked
+/// in the header. Returns the scroll offset, and whether a column of the
+/// row under the mouse pointer was cut off.
+fn draw_table(f: &mut Frame, app: &App, area: Rect) -> (usize, bool) {
+    let theme = &app.theme;
+    let narrow = f.area().width < NARROW_WIDTH;
+    let category_terms = app.highlight_terms(Field::Category);
+    let command_terms = app.highlight_terms(Field::Command);
+    let description_terms = app.highlight_terms(Field::Description);
+    let indices: Vec<usize> = app
+        .rows
+        .iter()
+        .filter_map(|row| match row {
+            Row::Entry(idx) => Some(*idx),
+            Row::Group { .. } => None,
+        })
+        .collect();
+    let entries: Vec<&CheatEntry> = indices.iter().map(|&idx| &app.cheats[idx]).collect();
+    let commands: Vec<String> = entries
+        .iter()
+        .map(|c| c.keys().render(Notation::Readable))
+        .collect();
+    let width = |widths: &mut dyn Iterator<Item = usize>| {
+        widths
+            .max()
+            .unwrap_or_default()
+            .clamp(8, TABLE_MAX_COLUMN_WIDTH) as u16
+    };
+    // Narrow screens leave out the category column.
+    let category_width = if narrow {
+        0
+    } else {
+        width(&mut entries.iter().map(|c| {
+            let icon = icon_span(app, &c.category).map_or(0, |icon| icon.width());
+            icon + c.category.chars().count()
+        }))
+    };
+    let command_width = width(&mut commands.iter().map(|c| c.chars().count()));
+    let first_width = (number_width(app) + mark_width(app)) as u16 + 1;
+    // What is left for the description once the other columns and the
+    // spacing between them are laid out.
+    let description_width = (area.width.saturating_sub(2) as usize).saturating_sub(
+        HIGHLIGHT_SYMBOL.len() + (first_width + category_width + command_width) as usize + 3,
+    );
+    let hover_cut = app
+        .hover
+        .and_then(|hover| match app.rows.get(hover.row) {
+            Some(Row::Entry(idx)) => Some(&app.cheats[*idx]),
+            _ => None,
+        })
+        .is_some_and(|c| {
+            let icon = icon_span(app, &c.category).map_or(0, |icon| icon.width());
+            let tags = c
+                .difficulty
+                .map(Difficulty::label)
+                .into_iter()
+                .chain(c.tags.iter().map(String::as_str))
+                .filter(|_| !narrow)
+                .map(|tag| tag.chars().count() + 3);
+            let description = Line::raw(c.description_in(app.lang.as_deref())).width()
+                + hidden_span(app, c).map_or(0, |span| span.width())
+                + tags.sum::<usize>();
+            (!narrow && icon + c.category.chars().count() > category_width as usize)
+                || c.keys().render(Notation::Readable).chars().count() > command_width as usize
+                || description > description_width
+        });
+
+    let rows =
+        entries
+            .iter()
+            .zip(&commands)
+            .zip(indices)
+            .enumerate()
+            .map(|(i, ((c, command), idx))| {
+                let star = if app.favorites.contains(c) {
+                    glyph(app, "★", "*")
+                } else {
+                    ""
+                };
+                let mut description = highlighted(
+                    c.description_in(app.lang.as_deref()),
+                    &description_terms,
+                    theme.description,
+                    theme,
+                );
+                let difficulty = c.difficulty.map(Difficulty::label);
+                let tags = difficulty
+                    .into_iter()
+                    .chain(c.tags.iter().map(String::as_str));
+                description.extend(hidden_span(app, c));
+                for tag in tags.filter(|_| !narrow) {
+                    description.push(Span::styled(format!("  #{tag}"), theme.muted));
+                }
+                let mut category: Vec<Span> = icon_span(app, &c.category).into_iter().collect();
+                category.extend(highlighted(
+                    &c.category,
+                    &category_terms,
+                    theme.category,
+                    theme,
+                ));
+                TableRow::new([
+                    Cell::from(Line::from_iter(number_span(app, i).into_iter().chain([
+                        Span::styled(mark(app, idx), theme.marked),
+                        Span::styled(star, theme.favorite),
+                    ]))),
+                    Cell::from(Line::from(category)),
+                    Cell::from(Line::from(highlighted(
+                        command,
+                        &command_terms,
+                        theme.command,
+                        theme,
+                    ))),
+                    Cell::from(Line::from(description)),
+                ])
+            });
+    let heading = |label: &str, column: SortColumn| {
+        let arrow = match app.sort {
+            Some(sorted) if sorted == column && app.sort_descending => glyph(app, " ▼", " v"),
+            Some(sorted) if sorted == column => glyph(app, " ▲", " ^"),
+            _ => "",
+        };
+        Cell::from(format!("{label}{arrow}"))
+    };
+    let header = TableRow::new([
+        Cell::from(""),
+        heading("Category", SortColumn::Category),
+        heading("Command", SortColumn::Command),
+        heading("Description", SortColumn::Description),
+    ])
+    .style(theme.pane_title);
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(first_width),
+            Constraint::Length(category_width),
+            Constraint::Length(command_width),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .block(block(app).title(Span::styled(app.editor.title(), theme.pane_title)))
+    .highlight_style(theme.selection)
+    .highlight_symbol(HIGHLIGHT_SYMBOL);
+
+    let mut state = TableState::default()
+        .with_offset(app.areas.get().list_offset)
+        .with_selected(if app.rows.is_empty() {
+            None
+        } else {
+            Some(app.selected)
+        });
+    f.render_stateful_widget(table, area, &mut state);
+    draw_scrollbar(f, app, area, 1, area.height.saturating_sub(3) as usize);
+    (state.offset(), hover_cut)
+}
+
+/// Scrollbar on the right border of the results, below `header` lines,
+/// when fewer than all rows are `shown`.
+fn draw_scrollbar(f: &mut Frame, app: &App, area: Rect, header: u16, shown: usize) {
+    let theme = &app.theme;
+    if app.rows.len() > shown && !app.plain {
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .style(theme.scrollbar);
+        let mut position = ScrollbarState::new(app.rows.len())
+            .viewport_content_length(shown)
+            .position(app.selected);
+        let track = area.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        });
+        let track = Rect {
+            y: track.y + header,
+            height: track.height.saturating_sub(header),
+            ..track
+        };
+        f.render_stateful_widget(scrollbar, track, &mut position);
+    }
+}
+
+/// Dropdown of completions for the word being typed, under the search bar
+/// where that word starts.
+fn draw_suggestions(f: &mut Frame, app: &App, input: Rect) {
+    let suggestions = app.suggestions();
+    if suggestions.is_empty() {
+        return;
+    }
+    let word = app.query.split_whitespace().last().unwrap_or_default();
+    let column = (app.query.chars().count() - word.chars().count()) as u16;
+    let width = suggestions
+        .iter()
+        .map(|s| s.chars().count() as u16)
+        .max()
+        .unwrap_or_default()
+        + selection_symbol(app).len() as u16
+        + 2;
+    let screen = f.area();
+    let x = (input.x + column).min(screen.right().saturating_sub(width));
+    let y = input.y + 1;
+    let area = Rect::new(x, y, width, suggestions.len() as u16 + 2).intersection(screen);
+
+    let items: Vec<ListItem> = suggestions
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let (marker, style) = if i == 0 {
+                (selection_symbol(app), app.theme.suggestion_selected)
+            } else {
+                ("", app.theme.suggestion)
+            };
+            ListItem::new(Span::styled(format!("{marker}{s}"), style))
+        })
+        .collect();
+    let dropdown = List::new(items).block(
+        block(app)
+            .title(Span::styled(" Tab ", app.theme.muted))
+            .border_style(app.theme.suggestion),
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(dropdown, area);
+}
+
+/// A bordered box, with blank borders in plain mode.
+fn block(app: &App) -> Block<'static> {
+    let block = Block::default().borders(Borders::ALL);
+    if app.plain {
+        block.border_set(PLAIN_BORDER)
+    } else {
+        block
+    }
+}
+
+/// Marker of entry `idx` in the results: a check mark if it is marked,
+/// blank if not, and nothing at all while no entry is.
+fn mark(app: &App, idx: usize) -> &'static str {
+    if app.marked.is_empty() {
+        ""
+    } else if app.marked.contains(&idx) {
+        glyph(app, "✓ ", "x ")
+    } else {
+        "  "
+    }
+}
+
+/// Label of hidden entries, listed while hidden ones are shown.
+fn hidden_span(app: &App, c: &CheatEntry) -> Option<Span<'static>> {
+    (app.show_hidden && app.hidden.contains(c)).then(|| Span::styled("  (hidden)", app.theme.muted))
+}
+
+/// Columns `mark` takes.
+fn mark_width(app: &App) -> usize {
+    if app.marked.is_empty() {
+        0
+    } else {
+        2
+    }
+}
+
+/// Number of row `i` of the results, padded to the width of the largest;
+/// nothing while row numbers are off.
+fn number_span(app: &App, i: usize) -> Option<Span<'static>> {
+    let number = match app.numbers {
+        Numbers::Off => return None,
+        Numbers::Relative if i != app.selected => i.abs_diff(app.selected),
+        Numbers::Absolute | Numbers::Relative => i + 1,
+    };
+    let width = number_width(app) - 1;
+    Some(Span::styled(format!("{number:>width$} "), app.theme.muted))
+}
+
+/// Columns `number_span` takes.
+fn number_width(app: &App) -> usize {
+    if app.numbers == Numbers::Off {
+        0
+    } else {
+        app.rows.len().max(1).to_string().len() + 1
+    }
+}
+
+/// `glyph`, or `ascii` in plain mode.
+fn glyph(app: &App, glyph: &'static str, ascii: &'static str) -> &'static str {
+    if app.plain {
+        ascii
+    } else {
+        glyph
+    }
+}
+
+/// Symbol before the selected item of lists other than the results,
+/// which only plain mode needs.
+fn selection_symbol(app: &App) -> &'static str {
+    glyph(app, "", PLAIN_SELECTION)
+}
+
+/// Dim the screen under an overlay, unless in plain mode.
+fn dim(f: &mut Frame, app: &App, screen: Rect) {
+    if !app.plain {
+        f.buffer_mut()
+            .set_style(screen, Style::default().add_modifier(Modifier::DIM));
+    }
+}
+
+/// The icon of `category` followed by a space, when it has one.
+fn icon_span(app: &App, category: &str) -> Option<Span<'static>> {
+    let icon = app.icon(category)?;
+    Some(Span::styled(format!("{icon} "), app.theme.category))
+}
+
+/// Split `text` into spans, underlining the characters any of `terms`
+/// match so it is clear why an entry is listed.
+fn highlighted(text: &str, terms: &[String], style: Style, theme: &Theme) -> Vec<Span<'static>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut marked = vec![false; chars.len()];
+    for term in terms {
+        if matcher::relevance(term, text).is_none_or(|s| s <= 0) {
+            continue;
+        }
+        for i in matcher::positions(term, text).into_iter().flatten() {
+            if let Some(m) = marked.get_mut(i) {
+                *m = true;
+            }
+        }
+    }
+    let matched = style.patch(theme.matched);
+    let mut spans = Vec::new();
+    let mut start = 0;
+    for i in 1..=chars.len() {
+        if i == chars.len() || marked[i] != marked[start] {
+            let run: String = chars[start..i].iter().collect();
+            spans.push(Span::styled(
+                run,
+                if marked[start] { matched } else { style },
+            ));
+            start = i;
+        }
+    }
+    spans
+}
+
+/// Full view of the selected entry, including its demo and worked
+/// examples.
+fn draw_detail(f: &mut Frame, app: &App, idx: usize, area: Rect) {
+    let entry = &app.cheats[idx];
+    let theme = &app.theme;
+    let label = theme.muted;
+    let keys = entry.keys();
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(keys.render(Notation::Readable), theme.command),
+            Span::styled(
+                match app.icon(&entry.category) {
+                    Some(icon) => format!("  [{icon} {}]", entry.category),
+                    None => format!("  [{}]", entry.category),
+                },
+                theme.category,
+            ),
+        ]),
+        Line::from(entry.description_in(app.lang.as_deref())),
+    ];
+    if let Some(notes) = &entry.notes {
+        lines.extend(notes.lines().map(Line::from));
+    }
+    if let Some(note) = app.notes.get(entry) {
+        lines.push(Line::from(vec![
+            Span::styled("Your note: ", label),
+            Span::styled(note, theme.annotated),
+        ]));
+    }
+    let difficulty = entry.difficulty.map(Difficulty::label);
+    let tags: Vec<String> = [entry.mode().label()]
+        .into_iter()
+        .chain(difficulty)
+        .chain(entry.tags.iter().map(String::as_str))
+        .map(|t| format!("#{t}"))
+        .collect();
+    if !tags.is_empty() {
+        lines.push(Line::styled(tags.join(" "), label));
+    }
+    let vim_notation = keys.render(Notation::Vim);
+    if vim_notation != keys.render(Notation::Readable) {
+        lines.push(Line::styled(format!("Vim notation: {vim_notation}"), label));
+    }
+    if let Some(topic) = &entry.help_topic {
+        lines.push(Line::styled(format!(":help {topic}  (F1 to open)"), label));
+    }
+
+    if !entry.related.is_empty() {
+        let mut spans = vec![Span::styled("Related: ", label)];
+        for (i, command) in entry.related.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled("  ", label));
+            }
+            let (marker, style) = if app.related == Some(i) {
+                (selection_symbol(app), theme.link_selected)
+            } else {
+                ("", theme.link)
+            };
+            spans.push(Span::styled(format!("{marker}{command}"), style));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if !entry.demo.is_empty() {
+        lines.push(Line::default());
+        lines.push(Line::styled("Demo", theme.title));
+        lines.extend(demo_lines(&entry.demo, demo_frame(app, idx), theme));
+    }
+
+    for (i, example) in entry.examples.iter().enumerate() {
+        lines.push(Line::default());
+        lines.push(Line::styled(format!("Example {}", i + 1), theme.title));
+        lines.extend(example_lines(example, theme));
+    }
+    if entry.examples.is_empty() {
+        lines.push(Line::default());
+        lines.push(Line::styled("No examples for this entry.", label));
+    }
+
+    let title = if entry.related.is_empty() {
+        " Details (Enter to close) "
+    } else {
+        " Details (←/→ pick related, Enter to jump or close) "
+    };
+    let detail = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(block(app).title(Span::styled(title, theme.pane_title)));
+    f.render_widget(detail, area);
+}
+
+fn example_lines<'a>(example: &'a Example, theme: &Theme) -> Vec<Line<'a>> {
+    let label = theme.muted;
+    let mut lines = Vec::new();
+    for (i, buffer_line) in example.before.lines().enumerate() {
+        let prefix = if i == 0 { "  before  " } else { "          " };
+        lines.push(buffer_with_cursor(prefix, buffer_line, theme));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("  keys    ", label),
+        Span::styled(example.keys.as_str(), theme.command),
+    ]));
+    for (i, buffer_line) in example.after.lines().enumerate() {
+        let prefix = if i == 0 { "  after   " } else { "          " };
+        lines.push(buffer_with_cursor(prefix, buffer_line, theme));
+    }
+    lines
+}
+
+/// How long each demo frame stays on screen.
+const DEMO_FRAME: Duration = Duration::from_millis(800);
+/// Extra frames the last one is held for before the demo starts over.
+const DEMO_HOLD_FRAMES: usize = 2;
+
+/// Frame of the demo of entry `idx` to show now, restarting the demo when
+/// another entry's details are shown.
+fn demo_frame(app: &App, idx: usize) -> usize {
+    let now = Instant::now();
+    let started = match app.demo.get() {
+        Some((shown, started)) if shown == idx => started,
+        _ => {
+            app.demo.set(Some((idx, now)));
+            now
+        }
+    };
+    let frame = now.duration_since(started).as_millis() / DEMO_FRAME.as_millis();
+    let frames = app.cheats[idx].demo.len();
+    (frame as usize % (frames + DEMO_HOLD_FRAMES)).min(frames - 1)
+}
+
+/// Frame `frame` of a demo: the keys typed so far and the buffer. Short
+/// buffers are padded to the tallest frame so the lines below stay put.
+fn demo_lines<'a>(demo: &'a [DemoStep], frame: usize, theme: &Theme) -> Vec<Line<'a>> {
+    let typed: Vec<&str> = demo[..=frame]
+        .iter()
+        .map(|step| step.keys.as_str())
+        .filter(|keys| !keys.is_empty())
+        .collect();
+    let mut lines = vec![Line::from(vec![
+        Span::styled("  keys    ", theme.muted),
+        Span::styled(typed.join(" "), theme.command),
+    ])];
+    let height = demo
+        .iter()
+        .map(|step| step.buffer.lines().count())
+        .max()
+        .unwrap_or(0);
+    let buffer: Vec<&str> = demo[frame].buffer.lines().collect();
+    for i in 0..height {
+        let prefix = if i == 0 { "  buffer  " } else { "          " };
+        lines.push(buffer_with_cursor(
+            prefix,
+            buffer.get(i).copied().unwrap_or(""),
+            theme,
+        ));
+    }
+    lines
+}
+
+/// Render a buffer line, drawing the character after `|` as the cursor.
+fn buffer_with_cursor<'a>(prefix: &'a str, text: &'a str, theme: &Theme) -> Line<'a> {
+    let cursor = theme.cursor;
+    let mut spans = vec![Span::styled(prefix, theme.muted)];
+    match text.split_once('|') {
+        Some((before, after)) => {
+            spans.push(Span::raw(before));
+            let mut rest = after.chars();
+            match rest.next() {
+                Some(c) => {
+                    spans.push(Span::styled(c.to_string(), cursor));
+                    spans.push(Span::raw(rest.as_str()));
+                }
+                None => spans.push(Span::styled(" ", cursor)),
+            }
+        }
+        None => spans.push(Span::raw(text)),
+    }
+    Line::from(spans)
+}
+
+/// Entries looked at and copied most, and the searches made most, in place
+/// of the results.
+fn draw_stats(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let (totals, searches) = app.usage.totals();
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "{} looks, {} copies, {searches} searches{}",
+            totals.views,
+            totals.copies,
+            if app.usage.is_persistent() {
+                ""
+            } else {
+                " this session (set `usage = true` in the config file to keep them)"
+            }
+        ),
+        theme.muted,
+    ))];
+    let room = area.height.saturating_sub(2) as usize;
+    let top = app.usage.top(room);
+    if !top.is_empty() {
+        let width = top.iter().map(|(_, command, _)| command.chars().count()).max();
+        let width = width.unwrap_or(0).max(12);
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            format!("{:<heading$}  looks  copies", "Most looked up", heading = width + 2),
+            theme.title,
+        )));
+        for (category, command, counts) in top {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {command:<width$}"), theme.command),
+                Span::raw(format!("  {:>5}  {:>6}  ", counts.views, counts.copies)),
+                Span::styled(format!("[{category}]"), theme.category),
+            ]));
+        }
+    }
+    let searches = app.usage.top_searches(room);
+    if !searches.is_empty() {
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled("Most searched for", theme.title)));
+        for (query, count) in searches {
+            lines.push(Line::from(vec![
+                Span::raw(format!("  {count:>5}  ")),
+                Span::styled(query.to_string(), theme.input),
+            ]));
+        }
+    }
+
+    let report = Paragraph::new(lines).block(block(app).title(Span::styled(
+        " Usage statistics — Esc to close ",
+        theme.pane_title,
+    )));
+    f.render_widget(report, area);
+}
+
+/// Every hidden entry, including those of other editor profiles, with
+/// `selected` picked to unhide.
+fn draw_hidden(f: &mut Frame, app: &App, selected: usize, area: Rect) {
+    let theme = &app.theme;
+    let hidden = app.hidden.entries();
+    let items: Vec<ListItem> = if hidden.is_empty() {
+        vec![ListItem::new(Span::styled("No entries are hidden.", theme.muted))]
+    } else {
+        let width = hidden.iter().map(|(_, command)| command.chars().count()).max();
+        let width = width.unwrap_or(0).min(30);
+        hidden
+            .iter()
+            .map(|&(category, command)| {
+                let entry = app
+                    .cheats
+                    .iter()
+                    .find(|c| c.category == category && c.command == command);
+                let description = match entry {
+                    Some(entry) => Span::raw(entry.description_in(app.lang.as_deref())),
+                    None => Span::styled("(not in this editor profile)", theme.muted),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{command:<width$}  "), theme.command),
+                    description,
+                    Span::styled(format!("  [{category}]"), theme.category),
+                ]))
+            })
+            .collect()
+    };
+    let unhide = app.keymap.describe(Action::Hide);
+    let title = format!(
+        " Hidden entries ({}) — {unhide} or Enter to unhide, Esc to close ",
+        hidden.len()
+    );
+    let mut state = ListState::default().with_selected((!hidden.is_empty()).then_some(selected));
+    let report = List::new(items)
+        .highlight_style(theme.selection)
+        .highlight_symbol(selection_symbol(app))
+        .block(block(app).title(Span::styled(title, theme.pane_title)));
+    f.render_stateful_widget(report, area, &mut state);
+}
+
+/// Report of user entries that override or annotate built-in ones.
+fn draw_conflicts(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let items: Vec<ListItem> = if app.conflicts.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "No user entries override built-in ones.",
+            theme.muted,
+        ))]
+    } else {
+        app.conflicts
+            .iter()
+            .map(|c| {
+                let (verb, style) = if c.annotated {
+                    ("annotated", theme.annotated)
+                } else {
+                    ("overridden", theme.overridden)
+                };
+                ListItem::new(vec![
+                    Line::from(vec![
+                        Span::styled(format!("{:<12}", c.builtin.command), theme.command),
+                        Span::styled(format!(" {verb} "), style),
+                        Span::styled(format!("[{}]", c.user.category), theme.category),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  built-in: ", theme.muted),
+                        Span::raw(c.builtin.description.as_str()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("  yours:    ", theme.muted),
+                        Span::raw(c.user.description.as_str()),
+                    ]),
+                ])
+            })
+            .collect()
+    };
+
+    let report = List::new(items).block(block(app).title(Span::styled(
+        format!(" Overrides ({}) — Ctrl+o to close ", app.conflicts.len()),
+        theme.pane_title,
+    )));
+    f.render_widget(report, area);
+}
@@ -0,0 +1,73 @@
+//! Undo and redo of the changes made to your data from the TUI: stars,
+//! hidden entries, notes and entries edited or added in packs. Each step keeps a
+//! copy of the data from before the change, which undoing restores (and
+//! saves again) as a whole.
+
+use std::path::PathBuf;
+
+use crate::{favorites::Favorites, hidden::Hidden, notes::Notes, CheatEntry};
+
+/// Steps kept, oldest dropped first.
+const MAX_STEPS: usize = 50;
+
+/// The changed data as it was before a step.
+#[derive(Debug)]
+pub enum Snapshot {
+    Favorites(Favorites),
+    Hidden(Hidden),
+    Notes(Notes),
+    /// A pack file's text (`None` if it did not exist yet) and the user
+    /// entries loaded at the time.
+    Pack {
+        path: PathBuf,
+        text: Option<String>,
+        user: Vec<CheatEntry>,
+    },
+}
+
+#[derive(Debug)]
+pub struct Step {
+    /// What the step did (e.g. `hide dd`), for notifications.
+    pub label: String,
+    pub before: Snapshot,
+}
+
+#[derive(Debug, Default)]
+pub struct Undo {
+    done: Vec<Step>,
+    undone: Vec<Step>,
+}
+
+impl Undo {
+    /// Record a new change, which clears the steps that could be redone.
+    pub fn record(&mut self, label: impl Into<String>, before: Snapshot) {
+        self.undone.clear();
+        self.push_done(Step {
+            label: label.into(),
+            before,
+        });
+    }
+
+    /// The latest step, to undo.
+    pub fn undo(&mut self) -> Option<Step> {
+        self.done.pop()
+    }
+
+    /// The latest undone step, to redo.
+    pub fn redo(&mut self) -> Option<Step> {
+        self.undone.pop()
+    }
+
+    /// Keep `step` to undo, e.g. after redoing it.
+    pub fn push_done(&mut self, step: Step) {
+        self.done.push(step);
+        if self.done.len() > MAX_STEPS {
+            self.done.remove(0);
+        }
+    }
+
+    /// Keep `step` to redo after undoing it.
+    pub fn push_undone(&mut self, step: Step) {
+        self.undone.push(step);
+    }
+}
@@ -0,0 +1,315 @@
+//! How often each entry has been looked at (opened in the detail pane or in
+//! `:help`) and copied, so frequently used entries rank higher in search
+//! results, and what was searched for, for the stats view and `vimbo
+//! stats`. Optionally persisted to `usage` in the data directory, one
+//! `views,copies<TAB>category<TAB>command` line per entry (files written
+//! before copies were counted have a bare view count), and to `searches`,
+//! one `count<TAB>query` line per query. Saving writes this instance's
+//! counts of the entries and queries it used over those on disk, the last
+//! writer winning, and keeps what other instances saved for the rest.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use log::warn;
+
+use crate::{paths, store, CheatEntry};
+
+/// Ranking boost per recorded use.
+const BOOST_PER_USE: i32 = 8;
+/// Uses beyond this no longer raise the boost, so a habit cannot bury
+/// better matches for good.
+const MAX_BOOSTED_USES: u32 = 10;
+
+/// Uses of one entry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub views: u32,
+    pub copies: u32,
+}
+
+impl Counts {
+    pub fn total(self) -> u32 {
+        self.views + self.copies
+    }
+}
+
+/// Uses by category, then command.
+type CountMap = HashMap<String, HashMap<String, Counts>>;
+
+#[derive(Debug, Default)]
+pub struct Usage {
+    counts: CountMap,
+    /// Times each query was searched for.
+    searches: HashMap<String, u32>,
+    /// Category and command of the entries used since the last save,
+    /// whose counts replace the saved ones then.
+    changed: HashSet<(String, String)>,
+    /// Queries searched for since the last save.
+    changed_searches: HashSet<String>,
+    /// Where to save the counts, when persisting; the searches go beside
+    /// them.
+    path: Option<PathBuf>,
+}
+
+impl Usage {
+    /// Usage counts that live only for this session.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Usage counts loaded from and saved to the data directory.
+    pub fn persistent() -> Result<Self> {
+        let path = paths::data_dir()?.join("usage");
+        Ok(Self {
+            counts: read_counts(&path),
+            searches: read_searches(&searches_path(&path)),
+            path: Some(path),
+            ..Self::default()
+        })
+    }
+
+    /// Whether the counts are kept across runs.
+    pub fn is_persistent(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// Count one more look at `entry`.
+    pub fn record(&mut self, entry: &CheatEntry) {
+        self.count_use(entry, |counts| counts.views += 1);
+        if let Err(err) = self.save() {
+            warn!("failed to save usage counts: {err:#}");
+        }
+    }
+
+    /// Count one more copy of `entry`.
+    pub fn record_copy(&mut self, entry: &CheatEntry) {
+        self.count_use(entry, |counts| counts.copies += 1);
+        if let Err(err) = self.save() {
+            warn!("failed to save usage counts: {err:#}");
+        }
+    }
+
+    /// Count one more search for `query`.
+    pub fn record_search(&mut self, query: &str) {
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        }
+        *self.searches.entry(query.to_string()).or_default() += 1;
+        self.changed_searches.insert(query.to_string());
+        if let Err(err) = self.save_searches() {
+            warn!("failed to save search counts: {err:#}");
+        }
+    }
+
+    /// Apply `count` to the uses of `entry`, to be saved.
+    fn count_use(&mut self, entry: &CheatEntry, count: fn(&mut Counts)) {
+        count(
+            self.counts
+                .entry(entry.category.clone())
+                .or_default()
+                .entry(entry.command.clone())
+                .or_default(),
+        );
+        self.changed
+            .insert((entry.category.clone(), entry.command.clone()));
+    }
+
+    /// How often `entry` was used, looked at or copied.
+    pub fn count(&self, entry: &CheatEntry) -> u32 {
+        self.counts
+            .get(&entry.category)
+            .and_then(|commands| commands.get(&entry.command))
+            .map_or(0, |counts| counts.total())
+    }
+
+    /// Ranking boost for `entry`, growing with how often it was used.
+    pub fn boost(&self, entry: &CheatEntry) -> i32 {
+        self.count(entry).min(MAX_BOOSTED_USES) as i32 * BOOST_PER_USE
+    }
+
+    /// The `n` most used entries as category, command and counts, most
+    /// used first.
+    pub fn top(&self, n: usize) -> Vec<(&str, &str, Counts)> {
+        let mut top: Vec<_> = self
+            .counts
+            .iter()
+            .flat_map(|(category, commands)| {
+                commands
+                    .iter()
+                    .map(move |(command, &counts)| (category.as_str(), command.as_str(), counts))
+            })
+            .filter(|(.., counts)| counts.total() > 0)
+            .collect();
+        top.sort_by(|a, b| b.2.total().cmp(&a.2.total()).then((a.0, a.1).cmp(&(b.0, b.1))));
+        top.truncate(n);
+        top
+    }
+
+    /// The `n` queries searched for most, with how often, most first.
+    pub fn top_searches(&self, n: usize) -> Vec<(&str, u32)> {
+        let mut top: Vec<_> = self
+            .searches
+            .iter()
+            .map(|(query, &count)| (query.as_str(), count))
+            .collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        top.truncate(n);
+        top
+    }
+
+    /// Looks and copies of all entries, and searches, summed up.
+    pub fn totals(&self) -> (Counts, u32) {
+        let mut totals = Counts::default();
+        for counts in self.counts.values().flat_map(HashMap::values) {
+            totals.views += counts.views;
+            totals.copies += counts.copies;
+        }
+        (totals, self.searches.values().sum())
+    }
+
+    /// Write the counts of the entries used since the last save over the
+    /// saved ones, and take up those other instances saved meanwhile.
+    fn save(&mut self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let counts = store::locked(path, || {
+            let mut counts = read_counts(path);
+            for (category, command) in &self.changed {
+                let ours = self.counts[category][command];
+                counts
+                    .entry(category.clone())
+                    .or_default()
+                    .insert(command.clone(), ours);
+            }
+            let mut lines: Vec<String> = counts
+                .iter()
+                .flat_map(|(category, commands)| {
+                    commands.iter().map(move |(command, counts)| {
+                        format!("{},{}\t{category}\t{command}\n", counts.views, counts.copies)
+                    })
+                })
+                .collect();
+            lines.sort();
+            store::write(path, &lines.concat())?;
+            Ok(counts)
+        })?;
+        self.counts = counts;
+        self.changed.clear();
+        Ok(())
+    }
+
+    /// Like [`Usage::save`], for the searches.
+    fn save_searches(&mut self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let path = searches_path(path);
+        let searches = store::locked(&path, || {
+            let mut searches = read_searches(&path);
+            for query in &self.changed_searches {
+                searches.insert(query.clone(), self.searches[query]);
+            }
+            let mut lines: Vec<String> = searches
+                .iter()
+                .map(|(query, count)| format!("{count}\t{query}\n"))
+                .collect();
+            lines.sort();
+            store::write(&path, &lines.concat())?;
+            Ok(searches)
+        })?;
+        self.searches = searches;
+        self.changed_searches.clear();
+        Ok(())
+    }
+}
+
+/// The counts saved at `path`; none if it cannot be read.
+fn read_counts(path: &Path) -> CountMap {
+    let mut counts = CountMap::new();
+    let Ok(text) = fs::read_to_string(path) else {
+        return counts;
+    };
+    for line in text.lines() {
+        let mut parts = line.splitn(3, '\t');
+        let (Some(count), Some(category), Some(command)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let (views, copies) = count.split_once(',').unwrap_or((count, "0"));
+        if let (Ok(views), Ok(copies)) = (views.parse(), copies.parse()) {
+            let commands = counts.entry(category.to_string()).or_default();
+            commands.insert(command.to_string(), Counts { views, copies });
+        }
+    }
+    counts
+}
+
+/// The searches saved at `path`; none if it cannot be read.
+fn read_searches(path: &Path) -> HashMap<String, u32> {
+    let mut searches = HashMap::new();
+    let Ok(text) = fs::read_to_string(path) else {
+        return searches;
+    };
+    for line in text.lines() {
+        let Some((count, query)) = line.split_once('\t') else {
+            continue;
+        };
+        if let Ok(count) = count.parse() {
+            searches.insert(query.to_string(), count);
+        }
+    }
+    searches
+}
+
+/// Where the searches are saved, beside the counts at `path`.
+fn searches_path(path: &Path) -> PathBuf {
+    path.with_file_name("searches")
+}
+
+/// `vimbo stats`: the `n` most used entries and most searched queries, as
+/// saved in the data directory.
+pub fn print_stats(n: usize) -> Result<()> {
+    let usage = Usage::persistent()?;
+    let (totals, searches) = usage.totals();
+    let mut out = String::new();
+    if totals.total() == 0 && searches == 0 {
+        out.push_str("Nothing recorded yet (set `usage = true` in the config file to count)\n");
+    } else {
+        out.push_str(&format!(
+            "{} looks, {} copies, {searches} searches\n",
+            totals.views, totals.copies
+        ));
+    }
+    let top = usage.top(n);
+    if !top.is_empty() {
+        let width = top.iter().map(|(_, command, _)| command.chars().count()).max();
+        let width = width.unwrap_or(0);
+        out.push_str("\nMost looked up:\n  looks  copies\n");
+        for (category, command, counts) in top {
+            out.push_str(&format!(
+                "  {:>5}  {:>6}  {command:<width$}  {category}\n",
+                counts.views, counts.copies
+            ));
+        }
+    }
+    let searches = usage.top_searches(n);
+    if !searches.is_empty() {
+        out.push_str("\nMost searched for:\n");
+        for (query, count) in searches {
+            out.push_str(&format!("  {count:>5}  {query}\n"));
+        }
+    }
+    match io::stdout().lock().write_all(out.as_bytes()) {
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result.context("failed to write to stdout"),
+    }
+}
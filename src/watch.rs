@@ -0,0 +1,54 @@
+//! Watching the local packs while the TUI is open, so a pack edited in
+//! another window shows up without restarting.
+
+use std::{
+    fs,
+    sync::mpsc::{self, Receiver},
+};
+
+use anyhow::{Context, Result};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::paths;
+
+/// Changes to the `*.toml` files in the packs directory.
+pub struct PackWatcher {
+    /// Stops watching when dropped.
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+}
+
+impl PackWatcher {
+    /// Watch the packs directory, creating it if need be.
+    pub fn new() -> Result<Self> {
+        let dir = paths::packs_dir()?;
+        fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .with_context(|| format!("failed to watch {}", dir.display()))?;
+        Ok(Self {
+            _watcher: watcher,
+            events,
+        })
+    }
+
+    /// Whether a pack was written, created or removed since the last call.
+    /// Takes every change queued so far, so a burst of them (an editor's
+    /// save is often several) counts once.
+    pub fn changed(&self) -> bool {
+        let mut changed = false;
+        for event in self.events.try_iter() {
+            let Ok(event) = event else {
+                continue;
+            };
+            changed |= !event.kind.is_access()
+                && event
+                    .paths
+                    .iter()
+                    .any(|path| path.extension().is_some_and(|ext| ext == "toml"));
+        }
+        changed
+    }
+}